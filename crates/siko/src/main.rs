@@ -2,7 +2,10 @@ use colored::*;
 use siko_compiler::compiler::Compiler;
 use siko_compiler::compiler::CompilerInput;
 use siko_compiler::config::Config;
+use siko_compiler::repl::Repl;
 use std::env;
+use std::fs;
+use std::io::stdin;
 use std::path::Path;
 use walkdir::WalkDir;
 
@@ -38,15 +41,86 @@ fn process_dir(arg: String, inputs: &mut Vec<CompilerInput>) -> bool {
 
 fn print_usage() {
     println!("arguments: OPTIONS FILENAME... ");
+    println!("           repl OPTIONS [FILENAME...]");
+    println!("           fmt [--check] FILENAME...");
     println!("Options:");
     println!("\t-c <path> compile");
     println!("\t-m measure durations");
-    println!("\t-i visualize");
+    println!("\t-j <path> write measured durations as json");
+    println!("\t-i <path> write graphviz files into the given folder");
     println!("\t-s <path> path to std");
+    println!("\t-- <args> pass the remaining arguments to the program");
+    println!("\t--check only report the files that would be reformatted (fmt)");
 }
 
-fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
+fn format_files(args: Vec<String>) -> bool {
     let mut inputs = Vec::new();
+    let mut check = false;
+    let mut success = true;
+    for arg in args {
+        match arg.as_ref() {
+            "--check" => {
+                check = true;
+            }
+            "-h" => {
+                success = false;
+            }
+            _ => {
+                if !process_dir(arg, &mut inputs) {
+                    success = false;
+                    break;
+                }
+            }
+        }
+    }
+    if inputs.is_empty() && success {
+        eprintln!("no file given to format");
+        success = false;
+    }
+    if !success {
+        print_usage();
+        return false;
+    }
+    let mut compiler = Compiler::new(Config::new());
+    for input in inputs {
+        let name = match &input {
+            CompilerInput::File { name } => name.clone(),
+            CompilerInput::Memory { name, .. } => name.clone(),
+        };
+        let formatted = match compiler.format(input) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                compiler.report_error(e);
+                success = false;
+                continue;
+            }
+        };
+        let original = fs::read_to_string(&name).unwrap_or_default();
+        if formatted == original {
+            continue;
+        }
+        if check {
+            eprintln!("{} {} is not formatted", "ERROR:".red(), name.yellow());
+            success = false;
+        } else if let Err(e) = fs::write(&name, formatted) {
+            eprintln!(
+                "{} failed to write {}: {}",
+                "ERROR:".red(),
+                name.yellow(),
+                e
+            );
+            success = false;
+        }
+    }
+    success
+}
+
+fn process_args(
+    args: Vec<String>,
+    file_required: bool,
+) -> (Config, Vec<CompilerInput>, Vec<CompilerInput>, bool) {
+    let mut inputs = Vec::new();
+    let mut std_inputs = Vec::new();
     let mut config = Config::new();
     let mut success = true;
     let mut std_path = format!("std");
@@ -70,8 +144,25 @@ fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
             "-m" => {
                 config.measure_durations = true;
             }
+            "-j" => {
+                if index + 1 >= args.len() {
+                    eprintln!("{} missing path after -j", "ERROR:".red(),);
+                    success = false;
+                    break;
+                } else {
+                    config.durations_json = Some(args[index + 1].to_string());
+                    index += 1;
+                }
+            }
             "-i" => {
-                config.visualize = true;
+                if index + 1 >= args.len() {
+                    eprintln!("{} missing path after -i", "ERROR:".red(),);
+                    success = false;
+                    break;
+                } else {
+                    config.visualize = Some(args[index + 1].to_string());
+                    index += 1;
+                }
             }
             "-s" => {
                 if index + 1 >= args.len() {
@@ -86,6 +177,10 @@ fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
             "-h" => {
                 success = false;
             }
+            "--" => {
+                config.program_args = args[index + 1..].to_vec();
+                break;
+            }
             _ => {
                 file_given = true;
                 if !process_dir(arg.to_string(), &mut inputs) {
@@ -96,14 +191,14 @@ fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
         }
         index += 1;
     }
-    if !file_given {
+    if !file_given && file_required {
         if success {
             eprintln!("no file given to compile");
         }
         success = false;
     }
     if success {
-        if !process_dir(std_path, &mut inputs) {
+        if !process_dir(std_path, &mut std_inputs) {
             success = false;
         }
     }
@@ -111,18 +206,42 @@ fn process_args(args: Vec<String>) -> (Config, Vec<CompilerInput>, bool) {
         print_usage();
     }
     //println!("Compiling {} file(s)", inputs.len());
-    (config, inputs, success)
+    (config, inputs, std_inputs, success)
 }
 
 fn main() {
     let args: Vec<_> = env::args().skip(1).collect();
 
-    let (config, inputs, success) = process_args(args);
+    if args.first().map(|arg| arg == "repl").unwrap_or(false) {
+        let (_, inputs, std_inputs, success) = process_args(args[1..].to_vec(), false);
+        if !success {
+            std::process::exit(1);
+        }
+        match Repl::new(inputs, std_inputs) {
+            Ok(mut repl) => {
+                let stdin = stdin();
+                repl.run(&mut stdin.lock());
+            }
+            Err(_) => std::process::exit(1),
+        }
+        return;
+    }
+
+    if args.first().map(|arg| arg == "fmt").unwrap_or(false) {
+        if !format_files(args[1..].to_vec()) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let (config, mut inputs, std_inputs, success) = process_args(args, true);
 
     if !success {
         std::process::exit(1);
     }
 
+    inputs.extend(std_inputs);
+
     let mut compiler = Compiler::new(config);
 
     if let Err(e) = compiler.compile(inputs) {
@@ -6,11 +6,15 @@ use crate::typedef_store::TypeDefStore;
 use siko_ir::program::Program as IrProgram;
 use siko_ir::types::Type;
 use siko_mir::program::Program as MirProgram;
+use siko_util::durations::Durations;
 
 pub struct Backend {}
 
 impl Backend {
-    pub fn compile(ir_program: &mut IrProgram) -> Result<MirProgram, ()> {
+    pub fn compile(
+        ir_program: &mut IrProgram,
+        durations: &mut Durations,
+    ) -> Result<MirProgram, ()> {
         let mut mir_program = MirProgram::new();
         let mut function_queue = FunctionQueue::new();
         let mut typedef_store = TypeDefStore::new();
@@ -20,8 +24,12 @@ impl Backend {
             FunctionQueueItem::Normal(main_id, context),
             &mut mir_program,
         );
-        function_queue.process_items(ir_program, &mut mir_program, &mut typedef_store);
-        run_passes(&mut mir_program);
+        durations.measure("Monomorphization", |_| {
+            function_queue.process_items(ir_program, &mut mir_program, &mut typedef_store)
+        });
+        durations.measure("Backend passes", |durations| {
+            run_passes(&mut mir_program, durations)
+        });
         Ok(mir_program)
     }
 }
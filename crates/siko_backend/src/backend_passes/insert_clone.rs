@@ -4,8 +4,10 @@ use siko_mir::pattern::Pattern;
 use siko_mir::pattern::PatternId;
 use siko_mir::program::Program;
 use siko_mir::walker::walk_expr;
+use siko_mir::walker::walk_pattern;
 use siko_mir::walker::Visitor;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 enum VarRef {
@@ -47,6 +49,126 @@ impl<'a> Visitor for VarRefCollector<'a> {
     fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
 }
 
+struct LoopCollector<'a> {
+    program: &'a mut Program,
+    loops: Vec<(PatternId, Vec<ExprId>)>,
+}
+
+impl<'a> Visitor for LoopCollector<'a> {
+    fn get_program(&self) -> &Program {
+        return self.program;
+    }
+    fn visit_expr(&mut self, _: ExprId, expr: &Expr) {
+        if let Expr::Loop(pattern, _, items) = expr {
+            self.loops.push((*pattern, items.clone()));
+        }
+    }
+    fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
+}
+
+struct LoopBodyCollector<'a> {
+    program: &'a mut Program,
+    bindings: BTreeSet<PatternId>,
+    refs: Vec<(ExprId, Option<PatternId>)>,
+}
+
+impl<'a> Visitor for LoopBodyCollector<'a> {
+    fn get_program(&self) -> &Program {
+        return self.program;
+    }
+    fn visit_expr(&mut self, expr_id: ExprId, expr: &Expr) {
+        match expr {
+            Expr::ArgRef(_) => {
+                self.refs.push((expr_id, None));
+            }
+            Expr::ExprValue(_, pattern) => {
+                self.refs.push((expr_id, Some(*pattern)));
+            }
+            _ => {}
+        }
+    }
+    fn visit_pattern(&mut self, pattern_id: PatternId, pattern: &Pattern) {
+        if let Pattern::Binding(_) = pattern {
+            self.bindings.insert(pattern_id);
+        }
+    }
+}
+
+// Values defined outside of a loop are moved in every iteration if they are used
+// in the loop body, so they must be cloned even if they are used only once.
+fn collect_loop_refs(expr_id: &ExprId, program: &mut Program) -> BTreeSet<ExprId> {
+    let mut loop_collector = LoopCollector {
+        program: program,
+        loops: Vec::new(),
+    };
+    walk_expr(expr_id, &mut loop_collector);
+    let loops = loop_collector.loops;
+    let mut loop_refs = BTreeSet::new();
+    for (pattern, items) in loops {
+        let mut collector = LoopBodyCollector {
+            program: program,
+            bindings: BTreeSet::new(),
+            refs: Vec::new(),
+        };
+        walk_pattern(&pattern, &mut collector);
+        for item in &items {
+            walk_expr(item, &mut collector);
+        }
+        for (ref_expr_id, pattern) in collector.refs {
+            match pattern {
+                Some(pattern) if collector.bindings.contains(&pattern) => {}
+                _ => {
+                    loop_refs.insert(ref_expr_id);
+                }
+            }
+        }
+    }
+    loop_refs
+}
+
+struct GuardCollector<'a> {
+    program: &'a mut Program,
+    guards: Vec<ExprId>,
+}
+
+impl<'a> Visitor for GuardCollector<'a> {
+    fn get_program(&self) -> &Program {
+        return self.program;
+    }
+    fn visit_expr(&mut self, _: ExprId, _: &Expr) {}
+    fn visit_pattern(&mut self, _: PatternId, pattern: &Pattern) {
+        if let Pattern::Guarded(_, guard) = pattern {
+            self.guards.push(*guard);
+        }
+    }
+}
+
+// Bindings cannot be moved in a pattern guard, so every value used in a guard is cloned.
+fn collect_guard_refs(expr_id: &ExprId, program: &mut Program) -> BTreeSet<ExprId> {
+    let mut guard_collector = GuardCollector {
+        program: program,
+        guards: Vec::new(),
+    };
+    walk_expr(expr_id, &mut guard_collector);
+    let guards = guard_collector.guards;
+    let mut guard_refs = BTreeSet::new();
+    for guard in guards {
+        let mut collector = LoopBodyCollector {
+            program: program,
+            bindings: BTreeSet::new(),
+            refs: Vec::new(),
+        };
+        walk_expr(&guard, &mut collector);
+        guard_refs.extend(
+            collector
+                .refs
+                .into_iter()
+                .map(|(ref_expr_id, _)| ref_expr_id),
+        );
+    }
+    guard_refs
+}
+
 pub fn insert_clone_pass(expr_id: &ExprId, program: &mut Program) {
     let mut collector = VarRefCollector {
         program: program,
@@ -54,17 +176,20 @@ pub fn insert_clone_pass(expr_id: &ExprId, program: &mut Program) {
     };
     walk_expr(expr_id, &mut collector);
     let refs = collector.refs;
+    let mut clones = collect_loop_refs(expr_id, program);
+    clones.extend(collect_guard_refs(expr_id, program));
     for (_, exprs) in refs {
         if exprs.len() == 1 {
             continue;
         }
-        for expr_id in exprs {
-            let location = program.exprs.get(&expr_id).location_id;
-            let new_ref = program.exprs.get(&expr_id).item.clone();
-            let ty = program.get_expr_type(&expr_id).clone();
-            let new_ref_id = program.add_expr(new_ref, location, ty);
-            let clone = Expr::Clone(new_ref_id);
-            program.update_expr(expr_id, clone);
-        }
+        clones.extend(exprs);
+    }
+    for expr_id in clones {
+        let location = program.exprs.get(&expr_id).location_id;
+        let new_ref = program.exprs.get(&expr_id).item.clone();
+        let ty = program.get_expr_type(&expr_id).clone();
+        let new_ref_id = program.add_expr(new_ref, location, ty);
+        let clone = Expr::Clone(new_ref_id);
+        program.update_expr(expr_id, clone);
     }
 }
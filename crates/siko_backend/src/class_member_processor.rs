@@ -106,6 +106,7 @@ pub fn generate_auto_derived_instance_member(
                         kind: NamedFunctionKind::Free,
                         location_id: location,
                         type_signature: None,
+                        multi_param_constraints: Vec::new(),
                         module: adt.module.clone(),
                         name: format!("{:?}", derived_class),
                     };
@@ -184,6 +185,7 @@ pub fn generate_auto_derived_instance_member(
                         kind: NamedFunctionKind::Free,
                         location_id: location,
                         type_signature: None,
+                        multi_param_constraints: Vec::new(),
                         module: record.module.clone(),
                         name: format!("{:?}", derived_class),
                     };
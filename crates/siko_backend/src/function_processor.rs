@@ -1,10 +1,14 @@
 use crate::expr_processor::process_expr;
+use crate::function_queue::CallContext;
 use crate::function_queue::FunctionQueue;
 use crate::function_queue::FunctionQueueItem;
 use crate::type_processor::process_type;
 use crate::typedef_store::TypeDefStore;
 use crate::util::get_call_unifier;
 use crate::util::preprocess_ir;
+use siko_constants::ACTOR_CLASS_NAME;
+use siko_constants::STD_ACTOR_MODULE_NAME;
+use siko_ir::class::ClassId;
 use siko_ir::data::TypeDef;
 use siko_ir::function::FunctionId as IrFunctionId;
 use siko_ir::function::FunctionInfo;
@@ -17,6 +21,50 @@ use siko_mir::function::FunctionInfo as MirFunctionInfo;
 use siko_mir::program::Program as MirProgram;
 use std::collections::BTreeMap;
 
+fn get_single_field_type(ty: &Type, ir_program: &IrProgram) -> Type {
+    let record_type_info = ir_program
+        .record_type_info_map
+        .get(&ty.get_typedef_id())
+        .expect("Record type info not found");
+    record_type_info.field_types[0].0.clone()
+}
+
+// Actor and Protocol instances have no members, instead the transpiler needs
+// to know about every actor and protocol type and the handler functions of the actors.
+fn process_actor_constraint(
+    class_id: ClassId,
+    ty: &Type,
+    ir_program: &mut IrProgram,
+    mir_program: &mut MirProgram,
+    function_queue: &mut FunctionQueue,
+    typedef_store: &mut TypeDefStore,
+) {
+    let class = ir_program.classes.get(&class_id);
+    let mir_typedef_id = typedef_store.add_type(ty.clone(), ir_program, mir_program);
+    if class.name == ACTOR_CLASS_NAME {
+        let actor = ir_program
+            .get_actor_by_type(ty.get_typedef_id())
+            .expect("Actor not found")
+            .clone();
+        let state_ty = get_single_field_type(ty, ir_program);
+        let mut handlers = BTreeMap::new();
+        for handler in &actor.handlers {
+            let protocol = ir_program.protocols.get(&handler.protocol_id);
+            let protocol_ty = Type::Named(protocol.name.clone(), protocol.typedef_id, Vec::new());
+            let payload_ty = get_single_field_type(&protocol_ty, ir_program);
+            let protocol_typedef_id = typedef_store.add_type(protocol_ty, ir_program, mir_program);
+            mir_program.protocols.insert(protocol_typedef_id);
+            let context = CallContext::new(vec![state_ty.clone(), payload_ty], state_ty.clone());
+            let queue_item = FunctionQueueItem::Normal(handler.function_id, context);
+            let mir_function_id = function_queue.insert(queue_item, mir_program);
+            handlers.insert(protocol_typedef_id, mir_function_id);
+        }
+        mir_program.actors.insert(mir_typedef_id, handlers);
+    } else {
+        mir_program.protocols.insert(mir_typedef_id);
+    }
+}
+
 pub fn process_function(
     ir_function_id: &IrFunctionId,
     mir_function_id: MirFunctionId,
@@ -76,6 +124,18 @@ pub fn process_function(
                         constraint.ty.get_resolved_type_string(ir_program)
                     );
                     */
+                    if ir_program.classes.get(&constraint.class_id).module == STD_ACTOR_MODULE_NAME
+                    {
+                        process_actor_constraint(
+                            constraint.class_id,
+                            &constraint.ty,
+                            ir_program,
+                            mir_program,
+                            function_queue,
+                            typedef_store,
+                        );
+                        continue;
+                    }
                     if constraint.class_id == ir_program.get_ord_class_id() {
                         let queue_item = FunctionQueueItem::ExternalCallImpl(
                             ir_program.get_partialeq_class_id(),
@@ -155,7 +215,36 @@ pub fn process_function(
             let lambda_name = lambda_name.replace(".", "_");
             let lambda_name = lambda_name.replace("#", "_");
             let mir_function = MirFunction {
-                name: lambda_name,
+                name: format!("{}_{}", lambda_name, mir_function_id.id),
+                module: info.module.clone(),
+                function_type: mir_function_type,
+                arg_count: function.arg_count,
+                info: MirFunctionInfo::Normal(mir_body),
+            };
+            mir_program
+                .functions
+                .add_item(mir_function_id, mir_function);
+        }
+        FunctionInfo::LocalFunction(info) => {
+            preprocess_ir(info.body, ir_program);
+            let mut expr_id_map = BTreeMap::new();
+            let mut pattern_id_map = BTreeMap::new();
+            let mir_body = process_expr(
+                &info.body,
+                ir_program,
+                mir_program,
+                &call_unifier,
+                function_queue,
+                typedef_store,
+                &mut expr_id_map,
+                &mut pattern_id_map,
+            );
+            let local_name = format!("{}", info);
+            let local_name = local_name.replace("/", "_");
+            let local_name = local_name.replace(".", "_");
+            let local_name = local_name.replace("#", "_");
+            let mir_function = MirFunction {
+                name: format!("{}_{}", local_name, mir_function_id.id),
                 module: info.module.clone(),
                 function_type: mir_function_type,
                 arg_count: function.arg_count,
@@ -5,23 +5,32 @@ use crate::backend_passes::process_data_types::process_data_types;
 use crate::backend_passes::process_static_calls::process_static_calls_pass;
 use siko_mir::function::FunctionInfo;
 use siko_mir::program::Program;
+use siko_util::durations::Durations;
 
-pub fn run_passes(program: &mut Program) {
-    process_data_types(program);
+pub fn run_passes(program: &mut Program, durations: &mut Durations) {
+    durations.measure("Process data types", |_| process_data_types(program));
     let mut bodies = Vec::new();
     for (_, function) in program.functions.items.iter() {
         if let FunctionInfo::Normal(body) = function.info {
             bodies.push(body);
         }
     }
-    for body in &bodies {
-        box_convert_pass(body, program);
-    }
-    for body in &bodies {
-        process_static_calls_pass(body, program);
-    }
-    for body in &bodies {
-        insert_clone_pass(body, program);
-    }
-    convert_args_to_closures(program);
+    durations.measure("Box convert", |_| {
+        for body in &bodies {
+            box_convert_pass(body, program);
+        }
+    });
+    durations.measure("Process static calls", |_| {
+        for body in &bodies {
+            process_static_calls_pass(body, program);
+        }
+    });
+    durations.measure("Insert clone", |_| {
+        for body in &bodies {
+            insert_clone_pass(body, program);
+        }
+    });
+    durations.measure("Convert args to closures", |_| {
+        convert_args_to_closures(program)
+    });
 }
@@ -67,6 +67,9 @@ pub fn process_pattern(
         IrPattern::CharRange(start, end, kind) => {
             MirPattern::CharRange(start.clone(), end.clone(), to_mir_kind(kind))
         }
+        IrPattern::IntegerRange(start, end, kind) => {
+            MirPattern::IntegerRange(start.clone(), end.clone(), to_mir_kind(kind))
+        }
         IrPattern::Record(_, items) => {
             let mir_typedef_id = typedef_store.add_type(ir_pattern_ty, ir_program, mir_program);
             let mir_items: Vec<_> = items
@@ -138,6 +141,47 @@ pub fn process_pattern(
             MirPattern::Variant(mir_typedef_id, *index, mir_items)
         }
         IrPattern::Wildcard => MirPattern::Wildcard,
+        IrPattern::List(items) => {
+            let mir_items: Vec<_> = items
+                .iter()
+                .map(|item| {
+                    process_pattern(
+                        item,
+                        ir_program,
+                        mir_program,
+                        unifier,
+                        function_queue,
+                        typedef_store,
+                        expr_id_map,
+                        pattern_id_map,
+                    )
+                })
+                .collect();
+            MirPattern::List(mir_items)
+        }
+        IrPattern::Cons(head, tail) => {
+            let mir_head = process_pattern(
+                head,
+                ir_program,
+                mir_program,
+                unifier,
+                function_queue,
+                typedef_store,
+                expr_id_map,
+                pattern_id_map,
+            );
+            let mir_tail = process_pattern(
+                tail,
+                ir_program,
+                mir_program,
+                unifier,
+                function_queue,
+                typedef_store,
+                expr_id_map,
+                pattern_id_map,
+            );
+            MirPattern::Cons(mir_head, mir_tail)
+        }
     };
     let mir_pattern_id =
         mir_program.add_pattern(mir_pattern, item_info.location_id, mir_pattern_ty);
@@ -1,51 +1,79 @@
 use crate::config::Config;
 use crate::error::Error;
+use crate::visualizer::visualize_ir;
+use crate::visualizer::visualize_mir;
 use siko_backend::backend::Backend;
 use siko_interpreter::interpreter::Interpreter;
+use siko_ir::program::Program as IrProgram;
 use siko_location_info::error_context::ErrorContext;
 use siko_location_info::file_manager::FileManager;
 use siko_location_info::filepath::FilePath;
 use siko_location_info::location_info::LocationInfo;
 use siko_name_resolver::resolver::Resolver;
+use siko_parser::error::LexerError;
+use siko_parser::error::ParseError;
+use siko_parser::formatter::format;
+use siko_parser::formatter::get_token_texts;
 use siko_parser::lexer::Lexer;
 use siko_parser::parser::Parser;
+use siko_parser::token::Comment;
+use siko_parser::token::TokenInfo;
+use siko_syntax::module::ModuleId;
 use siko_syntax::program::Program;
 use siko_transpiler::transpiler::Transpiler;
 use siko_type_checker::typechecker::Typechecker;
+use siko_util::durations::Durations;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
 
 pub enum CompilerInput {
-    File {
-        name: String,
-    },
-    #[allow(unused)]
-    Memory {
-        name: String,
-        content: String,
-    },
+    File { name: String },
+    Memory { name: String, content: String },
 }
 
-fn parse(
+fn lex(
     content: &str,
     file_path: FilePath,
-    program: &mut Program,
-    location_info: &mut LocationInfo,
-) -> Result<(), Error> {
+    lexer_errors: &mut Vec<LexerError>,
+) -> Option<Vec<TokenInfo>> {
     //println!("Compiling {}", file_path.path);
-    let mut lexer = Lexer::new(content, file_path.clone());
+    let mut lexer = Lexer::new(content, file_path);
     let mut errors = Vec::new();
-    let tokens = match lexer.process(&mut errors) {
+    match lexer.process(&mut errors) {
         Ok(tokens) => {
             if errors.is_empty() {
-                tokens
+                Some(tokens)
             } else {
-                return Err(Error::LexerError(errors));
+                lexer_errors.extend(errors);
+                None
             }
         }
         Err(e) => {
-            errors.push(e);
-            return Err(Error::LexerError(errors));
+            lexer_errors.extend(errors);
+            lexer_errors.push(e);
+            None
         }
-    };
+    }
+}
+
+fn get_comment_texts(comments: &[Comment]) -> Vec<String> {
+    comments
+        .iter()
+        .map(|comment| {
+            let lines: Vec<_> = comment.text.lines().map(|line| line.trim_end()).collect();
+            lines.join("\n")
+        })
+        .collect()
+}
+
+fn parse(
+    tokens: &[TokenInfo],
+    file_path: FilePath,
+    program: &mut Program,
+    location_info: &mut LocationInfo,
+    parse_errors: &mut Vec<ParseError>,
+) {
     /*
     let t: Vec<_> = tokens
         .iter()
@@ -53,15 +81,17 @@ fn parse(
         .collect();
     println!("Tokens {:?}", t);
     */
-    let mut parser = Parser::new(file_path, &tokens[..], program, location_info);
-    parser.parse()?;
-    Ok(())
+    let mut parser = Parser::new(file_path, tokens, program, location_info);
+    if let Err(errors) = parser.parse() {
+        parse_errors.extend(errors);
+    }
 }
 
 pub struct Compiler {
     file_manager: FileManager,
     location_info: LocationInfo,
     config: Config,
+    durations: Durations,
 }
 
 impl Compiler {
@@ -70,10 +100,11 @@ impl Compiler {
             file_manager: FileManager::new(),
             location_info: LocationInfo::new(),
             config: config,
+            durations: Durations::new(),
         }
     }
 
-    pub fn compile(&mut self, inputs: Vec<CompilerInput>) -> Result<(), Error> {
+    pub fn parse(&mut self, inputs: Vec<CompilerInput>) -> Result<Program, Error> {
         let mut program = Program::new();
         for input in inputs.iter() {
             match input {
@@ -86,35 +117,216 @@ impl Compiler {
                 }
             }
         }
-        for (file_path, content) in self.file_manager.files.iter() {
+        let mut lexer_errors = Vec::new();
+        let mut parse_errors = Vec::new();
+        let files = &self.file_manager.files;
+        let location_info = &mut self.location_info;
+        let tokens: Vec<_> = self.durations.measure("Lexing", |durations| {
+            files
+                .iter()
+                .map(|(file_path, content)| {
+                    durations.measure(&file_path.path, |_| {
+                        lex(content, file_path.clone(), &mut lexer_errors)
+                    })
+                })
+                .collect()
+        });
+        self.durations.measure("Parsing", |durations| {
+            for ((file_path, _), tokens) in files.iter().zip(tokens) {
+                if let Some(tokens) = tokens {
+                    durations.measure(&file_path.path, |_| {
+                        parse(
+                            &tokens[..],
+                            file_path.clone(),
+                            &mut program,
+                            location_info,
+                            &mut parse_errors,
+                        )
+                    });
+                }
+            }
+        });
+
+        if !lexer_errors.is_empty() || !parse_errors.is_empty() {
+            return Err(Error::SyntaxError(lexer_errors, parse_errors));
+        }
+
+        Ok(program)
+    }
+
+    // Parses one more source into an already parsed program and returns the ids of
+    // its modules, the earlier sources are not parsed again.
+    pub fn parse_into(
+        &mut self,
+        input: CompilerInput,
+        program: &mut Program,
+    ) -> Result<Vec<ModuleId>, Error> {
+        let file_path = match input {
+            CompilerInput::File { name } => {
+                let file_path = FilePath::new(name);
+                self.file_manager.read(file_path.clone())?;
+                file_path
+            }
+            CompilerInput::Memory { name, content } => {
+                let file_path = FilePath::new(name);
+                self.file_manager
+                    .add_from_memory(file_path.clone(), content);
+                file_path
+            }
+        };
+        let content = self.file_manager.content(&file_path).to_string();
+        let mut lexer_errors = Vec::new();
+        let mut parse_errors = Vec::new();
+        let existing_modules: BTreeSet<_> = program.modules.items.keys().cloned().collect();
+        if let Some(tokens) = lex(&content, file_path.clone(), &mut lexer_errors) {
             parse(
-                content,
-                file_path.clone(),
-                &mut program,
+                &tokens[..],
+                file_path,
+                program,
                 &mut self.location_info,
-            )?;
+                &mut parse_errors,
+            );
+        }
+        if !lexer_errors.is_empty() || !parse_errors.is_empty() {
+            return Err(Error::SyntaxError(lexer_errors, parse_errors));
         }
+        Ok(program
+            .modules
+            .items
+            .keys()
+            .filter(|id| !existing_modules.contains(id))
+            .cloned()
+            .collect())
+    }
 
+    // The formatted source must be lexed into the same tokens, blocks and comments
+    // as the original one, otherwise the formatting is rejected.
+    pub fn format(&mut self, input: CompilerInput) -> Result<String, Error> {
+        let file_path = match input {
+            CompilerInput::File { name } => {
+                let file_path = FilePath::new(name);
+                self.file_manager.read(file_path.clone())?;
+                file_path
+            }
+            CompilerInput::Memory { name, content } => {
+                let file_path = FilePath::new(name);
+                self.file_manager
+                    .add_from_memory(file_path.clone(), content);
+                file_path
+            }
+        };
+        let content = self.file_manager.content(&file_path).to_string();
+        let mut lexer = Lexer::new(&content, file_path.clone());
+        let mut lexer_errors = Vec::new();
+        let tokens = match lexer.process(&mut lexer_errors) {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                lexer_errors.push(e);
+                Vec::new()
+            }
+        };
+        if !lexer_errors.is_empty() {
+            return Err(Error::SyntaxError(lexer_errors, Vec::new()));
+        }
+        let mut parse_errors = Vec::new();
+        parse(
+            &tokens[..],
+            file_path.clone(),
+            &mut Program::new(),
+            &mut self.location_info,
+            &mut parse_errors,
+        );
+        if !parse_errors.is_empty() {
+            return Err(Error::SyntaxError(Vec::new(), parse_errors));
+        }
+        let comments = lexer.get_comments();
+        let formatted = format(&content, &tokens[..], comments);
+        let mut formatted_lexer = Lexer::new(&formatted, file_path.clone());
+        let mut formatted_errors = Vec::new();
+        let same = match formatted_lexer.process(&mut formatted_errors) {
+            Ok(formatted_tokens) => {
+                formatted_errors.is_empty()
+                    && get_token_texts(&content, &tokens[..])
+                        == get_token_texts(&formatted, &formatted_tokens[..])
+                    && get_comment_texts(comments)
+                        == get_comment_texts(formatted_lexer.get_comments())
+            }
+            Err(_) => false,
+        };
+        if !same {
+            return Err(Error::FormatError(file_path));
+        }
+        Ok(formatted)
+    }
+
+    pub fn check(&mut self, program: &Program) -> Result<IrProgram, Error> {
         let mut resolver = Resolver::new();
-        let mut ir_program = resolver.resolve(&program)?;
+        let mut ir_program = self
+            .durations
+            .measure("Name resolution", |_| resolver.resolve(program))?;
+
+        let typechecker = Typechecker::new(self.config.main_required);
 
-        let typechecker = Typechecker::new();
+        self.durations.measure("Type checking", |durations| {
+            typechecker.check(&mut ir_program, durations)
+        })?;
 
-        typechecker.check(&mut ir_program)?;
+        if let Some(dir) = &self.config.visualize {
+            visualize_ir(&ir_program, &resolver.get_module_imports(), Path::new(dir))?;
+        }
+
+        Ok(ir_program)
+    }
+
+    fn run(&mut self, inputs: Vec<CompilerInput>) -> Result<(), Error> {
+        let program = self.parse(inputs)?;
+
+        let mut ir_program = self.check(&program)?;
 
         if let Some(compile_target) = &self.config.compile {
-            let mir_program = Backend::compile(&mut ir_program);
+            let mir_program = self.durations.measure("Backend", |durations| {
+                Backend::compile(&mut ir_program, durations)
+            });
             let mir_program = mir_program.expect("TODO");
-            Transpiler::process(&mir_program, compile_target).expect("Transpiler failed");
+            if let Some(dir) = &self.config.visualize {
+                visualize_mir(&mir_program, Path::new(dir))?;
+            }
+            self.durations
+                .measure("Transpilation", |_| {
+                    Transpiler::process(&mir_program, compile_target)
+                })
+                .expect("Transpiler failed");
         } else {
-            Interpreter::run(ir_program, self.context());
+            let program_args = self.config.program_args.clone();
+            self.durations.measure("Interpretation", |_| {
+                Interpreter::run(ir_program, program_args)
+            })?;
         }
 
         //println!("Result {}", value);
         Ok(())
     }
 
-    fn context(&self) -> ErrorContext {
+    pub fn compile(&mut self, inputs: Vec<CompilerInput>) -> Result<(), Error> {
+        let result = self.run(inputs);
+        if self.config.measure_durations {
+            eprintln!("{}", self.durations.format_table());
+        }
+        if let Some(path) = &self.config.durations_json {
+            fs::write(path, self.durations.to_json())?;
+        }
+        result
+    }
+
+    pub fn get_location_info(&self) -> &LocationInfo {
+        &self.location_info
+    }
+
+    pub fn get_file_manager(&self) -> &FileManager {
+        &self.file_manager
+    }
+
+    pub fn context(&self) -> ErrorContext {
         ErrorContext {
             file_manager: self.file_manager.clone(),
             location_info: self.location_info.clone(),
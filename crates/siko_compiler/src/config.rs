@@ -1,15 +1,21 @@
 pub struct Config {
     pub measure_durations: bool,
-    pub visualize: bool,
+    pub durations_json: Option<String>,
+    pub visualize: Option<String>,
     pub compile: Option<String>,
+    pub main_required: bool,
+    pub program_args: Vec<String>,
 }
 
 impl Config {
     pub fn new() -> Config {
         Config {
             measure_durations: false,
-            visualize: false,
+            durations_json: None,
+            visualize: None,
             compile: None,
+            main_required: true,
+            program_args: Vec::new(),
         }
     }
 }
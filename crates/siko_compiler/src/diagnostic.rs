@@ -0,0 +1,122 @@
+use crate::error::get_resolver_error_location;
+use crate::error::get_typecheck_error_location;
+use crate::error::report_resolver_error;
+use crate::error::report_typecheck_error;
+use crate::error::Error;
+use siko_location_info::error_context::ErrorContext;
+use siko_location_info::filepath::FilePath;
+use siko_location_info::location::Location;
+use siko_location_info::location_id::LocationId;
+use siko_location_info::location_info::LocationInfo;
+use siko_parser::error::LexerError;
+
+// lines and columns are zero based, the end column is exclusive
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub file_path: FilePath,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl Diagnostic {
+    fn from_location(message: String, file_path: &FilePath, location: &Location) -> Diagnostic {
+        Diagnostic {
+            message: message,
+            file_path: file_path.clone(),
+            start: (location.line, location.span.start),
+            end: (location.line, location.span.end),
+        }
+    }
+
+    fn from_location_id(
+        message: String,
+        location_info: &LocationInfo,
+        location_id: LocationId,
+    ) -> Diagnostic {
+        let location_set = location_info.get_item_location(&location_id);
+        Diagnostic {
+            message: message,
+            file_path: location_set.file_path.clone(),
+            start: location_set.get_start(),
+            end: location_set.get_end(),
+        }
+    }
+}
+
+// The printed form of an error starts with its message, the source snippets
+// printed after it all start with "-- ". Colors should be disabled by the caller.
+fn get_message(rendered: Vec<u8>) -> String {
+    let rendered = String::from_utf8_lossy(&rendered).to_string();
+    let lines: Vec<_> = rendered
+        .lines()
+        .take_while(|line| !line.starts_with("-- "))
+        .collect();
+    let message = lines.join("\n");
+    match message.strip_prefix("ERROR: ") {
+        Some(message) => message.to_string(),
+        None => message,
+    }
+}
+
+pub fn get_diagnostics(error: &Error, context: &ErrorContext) -> Vec<Diagnostic> {
+    let file_manager = &context.file_manager;
+    let location_info = &context.location_info;
+    let mut diagnostics = Vec::new();
+    match error {
+        Error::SyntaxError(lexer_errors, parse_errors) => {
+            for err in lexer_errors {
+                match err {
+                    LexerError::General(msg, file_path, location) => {
+                        let diagnostic =
+                            Diagnostic::from_location(msg.clone(), file_path, location);
+                        diagnostics.push(diagnostic);
+                    }
+                    LexerError::UnsupportedCharacter(c, location) => {
+                        let msg = format!("unsupported character {}", c);
+                        let diagnostic =
+                            Diagnostic::from_location(msg, &location.file_path, &location.location);
+                        diagnostics.push(diagnostic);
+                    }
+                }
+            }
+            for err in parse_errors {
+                let diagnostic =
+                    Diagnostic::from_location(err.msg.clone(), &err.file_path, &err.location);
+                diagnostics.push(diagnostic);
+            }
+        }
+        Error::ResolverError(errs) => {
+            for err in &errs.errors {
+                if let Some(location_id) = get_resolver_error_location(err) {
+                    let mut rendered = Vec::new();
+                    report_resolver_error(err, file_manager, location_info, &mut rendered)
+                        .expect("Failed to render error");
+                    let msg = get_message(rendered);
+                    let diagnostic = Diagnostic::from_location_id(msg, location_info, location_id);
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+        Error::TypecheckError(errs) => {
+            for err in &errs.errors {
+                if let Some(location_id) = get_typecheck_error_location(err) {
+                    let mut rendered = Vec::new();
+                    report_typecheck_error(err, file_manager, location_info, &mut rendered)
+                        .expect("Failed to render error");
+                    let msg = get_message(rendered);
+                    let diagnostic = Diagnostic::from_location_id(msg, location_info, location_id);
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+        Error::RuntimeError(err) => {
+            let diagnostic =
+                Diagnostic::from_location_id(err.msg.clone(), location_info, err.location_id);
+            diagnostics.push(diagnostic);
+        }
+        Error::IoError(_) => {}
+        Error::FormatError(_) => {}
+    }
+    diagnostics
+}
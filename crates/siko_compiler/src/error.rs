@@ -1,11 +1,13 @@
 use colored::*;
 use siko_constants::MAIN_FUNCTION;
 use siko_constants::MAIN_MODULE_NAME;
+use siko_interpreter::error::RuntimeError;
 use siko_location_info::error_context::ErrorContext;
 use siko_location_info::file_manager::FileManager;
 use siko_location_info::filepath::FilePath;
 use siko_location_info::location::Location;
 use siko_location_info::location_id::LocationId;
+use siko_location_info::location_info::LocationInfo;
 use siko_location_info::location_set::LocationSet;
 use siko_name_resolver::error::Error as ResolverErrorContainer;
 use siko_name_resolver::error::ResolverError;
@@ -16,7 +18,10 @@ use siko_type_checker::error::TypecheckError;
 use siko_util::format_list;
 use std::cmp;
 use std::convert::From;
+use std::io::stderr;
 use std::io::Error as IoError;
+use std::io::Result as IoResult;
+use std::io::Write;
 
 fn s_from_range(chars: &[char], start: usize, end: usize) -> String {
     let start = cmp::min(start, end);
@@ -25,7 +30,11 @@ fn s_from_range(chars: &[char], start: usize, end: usize) -> String {
     s
 }
 
-fn print_location_set(file_manager: &FileManager, location_set: &LocationSet) {
+fn print_location_set(
+    out: &mut dyn Write,
+    file_manager: &FileManager,
+    location_set: &LocationSet,
+) -> IoResult<()> {
     let input = file_manager.content(&location_set.file_path);
     let lines: Vec<_> = input.lines().collect();
     let mut first = true;
@@ -35,715 +44,1114 @@ fn print_location_set(file_manager: &FileManager, location_set: &LocationSet) {
         last_line = *line_index;
         if first {
             first = false;
-            eprintln!(
+            writeln!(
+                out,
                 "{}{}:{}",
                 "-- ".blue(),
                 location_set.file_path.path.green(),
                 format!("{}", line_index + 1).green()
-            );
+            )?;
             if *line_index != 0 {
                 let line = &lines[*line_index - 1];
-                eprintln!("{} {}", pipe.blue(), line);
+                writeln!(out, "{} {}", pipe.blue(), line)?;
             }
         }
         let line = &lines[*line_index];
         let chars: Vec<_> = line.chars().collect();
         let first = s_from_range(&chars[..], 0, ranges[0].start);
-        eprint!("{} {}", pipe.blue(), first);
+        write!(out, "{} {}", pipe.blue(), first)?;
         for (index, range) in ranges.iter().enumerate() {
             let s = s_from_range(&chars[..], range.start, range.end);
-            eprint!("{}", s.yellow());
+            write!(out, "{}", s.yellow())?;
             if index < ranges.len() - 1 {
                 let s = s_from_range(&chars[..], range.end, ranges[index + 1].start);
-                eprint!("{}", s);
+                write!(out, "{}", s)?;
             }
         }
         let last = s_from_range(&chars[..], ranges[ranges.len() - 1].end, chars.len());
-        eprintln!("{}", last);
+        writeln!(out, "{}", last)?;
     }
     if last_line + 1 < lines.len() {
         let line = &lines[last_line + 1];
-        eprintln!("{} {}", pipe.blue(), line);
+        writeln!(out, "{} {}", pipe.blue(), line)?;
     }
+    Ok(())
+}
+
+fn location_sort_key(
+    location_info: &LocationInfo,
+    location_id: Option<LocationId>,
+) -> (bool, String, usize) {
+    match location_id {
+        Some(id) => {
+            let location_set = location_info.get_item_location(&id);
+            let line = location_set.lines.keys().next().cloned().unwrap_or(0);
+            (false, location_set.file_path.path.clone(), line)
+        }
+        None => (true, String::new(), 0),
+    }
+}
+
+fn format_kind(arity: usize) -> String {
+    let stars: Vec<_> = (0..=arity).map(|_| "*").collect();
+    stars.join(" -> ")
+}
+
+fn print_suggestions(out: &mut dyn Write, suggestions: &Vec<String>) -> IoResult<()> {
+    if !suggestions.is_empty() {
+        writeln!(out, "did you mean {}?", format_list(suggestions).yellow())?;
+    }
+    Ok(())
+}
+
+pub fn get_resolver_error_location(err: &ResolverError) -> Option<LocationId> {
+    match err {
+        ResolverError::ModuleConflict(errors) => errors
+            .values()
+            .next()
+            .and_then(|ids| ids.iter().next().cloned()),
+        ResolverError::InternalModuleConflicts(_, _, ids) => ids.first().cloned(),
+        ResolverError::ImportedModuleNotFound(_, _, id) => Some(*id),
+        ResolverError::UnknownTypeName(_, _, id) => Some(*id),
+        ResolverError::UnknownTypeArg(_, id) => Some(*id),
+        ResolverError::TypeArgumentConflict(_, id) => Some(*id),
+        ResolverError::ArgumentConflict(_, id) => Some(*id),
+        ResolverError::LambdaArgumentConflict(_, id) => Some(*id),
+        ResolverError::UnknownFunction(_, _, id) => Some(*id),
+        ResolverError::AmbiguousName(_, id) => Some(*id),
+        ResolverError::UnusedTypeArgument(_, id) => Some(*id),
+        ResolverError::RecordFieldNotUnique(_, _, id) => Some(*id),
+        ResolverError::VariantNotUnique(_, _, id) => Some(*id),
+        ResolverError::ExportNoMatch(_, _, id) => Some(*id),
+        ResolverError::ImportNoMatch(_, _, id) => Some(*id),
+        ResolverError::IncorrectTypeArgumentCount(_, _, _, id) => Some(*id),
+        ResolverError::NameNotType(_, id) => Some(*id),
+        ResolverError::UnusedHiddenItem(_, _, id) => Some(*id),
+        ResolverError::UnknownFieldName(_, _, id) => Some(*id),
+        ResolverError::NotIrrefutablePattern(id) => Some(*id),
+        ResolverError::NotRecordType(_, id) => Some(*id),
+        ResolverError::NoSuchField(_, _, id) => Some(*id),
+        ResolverError::MissingFields(_, id) => Some(*id),
+        ResolverError::FieldsInitializedMultipleTimes(_, id) => Some(*id),
+        ResolverError::NoRecordFoundWithFields(_, id) => Some(*id),
+        ResolverError::NotAClassName(_, id) => Some(*id),
+        ResolverError::InvalidArgumentInTypeClassConstraint(_, id) => Some(*id),
+        ResolverError::NotAClassMember(_, _, id) => Some(*id),
+        ResolverError::MissingClassMemberInInstance(_, _, id) => Some(*id),
+        ResolverError::ClassMemberTypeArgMissing(_, _, id) => Some(*id),
+        ResolverError::ExtraConstraintInClassMember(_, id) => Some(*id),
+        ResolverError::ConflictingDefaultClassMember(_, _, ids) => ids.first().cloned(),
+        ResolverError::ConflictingFunctionTypesInModule(_, _, ids) => ids.first().cloned(),
+        ResolverError::DefaultClassMemberWithoutType(_, _, id) => Some(*id),
+        ResolverError::InstanceMemberWithoutImplementation(_, id) => Some(*id),
+        ResolverError::ConflictingInstanceMemberFunction(_, ids) => ids.first().cloned(),
+        ResolverError::ConflictingFunctionTypesInInstance(_, ids) => ids.first().cloned(),
+        ResolverError::FunctionTypeWithoutImplementationInModule(_, _, id) => Some(*id),
+        ResolverError::InvalidClassArgument(id) => Some(*id),
+        ResolverError::InvalidTypeArgInInstanceConstraint(_, id) => Some(*id),
+        ResolverError::NamedInstancedNotUnique(_, _, id) => Some(*id),
+        ResolverError::PatternBindConflict(_, ids) => ids.first().cloned(),
+        ResolverError::PatternBindNotPresent(_, id) => Some(*id),
+        ResolverError::ContinueOutsideLoop(id) => Some(*id),
+        ResolverError::BreakOutsideLoop(id) => Some(*id),
+        ResolverError::NotAProtocolName(_, id) => Some(*id),
+        ResolverError::ConflictingProtocolHandler(_, _, id) => Some(*id),
+        ResolverError::KindMismatch(_, _, _, id) => Some(*id),
+        ResolverError::InvalidInstanceKind(_, _, id) => Some(*id),
+        ResolverError::ConflictingLocalFunction(_, ids) => ids.first().cloned(),
+        ResolverError::RecursiveLocalValue(_, id) => Some(*id),
+        ResolverError::IncorrectClassArgumentCount(_, _, _, id) => Some(*id),
+        ResolverError::MultiParamConstraintNotAllowed(_, id) => Some(*id),
+        ResolverError::InstanceMemberTypeMismatch(_, _, _, id) => Some(*id),
+    }
+}
+
+pub fn get_typecheck_error_location(err: &TypecheckError) -> Option<LocationId> {
+    match err {
+        TypecheckError::ConflictingInstances(_, id, _) => Some(*id),
+        TypecheckError::DeriveFailureNoInstanceFound(_, _, id) => Some(*id),
+        TypecheckError::DeriveFailureInstanceNotGeneric(_, _, id) => Some(*id),
+        TypecheckError::UntypedExternFunction(_, id) => Some(*id),
+        TypecheckError::FunctionArgAndSignatureMismatch(_, _, _, id, _) => Some(*id),
+        TypecheckError::MainNotFound => None,
+        TypecheckError::IncorrectTypeForMain(_, id) => Some(*id),
+        TypecheckError::TypeMismatch(id, _, _) => Some(*id),
+        TypecheckError::FunctionArgumentMismatch(id, _, _) => Some(*id),
+        TypecheckError::InvalidVariantPattern(id, _, _, _) => Some(*id),
+        TypecheckError::InvalidRecordPattern(id, _, _, _) => Some(*id),
+        TypecheckError::TypeAnnotationNeeded(id) => Some(*id),
+        TypecheckError::InvalidFormatString(id) => Some(*id),
+        TypecheckError::CyclicClassDependencies(id, _) => Some(*id),
+        TypecheckError::MissingInstance(_, id) => Some(*id),
+        TypecheckError::ClassNotAutoDerivable(_, id) => Some(*id),
+        TypecheckError::UnreachablePattern(id, _) => Some(*id),
+        TypecheckError::NonExhaustivePattern(_, id) => Some(*id),
+        TypecheckError::EmptyRangePattern(id) => Some(*id),
+        TypecheckError::InvalidProtocolHandler(_, _, _, id) => Some(*id),
+        TypecheckError::TypedHole(_, _, _, _, id) => Some(*id),
+    }
+}
+
+pub fn report_resolver_error(
+    err: &ResolverError,
+    file_manager: &FileManager,
+    location_info: &LocationInfo,
+    out: &mut dyn Write,
+) -> IoResult<()> {
+    let error = "ERROR:";
+    match err {
+        ResolverError::ModuleConflict(errors) => {
+            for (name, ids) in errors.iter() {
+                writeln!(
+                    out,
+                    "{} module name {} defined more than once",
+                    error.red(),
+                    name.yellow()
+                )?;
+                for id in ids.iter() {
+                    let location_set = location_info.get_item_location(id);
+                    print_location_set(out, file_manager, location_set)?;
+                }
+            }
+        }
+        ResolverError::ImportedModuleNotFound(name, suggestions, id) => {
+            writeln!(
+                out,
+                "{} imported module {} does not exist",
+                error.red(),
+                name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            print_suggestions(out, suggestions)?;
+        }
+        ResolverError::UnknownTypeName(var_name, suggestions, id) => {
+            writeln!(
+                out,
+                "{} unknown type name {}",
+                error.red(),
+                var_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            print_suggestions(out, suggestions)?;
+        }
+        ResolverError::UnknownTypeArg(var_name, id) => {
+            writeln!(
+                out,
+                "{} unknown type argument {}",
+                error.red(),
+                var_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::TypeArgumentConflict(args, id) => {
+            writeln!(
+                out,
+                "{} type argument(s) are not unique: {}",
+                error.red(),
+                format_list(args).yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ArgumentConflict(args, id) => {
+            writeln!(
+                out,
+                "{} argument(s) are not unique: {}",
+                error.red(),
+                format_list(args).yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::LambdaArgumentConflict(args, id) => {
+            writeln!(
+                out,
+                "{} lambda argument(s) {} are not unique",
+                error.red(),
+                format_list(args).yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::UnknownFunction(var_name, suggestions, id) => {
+            writeln!(
+                out,
+                "{} unknown function {}",
+                error.red(),
+                var_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            print_suggestions(out, suggestions)?;
+        }
+        ResolverError::AmbiguousName(var_name, id) => {
+            writeln!(out, "{} ambiguous name {}", error.red(), var_name.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::UnusedTypeArgument(arg, id) => {
+            writeln!(
+                out,
+                "{} unused type argument: {}",
+                error.red(),
+                arg.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::InternalModuleConflicts(module_name, name, locations) => {
+            writeln!(
+                out,
+                "{} conflicting items named {} in module {}",
+                error.red(),
+                name.yellow(),
+                module_name.yellow()
+            )?;
+            for id in locations {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::RecordFieldNotUnique(record_name, item_name, id) => {
+            writeln!(
+                out,
+                "{} field name {} is not unique in record {}",
+                error.red(),
+                item_name.yellow(),
+                record_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::VariantNotUnique(adt_name, variant_name, id) => {
+            writeln!(
+                out,
+                "{} variant name {} is not unique in type {}",
+                error.red(),
+                variant_name.yellow(),
+                adt_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ExportNoMatch(module_name, entity_name, id) => {
+            writeln!(
+                out,
+                "{} item {} does not export anything in module {}",
+                error.red(),
+                entity_name.yellow(),
+                module_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ImportNoMatch(module_name, entity_name, id) => {
+            writeln!(
+                out,
+                "{} item {} does not import anything from module {}",
+                error.red(),
+                entity_name.yellow(),
+                module_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::IncorrectTypeArgumentCount(type_name, expected, found, id) => {
+            writeln!(
+                out,
+                "{} incorrect type argument count for type {}",
+                error.red(),
+                type_name.yellow(),
+            )?;
+            let expected = format!("{}", expected);
+            let found = format!("{}", found);
+            writeln!(out, "Expected: {}", expected.yellow())?;
+            writeln!(out, "Found:    {}", found.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NameNotType(name, id) => {
+            writeln!(out, "{} name is not a type {}", error.red(), name.yellow(),)?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::UnusedHiddenItem(hidden_item, module_name, id) => {
+            writeln!(
+                out,
+                "{} hidden item {} does not hide anything from module {}",
+                error.red(),
+                hidden_item.yellow(),
+                module_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::UnknownFieldName(field_name, suggestions, id) => {
+            writeln!(
+                out,
+                "{} unknown field name {}",
+                error.red(),
+                field_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            print_suggestions(out, suggestions)?;
+        }
+        ResolverError::NotIrrefutablePattern(id) => {
+            writeln!(out, "{} not irrefutable pattern", error.red(),)?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NotRecordType(name, id) => {
+            writeln!(
+                out,
+                "{} {} is not a record type",
+                error.red(),
+                name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NoSuchField(record, field_name, id) => {
+            writeln!(
+                out,
+                "{} there is no field named {} in {}",
+                error.red(),
+                field_name.yellow(),
+                record.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::MissingFields(missing_fields, id) => {
+            writeln!(
+                out,
+                "{} missing initialization of the following field(s): {}",
+                error.red(),
+                format_list(missing_fields).yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::FieldsInitializedMultipleTimes(fields_initialized_twice, id) => {
+            writeln!(
+                out,
+                "{} the following field(s) are initialized multiple times: {}",
+                error.red(),
+                format_list(fields_initialized_twice).yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NoRecordFoundWithFields(fields, id) => {
+            writeln!(
+                out,
+                "{} no record found that has all the following field(s): {}",
+                error.red(),
+                format_list(fields).yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NotAClassName(name, id) => {
+            writeln!(out, "{} {} is not a class", error.red(), name.yellow(),)?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::InvalidArgumentInTypeClassConstraint(arg, id) => {
+            writeln!(
+                out,
+                "{} class constraint argument {} is unknown type argument",
+                error.red(),
+                arg.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NotAClassMember(member_name, class_name, id) => {
+            writeln!(
+                out,
+                "{} {} is not a member of class {}",
+                error.red(),
+                member_name.yellow(),
+                class_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::MissingClassMemberInInstance(member_name, class_name, id) => {
+            writeln!(
+                out,
+                "{} class member {} of class {} is missing in instance",
+                error.red(),
+                member_name.yellow(),
+                class_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ClassMemberTypeArgMissing(member_name, class_arg, id) => {
+            writeln!(
+                    out,
+                    "{} type arguments of class member {} does not contain the type argument of class: {}",
+                    error.red(),
+                    member_name.yellow(),
+                    class_arg.yellow(),
+                )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ExtraConstraintInClassMember(member_name, id) => {
+            writeln!(
+                out,
+                "{} extra type constraint in class member {}",
+                error.red(),
+                member_name.yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ConflictingDefaultClassMember(class, name, locations) => {
+            writeln!(
+                out,
+                "{} conflicting default implementations for class member {} in class {}",
+                error.red(),
+                name.yellow(),
+                class.yellow()
+            )?;
+            for id in locations {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::DefaultClassMemberWithoutType(class, name, id) => {
+            writeln!(
+                out,
+                "{} class member {} in class {} has no type signature",
+                error.red(),
+                name.yellow(),
+                class.yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ConflictingFunctionTypesInModule(module, name, locations) => {
+            writeln!(
+                out,
+                "{} conflicting function types named {} in module {}",
+                error.red(),
+                name.yellow(),
+                module.yellow()
+            )?;
+            for id in locations {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::InstanceMemberWithoutImplementation(name, id) => {
+            writeln!(
+                out,
+                "{} instance member {} has no implementation",
+                error.red(),
+                name.yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ConflictingInstanceMemberFunction(name, locations) => {
+            writeln!(
+                out,
+                "{} conflicting instance member function named {}",
+                error.red(),
+                name.yellow(),
+            )?;
+            for id in locations {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::ConflictingFunctionTypesInInstance(name, locations) => {
+            writeln!(
+                out,
+                "{} conflicting function types named {} in instance",
+                error.red(),
+                name.yellow(),
+            )?;
+            for id in locations {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::FunctionTypeWithoutImplementationInModule(module, name, id) => {
+            writeln!(
+                out,
+                "{} function type {} has no implementation in module {}",
+                error.red(),
+                name.yellow(),
+                module.yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::InvalidClassArgument(id) => {
+            writeln!(
+                out,
+                "{} invalid class argument, must be a single type argument",
+                error.red(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::InvalidTypeArgInInstanceConstraint(arg, id) => {
+            writeln!(
+                out,
+                "{} instance constraint argument {} is unknown type argument",
+                error.red(),
+                arg.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NamedInstancedNotUnique(module, instance, id) => {
+            writeln!(
+                out,
+                "{} named instance {} is not unique in module {}",
+                error.red(),
+                instance.yellow(),
+                module.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::PatternBindConflict(name, ids) => {
+            writeln!(
+                out,
+                "{} multiple variable named {} found",
+                error.red(),
+                name.yellow(),
+            )?;
+            for id in ids {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::PatternBindNotPresent(name, id) => {
+            writeln!(
+                out,
+                "{} variable {} not present in all patterns in or pattern",
+                error.red(),
+                name.yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ContinueOutsideLoop(id) => {
+            writeln!(out, "{} continue outside of a loop", error.red())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::BreakOutsideLoop(id) => {
+            writeln!(out, "{} break outside of a loop", error.red())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::NotAProtocolName(name, id) => {
+            writeln!(out, "{} {} is not a protocol", error.red(), name.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ConflictingProtocolHandler(actor, protocol, id) => {
+            writeln!(
+                out,
+                "{} actor {} has multiple handlers for protocol {}",
+                error.red(),
+                actor.yellow(),
+                protocol.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::KindMismatch(type_arg, expected, found, id) => {
+            writeln!(
+                out,
+                "{} kind mismatch for type argument {}",
+                error.red(),
+                type_arg.yellow(),
+            )?;
+            let expected = format_kind(*expected);
+            let found = format_kind(*found);
+            writeln!(out, "Expected: {}", expected.yellow())?;
+            writeln!(out, "Found:    {}", found.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::InvalidInstanceKind(class_name, arity, id) => {
+            let kind = format_kind(*arity);
+            writeln!(
+                out,
+                "{} instance type of class {} must have kind {}",
+                error.red(),
+                class_name.yellow(),
+                kind.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::ConflictingLocalFunction(name, locations) => {
+            writeln!(
+                out,
+                "{} conflicting local definitions named {}",
+                error.red(),
+                name.yellow(),
+            )?;
+            for id in locations {
+                let location_set = location_info.get_item_location(id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        ResolverError::RecursiveLocalValue(names, id) => {
+            writeln!(
+                out,
+                "{} local value(s) depend on themselves: {}",
+                error.red(),
+                format_list(names).yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::IncorrectClassArgumentCount(class_name, expected, found, id) => {
+            writeln!(
+                out,
+                "{} incorrect argument count for class {}",
+                error.red(),
+                class_name.yellow(),
+            )?;
+            let expected = format!("{}", expected);
+            let found = format!("{}", found);
+            writeln!(out, "Expected: {}", expected.yellow())?;
+            writeln!(out, "Found:    {}", found.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::MultiParamConstraintNotAllowed(class_name, id) => {
+            writeln!(
+                out,
+                "{} constraint of multi parameter class {} is not allowed here",
+                error.red(),
+                class_name.yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        ResolverError::InstanceMemberTypeMismatch(member_name, expected, found, id) => {
+            writeln!(
+                out,
+                "{} type signature of instance member {} does not match the class member",
+                error.red(),
+                member_name.yellow(),
+            )?;
+            writeln!(out, "Expected: {}", expected.yellow())?;
+            writeln!(out, "Found:    {}", found.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn report_typecheck_error(
+    err: &TypecheckError,
+    file_manager: &FileManager,
+    location_info: &LocationInfo,
+    out: &mut dyn Write,
+) -> IoResult<()> {
+    let error = "ERROR:";
+    match err {
+        TypecheckError::ConflictingInstances(name, id1, id2) => {
+            writeln!(
+                out,
+                "{} conflicting class instances for class {}",
+                error.red(),
+                name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id1);
+            print_location_set(out, file_manager, location_set)?;
+            let location_set = location_info.get_item_location(id2);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::DeriveFailureNoInstanceFound(type_name, class_name, id) => {
+            writeln!(
+                out,
+                "{} auto derive failure, no instance found for class {} for a member of {}",
+                error.red(),
+                class_name.yellow(),
+                type_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::DeriveFailureInstanceNotGeneric(type_name, class_name, id) => {
+            writeln!(
+                out,
+                "{} auto derive failure, instance not generic for class {} for a member of {}",
+                error.red(),
+                class_name.yellow(),
+                type_name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::UntypedExternFunction(name, id) => {
+            writeln!(
+                out,
+                "{} extern function {} does not have a type signature",
+                error.red(),
+                name.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::FunctionArgAndSignatureMismatch(
+            name,
+            arg_count,
+            signature_arg_count,
+            id,
+            is_member,
+        ) => {
+            if *is_member {
+                writeln!(
+                    out,
+                    "{} member function type signature of {} does not match its argument count",
+                    error.red(),
+                    name.yellow()
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "{} function type signature of {} does not match its argument count",
+                    error.red(),
+                    name.yellow()
+                )?;
+            }
+            writeln!(
+                out,
+                "Arguments:                      {}",
+                format!("{}", arg_count).yellow()
+            )?;
+            writeln!(
+                out,
+                "Arguments in type signature:    {}",
+                format!("{}", signature_arg_count).yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::MainNotFound => {
+            writeln!(
+                out,
+                "{} {} function in module {} not found",
+                error.red(),
+                "main".yellow(),
+                "Main".yellow()
+            )?;
+        }
+        TypecheckError::TypeMismatch(id, expected, found) => {
+            writeln!(out, "{} type mismatch in expression", error.red())?;
+            writeln!(out, "Expected: {}", expected.yellow())?;
+            writeln!(out, "Found:    {}", found.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::FunctionArgumentMismatch(id, args, func) => {
+            writeln!(out, "{} invalid argument(s)", error.red())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            writeln!(out, "Argument(s):      {}", args.yellow())?;
+            writeln!(out, "Function type:    {}", func.yellow())?;
+        }
+        TypecheckError::InvalidVariantPattern(id, name, expected, found) => {
+            writeln!(
+                out,
+                "{} invalid {} variant pattern, argument count mismatch",
+                error.red(),
+                name.yellow()
+            )?;
+            writeln!(out, "Expected:      {}", format!("{}", expected).yellow())?;
+            writeln!(out, "Found:         {}", format!("{}", found).yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::InvalidRecordPattern(id, name, expected, found) => {
+            writeln!(
+                out,
+                "{} invalid {} record pattern, argument count mismatch",
+                error.red(),
+                name.yellow()
+            )?;
+            writeln!(out, "Expected:      {}", format!("{}", expected).yellow())?;
+            writeln!(out, "Found:         {}", format!("{}", found).yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::TypeAnnotationNeeded(id) => {
+            writeln!(out, "{} Type annotation needed", error.red())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::InvalidFormatString(id) => {
+            writeln!(out, "{} invalid format string", error.red())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::CyclicClassDependencies(id, path) => {
+            writeln!(
+                out,
+                "{} cyclic class dependencies: {}",
+                error.red(),
+                path.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::MissingInstance(class, id) => {
+            writeln!(
+                out,
+                "{} missing instance of {}",
+                error.red(),
+                class.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::ClassNotAutoDerivable(class, id) => {
+            writeln!(
+                out,
+                "{} class {} is not auto derivable",
+                error.red(),
+                class.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::IncorrectTypeForMain(ty, id) => {
+            writeln!(
+                out,
+                "{} {} in module {} has type {} instead of {}",
+                error.red(),
+                MAIN_FUNCTION.yellow(),
+                MAIN_MODULE_NAME.yellow(),
+                ty.yellow(),
+                "()".yellow(),
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::UnreachablePattern(id, covering_id) => {
+            writeln!(out, "{} unreachable pattern", error.red(),)?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            if let Some(covering_id) = covering_id {
+                writeln!(out, "already matched by")?;
+                let location_set = location_info.get_item_location(covering_id);
+                print_location_set(out, file_manager, location_set)?;
+            }
+        }
+        TypecheckError::NonExhaustivePattern(missing_patterns, id) => {
+            writeln!(out, "{} non exhaustive pattern", error.red(),)?;
+            for missing_pattern in missing_patterns {
+                writeln!(out, "Missing: {}", missing_pattern.yellow())?;
+            }
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::EmptyRangePattern(id) => {
+            writeln!(out, "{} empty range pattern", error.red(),)?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+        TypecheckError::TypedHole(name, ty, bindings, candidates, id) => {
+            writeln!(
+                out,
+                "{} found hole {} of type {}",
+                error.red(),
+                format!("?{}", name).yellow(),
+                ty.yellow()
+            )?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+            if !bindings.is_empty() {
+                writeln!(out, "Relevant bindings:")?;
+                for (name, ty) in bindings {
+                    writeln!(out, "  {} :: {}", name, ty.yellow())?;
+                }
+            }
+            if !candidates.is_empty() {
+                writeln!(out, "Candidates:")?;
+                for (name, ty) in candidates {
+                    writeln!(out, "  {} :: {}", name, ty.yellow())?;
+                }
+            }
+        }
+        TypecheckError::InvalidProtocolHandler(protocol, expected, found, id) => {
+            writeln!(
+                out,
+                "{} invalid handler for protocol {}",
+                error.red(),
+                protocol.yellow()
+            )?;
+            writeln!(out, "Expected: {}", expected.yellow())?;
+            writeln!(out, "Found:    {}", found.yellow())?;
+            let location_set = location_info.get_item_location(id);
+            print_location_set(out, file_manager, location_set)?;
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
 pub enum Error {
     IoError(IoError),
-    LexerError(Vec<LexerError>),
-    ParseError(ParseError),
+    SyntaxError(Vec<LexerError>, Vec<ParseError>),
     ResolverError(ResolverErrorContainer),
     TypecheckError(TypecheckErrorContainer),
-    RuntimeError(String, LocationId),
+    RuntimeError(RuntimeError),
+    FormatError(FilePath),
 }
 
 impl Error {
-    fn report_location(file_manager: &FileManager, file_path: &FilePath, location: &Location) {
+    fn report_location(
+        out: &mut dyn Write,
+        file_manager: &FileManager,
+        file_path: &FilePath,
+        location: &Location,
+    ) -> IoResult<()> {
         let input = file_manager.content(file_path);
         let lines: Vec<_> = input.lines().collect();
-        eprintln!(
+        writeln!(
+            out,
             "--{}:{}",
             file_path.path.green(),
             format!("{}", location.line + 1).green()
-        );
+        )?;
         let line = &lines[location.line];
         let chars: Vec<_> = line.chars().collect();
         let first = s_from_range(&chars[..], 0, location.span.start);
-        eprint!("{}", first);
+        write!(out, "{}", first)?;
         let s = s_from_range(&chars[..], location.span.start, location.span.end);
-        eprint!("{}", s.red());
+        write!(out, "{}", s.red())?;
         let last = s_from_range(&chars[..], location.span.end, chars.len());
-        eprintln!("{}", last);
+        writeln!(out, "{}", last)?;
+        Ok(())
     }
 
     fn report_error_base(
+        out: &mut dyn Write,
         msg: &str,
         file_manager: &FileManager,
         file_path: &FilePath,
         location: &Location,
-    ) {
+    ) -> IoResult<()> {
         let error = "ERROR:";
-        eprintln!("{} {}", error.red(), msg);
-        Error::report_location(file_manager, file_path, location);
+        writeln!(out, "{} {}", error.red(), msg)?;
+        Error::report_location(out, file_manager, file_path, location)
     }
 
     pub fn report_error(&self, context: &ErrorContext) {
+        let mut out = stderr();
+        self.write_error(context, &mut out)
+            .expect("Failed to report error");
+    }
+
+    pub fn write_error(&self, context: &ErrorContext, out: &mut dyn Write) -> IoResult<()> {
         let file_manager = &context.file_manager;
         let location_info = &context.location_info;
         let error = "ERROR:";
         match self {
-            Error::LexerError(errors) => {
-                for err in errors {
+            Error::SyntaxError(lexer_errors, parse_errors) => {
+                let mut errors = Vec::new();
+                for err in lexer_errors {
                     match err {
                         LexerError::General(msg, file_path, location) => {
-                            Error::report_error_base(msg, file_manager, file_path, location);
+                            errors.push((msg.clone(), file_path, location));
                         }
                         LexerError::UnsupportedCharacter(c, location) => {
-                            Error::report_error_base(
-                                &format!(
-                                    "{} unsupported character {}",
-                                    error.red(),
-                                    format!("{}", c).yellow()
-                                ),
-                                file_manager,
-                                &location.file_path,
-                                &location.location,
+                            let msg = format!(
+                                "{} unsupported character {}",
+                                error.red(),
+                                format!("{}", c).yellow()
                             );
+                            errors.push((msg, &location.file_path, &location.location));
                         }
                     }
                 }
-            }
-            Error::ParseError(err) => {
-                Error::report_error_base(&err.msg, file_manager, &err.file_path, &err.location);
+                for err in parse_errors {
+                    errors.push((err.msg.clone(), &err.file_path, &err.location));
+                }
+                errors.sort_by_key(|(_, file_path, location)| {
+                    (file_path.path.clone(), location.line)
+                });
+                for (msg, file_path, location) in errors {
+                    Error::report_error_base(out, &msg, file_manager, file_path, location)?;
+                }
             }
             Error::ResolverError(errs) => {
-                for err in &errs.errors {
-                    match err {
-                        ResolverError::ModuleConflict(errors) => {
-                            for (name, ids) in errors.iter() {
-                                eprintln!(
-                                    "{} module name {} defined more than once",
-                                    error.red(),
-                                    name.yellow()
-                                );
-                                for id in ids.iter() {
-                                    let location_set = location_info.get_item_location(id);
-                                    print_location_set(file_manager, location_set);
-                                }
-                            }
-                        }
-                        ResolverError::ImportedModuleNotFound(name, id) => {
-                            eprintln!(
-                                "{} imported module {} does not exist",
-                                error.red(),
-                                name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::UnknownTypeName(var_name, id) => {
-                            eprintln!("{} unknown type name {}", error.red(), var_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::UnknownTypeArg(var_name, id) => {
-                            eprintln!(
-                                "{} unknown type argument {}",
-                                error.red(),
-                                var_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::TypeArgumentConflict(args, id) => {
-                            eprintln!(
-                                "{} type argument(s) are not unique: {}",
-                                error.red(),
-                                format_list(args).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ArgumentConflict(args, id) => {
-                            eprintln!(
-                                "{} argument(s) are not unique: {}",
-                                error.red(),
-                                format_list(args).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::LambdaArgumentConflict(args, id) => {
-                            eprintln!(
-                                "{} lambda argument(s) {} are not unique",
-                                error.red(),
-                                format_list(args).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::UnknownFunction(var_name, id) => {
-                            eprintln!("{} unknown function {}", error.red(), var_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::AmbiguousName(var_name, id) => {
-                            eprintln!("{} ambiguous name {}", error.red(), var_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::UnusedTypeArgument(arg, id) => {
-                            eprintln!("{} unused type argument: {}", error.red(), arg.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::InternalModuleConflicts(module_name, name, locations) => {
-                            eprintln!(
-                                "{} conflicting items named {} in module {}",
-                                error.red(),
-                                name.yellow(),
-                                module_name.yellow()
-                            );
-                            for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
-                            }
-                        }
-                        ResolverError::RecordFieldNotUnique(record_name, item_name, id) => {
-                            eprintln!(
-                                "{} field name {} is not unique in record {}",
-                                error.red(),
-                                item_name.yellow(),
-                                record_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::VariantNotUnique(adt_name, variant_name, id) => {
-                            eprintln!(
-                                "{} variant name {} is not unique in type {}",
-                                error.red(),
-                                variant_name.yellow(),
-                                adt_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ExportNoMatch(module_name, entity_name, id) => {
-                            eprintln!(
-                                "{} item {} does not export anything in module {}",
-                                error.red(),
-                                entity_name.yellow(),
-                                module_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ImportNoMatch(module_name, entity_name, id) => {
-                            eprintln!(
-                                "{} item {} does not import anything from module {}",
-                                error.red(),
-                                entity_name.yellow(),
-                                module_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::IncorrectTypeArgumentCount(
-                            type_name,
-                            expected,
-                            found,
-                            id,
-                        ) => {
-                            eprintln!(
-                                "{} incorrect type argument count for type {}",
-                                error.red(),
-                                type_name.yellow(),
-                            );
-                            let expected = format!("{}", expected);
-                            let found = format!("{}", found);
-                            eprintln!("Expected: {}", expected.yellow());
-                            eprintln!("Found:    {}", found.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NameNotType(name, id) => {
-                            eprintln!("{} name is not a type {}", error.red(), name.yellow(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::UnusedHiddenItem(hidden_item, module_name, id) => {
-                            eprintln!(
-                                "{} hidden item {} does not hide anything from module {}",
-                                error.red(),
-                                hidden_item.yellow(),
-                                module_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::UnknownFieldName(field_name, id) => {
-                            eprintln!("{} unknown field name {}", error.red(), field_name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NotIrrefutablePattern(id) => {
-                            eprintln!("{} not irrefutable pattern", error.red(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NotRecordType(name, id) => {
-                            eprintln!("{} {} is not a record type", error.red(), name.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NoSuchField(record, field_name, id) => {
-                            eprintln!(
-                                "{} there is no field named {} in {}",
-                                error.red(),
-                                field_name.yellow(),
-                                record.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::MissingFields(missing_fields, id) => {
-                            eprintln!(
-                                "{} missing initialization of the following field(s): {}",
-                                error.red(),
-                                format_list(missing_fields).yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::FieldsInitializedMultipleTimes(
-                            fields_initialized_twice,
-                            id,
-                        ) => {
-                            eprintln!(
-                                "{} the following field(s) are initialized multiple times: {}",
-                                error.red(),
-                                format_list(fields_initialized_twice).yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NoRecordFoundWithFields(fields, id) => {
-                            eprintln!(
-                                "{} no record found that has all the following field(s): {}",
-                                error.red(),
-                                format_list(fields).yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NotAClassName(name, id) => {
-                            eprintln!("{} {} is not a class", error.red(), name.yellow(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::InvalidArgumentInTypeClassConstraint(arg, id) => {
-                            eprintln!(
-                                "{} class constraint argument {} is unknown type argument",
-                                error.red(),
-                                arg.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NotAClassMember(member_name, class_name, id) => {
-                            eprintln!(
-                                "{} {} is not a member of class {}",
-                                error.red(),
-                                member_name.yellow(),
-                                class_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::MissingClassMemberInInstance(
-                            member_name,
-                            class_name,
-                            id,
-                        ) => {
-                            eprintln!(
-                                "{} class member {} of class {} is missing in instance",
-                                error.red(),
-                                member_name.yellow(),
-                                class_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ClassMemberTypeArgMissing(member_name, class_arg, id) => {
-                            eprintln!(
-                                "{} type arguments of class member {} does not contain the type argument of class: {}",
-                                error.red(),
-                                member_name.yellow(),
-                                class_arg.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ExtraConstraintInClassMember(member_name, id) => {
-                            eprintln!(
-                                "{} extra type constraint in class member {}",
-                                error.red(),
-                                member_name.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ConflictingDefaultClassMember(class, name, locations) => {
-                            eprintln!(
-                                "{} conflicting default implementations for class member {} in class {}",
-                                error.red(),
-                                name.yellow(),
-                                class.yellow()
-                            );
-                            for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
-                            }
-                        }
-                        ResolverError::DefaultClassMemberWithoutType(class, name, id) => {
-                            eprintln!(
-                                "{} class member {} in class {} has no type signature",
-                                error.red(),
-                                name.yellow(),
-                                class.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ConflictingFunctionTypesInModule(
-                            module,
-                            name,
-                            locations,
-                        ) => {
-                            eprintln!(
-                                "{} conflicting function types named {} in module {}",
-                                error.red(),
-                                name.yellow(),
-                                module.yellow()
-                            );
-                            for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
-                            }
-                        }
-                        ResolverError::InstanceMemberWithoutImplementation(name, id) => {
-                            eprintln!(
-                                "{} instance member {} has no implementation",
-                                error.red(),
-                                name.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ConflictingInstanceMemberFunction(name, locations) => {
-                            eprintln!(
-                                "{} conflicting instance member function named {}",
-                                error.red(),
-                                name.yellow(),
-                            );
-                            for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
-                            }
-                        }
-                        ResolverError::ConflictingFunctionTypesInInstance(name, locations) => {
-                            eprintln!(
-                                "{} conflicting function types named {} in instance",
-                                error.red(),
-                                name.yellow(),
-                            );
-                            for id in locations {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
-                            }
-                        }
-                        ResolverError::FunctionTypeWithoutImplementationInModule(
-                            module,
-                            name,
-                            id,
-                        ) => {
-                            eprintln!(
-                                "{} function type {} has no implementation in module {}",
-                                error.red(),
-                                name.yellow(),
-                                module.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::InvalidClassArgument(id) => {
-                            eprintln!(
-                                "{} invalid class argument, must be a single type argument",
-                                error.red(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::InvalidTypeArgInInstanceConstraint(arg, id) => {
-                            eprintln!(
-                                "{} instance constraint argument {} is unknown type argument",
-                                error.red(),
-                                arg.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::NamedInstancedNotUnique(module, instance, id) => {
-                            eprintln!(
-                                "{} named instance {} is not unique in module {}",
-                                error.red(),
-                                instance.yellow(),
-                                module.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::PatternBindConflict(name, ids) => {
-                            eprintln!(
-                                "{} multiple variable named {} found",
-                                error.red(),
-                                name.yellow(),
-                            );
-                            for id in ids {
-                                let location_set = location_info.get_item_location(id);
-                                print_location_set(file_manager, location_set);
-                            }
-                        }
-                        ResolverError::PatternBindNotPresent(name, id) => {
-                            eprintln!(
-                                "{} variable {} not present in all patterns in or pattern",
-                                error.red(),
-                                name.yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::ContinueOutsideLoop(id) => {
-                            eprintln!("{} continue outside of a loop", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        ResolverError::BreakOutsideLoop(id) => {
-                            eprintln!("{} break outside of a loop", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
+                let mut errors: Vec<_> = errs.errors.iter().collect();
+                errors.sort_by_key(|err| {
+                    location_sort_key(location_info, get_resolver_error_location(err))
+                });
+                for err in errors {
+                    report_resolver_error(err, file_manager, location_info, out)?;
+                }
+            }
+            Error::RuntimeError(err) => {
+                writeln!(out, "{} {}", error.red(), err.msg)?;
+                let location_set = location_info.get_item_location(&err.location_id);
+                print_location_set(out, file_manager, location_set)?;
+                writeln!(out, "Call stack:")?;
+                for frame in &err.call_stack {
+                    match frame.call_site {
+                        Some(id) => {
+                            let location_set = location_info.get_item_location(&id);
+                            let line = location_set.lines.keys().next().expect("Empty location");
+                            writeln!(
+                                out,
+                                "    {} called at {}:{}",
+                                frame.function.yellow(),
+                                location_set.file_path.path.green(),
+                                format!("{}", line + 1).green()
+                            )?;
+                        }
+                        None => {
+                            writeln!(out, "    {}", frame.function.yellow())?;
                         }
                     }
                 }
             }
-            Error::RuntimeError(err, id) => {
-                eprintln!("{} {}", error.red(), err);
-                let location_set = location_info.get_item_location(id);
-                print_location_set(file_manager, location_set);
-            }
             Error::TypecheckError(errs) => {
-                for err in &errs.errors {
-                    match err {
-                        TypecheckError::ConflictingInstances(name, id1, id2) => {
-                            eprintln!(
-                                "{} conflicting class instances for class {}",
-                                error.red(),
-                                name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id1);
-                            print_location_set(file_manager, location_set);
-                            let location_set = location_info.get_item_location(id2);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::DeriveFailureNoInstanceFound(type_name, class_name, id) => {
-                            eprintln!(
-                                "{} auto derive failure, no instance found for class {} for a member of {}",
-                                error.red(),
-                                class_name.yellow(),
-                                type_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::DeriveFailureInstanceNotGeneric(
-                            type_name,
-                            class_name,
-                            id,
-                        ) => {
-                            eprintln!(
-                                "{} auto derive failure, instance not generic for class {} for a member of {}",
-                                error.red(),
-                                class_name.yellow(),
-                                type_name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::UntypedExternFunction(name, id) => {
-                            eprintln!(
-                                "{} extern function {} does not have a type signature",
-                                error.red(),
-                                name.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::FunctionArgAndSignatureMismatch(
-                            name,
-                            arg_count,
-                            signature_arg_count,
-                            id,
-                            is_member,
-                        ) => {
-                            if *is_member {
-                                eprintln!(
-                                "{} member function type signature of {} does not match its argument count",
-                                error.red(),
-                                name.yellow()
-                            );
-                            } else {
-                                eprintln!(
-                                "{} function type signature of {} does not match its argument count",
-                                error.red(),
-                                name.yellow()
-                            );
-                            }
-                            eprintln!(
-                                "Arguments:                      {}",
-                                format!("{}", arg_count).yellow()
-                            );
-                            eprintln!(
-                                "Arguments in type signature:    {}",
-                                format!("{}", signature_arg_count).yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::MainNotFound => {
-                            eprintln!(
-                                "{} {} function in module {} not found",
-                                error.red(),
-                                "main".yellow(),
-                                "Main".yellow()
-                            );
-                        }
-                        TypecheckError::TypeMismatch(id, expected, found) => {
-                            eprintln!("{} type mismatch in expression", error.red());
-                            eprintln!("Expected: {}", expected.yellow());
-                            eprintln!("Found:    {}", found.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::FunctionArgumentMismatch(id, args, func) => {
-                            eprintln!("{} invalid argument(s)", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                            eprintln!("Argument(s):      {}", args.yellow());
-                            eprintln!("Function type:    {}", func.yellow());
-                        }
-                        TypecheckError::InvalidVariantPattern(id, name, expected, found) => {
-                            eprintln!(
-                                "{} invalid {} variant pattern, argument count mismatch",
-                                error.red(),
-                                name.yellow()
-                            );
-                            eprintln!("Expected:      {}", format!("{}", expected).yellow());
-                            eprintln!("Found:         {}", format!("{}", found).yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::InvalidRecordPattern(id, name, expected, found) => {
-                            eprintln!(
-                                "{} invalid {} record pattern, argument count mismatch",
-                                error.red(),
-                                name.yellow()
-                            );
-                            eprintln!("Expected:      {}", format!("{}", expected).yellow());
-                            eprintln!("Found:         {}", format!("{}", found).yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::TypeAnnotationNeeded(id) => {
-                            eprintln!("{} Type annotation needed", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::InvalidFormatString(id) => {
-                            eprintln!("{} invalid format string", error.red());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::CyclicClassDependencies(id, path) => {
-                            eprintln!(
-                                "{} cyclic class dependencies: {}",
-                                error.red(),
-                                path.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::MissingInstance(class, id) => {
-                            eprintln!("{} missing instance of {}", error.red(), class.yellow());
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::ClassNotAutoDerivable(class, id) => {
-                            eprintln!(
-                                "{} class {} is not auto derivable",
-                                error.red(),
-                                class.yellow()
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::IncorrectTypeForMain(ty, id) => {
-                            eprintln!(
-                                "{} {} in module {} has type {} instead of {}",
-                                error.red(),
-                                MAIN_FUNCTION.yellow(),
-                                MAIN_MODULE_NAME.yellow(),
-                                ty.yellow(),
-                                "()".yellow(),
-                            );
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::UnreachablePattern(id) => {
-                            eprintln!("{} unreachable pattern", error.red(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                        TypecheckError::NonExhaustivePattern(id) => {
-                            eprintln!("{} non exhaustive pattern", error.red(),);
-                            let location_set = location_info.get_item_location(id);
-                            print_location_set(file_manager, location_set);
-                        }
-                    }
+                let mut errors: Vec<_> = errs.errors.iter().collect();
+                errors.sort_by_key(|err| {
+                    location_sort_key(location_info, get_typecheck_error_location(err))
+                });
+                for err in errors {
+                    report_typecheck_error(err, file_manager, location_info, out)?;
                 }
             }
-            _ => unimplemented!(),
+            Error::IoError(err) => {
+                writeln!(out, "{} {}", error.red(), err)?;
+            }
+            Error::FormatError(file_path) => {
+                writeln!(
+                    out,
+                    "{} formatting would change the layout of {}, the file is left unchanged",
+                    error.red(),
+                    file_path.path.yellow()
+                )?;
+            }
         }
+        Ok(())
     }
 }
 
@@ -753,18 +1161,18 @@ impl From<IoError> for Error {
     }
 }
 
-impl From<ParseError> for Error {
-    fn from(e: ParseError) -> Error {
-        Error::ParseError(e)
-    }
-}
-
 impl From<ResolverErrorContainer> for Error {
     fn from(e: ResolverErrorContainer) -> Error {
         Error::ResolverError(e)
     }
 }
 
+impl From<RuntimeError> for Error {
+    fn from(e: RuntimeError) -> Error {
+        Error::RuntimeError(e)
+    }
+}
+
 impl From<TypecheckErrorContainer> for Error {
     fn from(e: TypecheckErrorContainer) -> Error {
         Error::TypecheckError(e)
@@ -1,3 +1,6 @@
 pub mod compiler;
 pub mod config;
+pub mod diagnostic;
 pub mod error;
+pub mod repl;
+pub mod visualizer;
@@ -0,0 +1,503 @@
+use crate::compiler::Compiler;
+use crate::compiler::CompilerInput;
+use crate::config::Config;
+use crate::error::Error;
+use siko_interpreter::interpreter::Interpreter;
+use siko_ir::function::FunctionId;
+use siko_ir::function::FunctionInfo;
+use siko_name_resolver::module::Module;
+use siko_name_resolver::resolver::Resolver;
+use siko_syntax::module::ModuleId;
+use siko_syntax::program::Program;
+use siko_type_checker::type_info_provider::TypeInfoProvider;
+use siko_type_checker::typechecker::Typechecker;
+use siko_util::durations::Durations;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::stdout;
+use std::io::BufRead;
+use std::io::Write;
+
+const REPL_MODULE_NAME: &str = "Repl";
+const VALUE_FUNCTION: &str = "it";
+
+const HELP: &str = "Enter an expression to evaluate it, or a definition to add it to the session.
+Lines ending with do, where, of, = or -> continue until an empty line.
+    let name = expr     bind a value
+    name args = expr    define a function
+    name :: type        give the type of the next definition of name
+    import Module       import a module into the session
+Data types, classes and instances can be defined in the loaded modules.
+Commands:
+    :type expr          show the type of an expression
+    :help               show this help
+    :quit               leave the repl";
+
+enum Input {
+    Command(String, String),
+    Import(String),
+    Signature(String),
+    Definition(String, String),
+    Unsupported(String),
+    Expr(String),
+}
+
+struct Signature {
+    name: String,
+    source: String,
+}
+
+// the functions added to the session by one input
+struct Addition {
+    saved_module: Module,
+    functions: BTreeMap<String, FunctionId>,
+    function_ids: BTreeSet<FunctionId>,
+}
+
+fn is_operator_char(c: char) -> bool {
+    "=<>!/+-*:|&\\.".contains(c)
+}
+
+// finds the given operator outside of parens, brackets and literals
+fn find_top_level(text: &str, op: &str) -> Option<usize> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let op: Vec<char> = op.chars().collect();
+    let mut depth = 0;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index].1;
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '"' | '\'' => {
+                index += 1;
+                while index < chars.len() && chars[index].1 != c {
+                    if chars[index].1 == '\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            _ => {
+                let end = index + op.len();
+                if depth == 0 && end <= chars.len() {
+                    let matches = chars[index..end]
+                        .iter()
+                        .map(|(_, c)| *c)
+                        .eq(op.iter().cloned());
+                    let before = index > 0 && is_operator_char(chars[index - 1].1);
+                    let after = end < chars.len() && is_operator_char(chars[end].1);
+                    if matches && !before && !after {
+                        return Some(chars[index].0);
+                    }
+                }
+            }
+        }
+        index += 1;
+    }
+    None
+}
+
+fn get_identifier(text: &str) -> Option<String> {
+    let text = text.trim();
+    let first = text.chars().next()?;
+    if (first.is_lowercase() || first == '_')
+        && text.chars().all(|c| c.is_alphanumeric() || c == '_')
+    {
+        Some(text.to_string())
+    } else {
+        None
+    }
+}
+
+fn classify(text: &str) -> Input {
+    let trimmed = text.trim();
+    if trimmed.starts_with(':') {
+        let mut parts = trimmed.splitn(2, char::is_whitespace);
+        let command = parts.next().unwrap_or("").to_string();
+        let arg = parts.next().unwrap_or("").trim().to_string();
+        return Input::Command(command, arg);
+    }
+    let words: Vec<&str> = trimmed.split_whitespace().collect();
+    match words[0] {
+        "import" => return Input::Import(trimmed.to_string()),
+        "data" | "class" | "instance" | "actor" | "protocol" => {
+            return Input::Unsupported(words[0].to_string());
+        }
+        "let" if !words.contains(&"in") => {
+            let binding = trimmed["let".len()..].trim_start();
+            if let Some(index) = find_top_level(binding, "=") {
+                if let Some(name) = get_identifier(&binding[..index]) {
+                    return Input::Definition(name, binding.to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+    if let Some(index) = find_top_level(trimmed, "=") {
+        if let Some(name) = get_identifier(words[0]) {
+            if trimmed[..index].trim_start().starts_with(&name) {
+                return Input::Definition(name, trimmed.to_string());
+            }
+        }
+    }
+    if let Some(index) = find_top_level(trimmed, "::") {
+        if let Some(name) = get_identifier(&trimmed[..index]) {
+            return Input::Signature(name);
+        }
+    }
+    Input::Expr(trimmed.to_string())
+}
+
+fn needs_more_lines(line: &str) -> bool {
+    let line = line.trim_end();
+    ["do", "where", "of", "=", "->"].iter().any(|end| {
+        line.ends_with(end) && {
+            let rest = &line[..line.len() - end.len()];
+            rest.is_empty() || !rest.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        }
+    })
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn generate_module(source: &str) -> String {
+    format!("module {} where\n\n{}\n", REPL_MODULE_NAME, source)
+}
+
+// std and the loaded modules are checked once when the session starts. Every
+// input is parsed as a separate source, its functions are resolved and checked
+// into the Repl module of the already checked program, which stays in the
+// interpreter. A definition shadows the earlier one with the same name, the
+// functions using the earlier one keep using it. Definitions without arguments
+// are evaluated once and their values are kept by the interpreter.
+pub struct Repl {
+    compiler: Compiler,
+    program: Program,
+    resolver: Resolver,
+    typechecker: Typechecker,
+    type_info_provider: TypeInfoProvider,
+    repl_module_id: ModuleId,
+    input_count: usize,
+    signature: Option<Signature>,
+}
+
+impl Repl {
+    pub fn new(inputs: Vec<CompilerInput>, std_inputs: Vec<CompilerInput>) -> Result<Repl, ()> {
+        let mut config = Config::new();
+        config.main_required = false;
+        let mut compiler = Compiler::new(config);
+        match Repl::start(&mut compiler, inputs, std_inputs) {
+            Ok((program, resolver, typechecker, type_info_provider, repl_module_id)) => Ok(Repl {
+                compiler: compiler,
+                program: program,
+                resolver: resolver,
+                typechecker: typechecker,
+                type_info_provider: type_info_provider,
+                repl_module_id: repl_module_id,
+                input_count: 0,
+                signature: None,
+            }),
+            Err(err) => {
+                compiler.report_error(err);
+                Err(())
+            }
+        }
+    }
+
+    fn start(
+        compiler: &mut Compiler,
+        inputs: Vec<CompilerInput>,
+        std_inputs: Vec<CompilerInput>,
+    ) -> Result<(Program, Resolver, Typechecker, TypeInfoProvider, ModuleId), Error> {
+        let user_files: BTreeSet<_> = inputs
+            .iter()
+            .map(|input| match input {
+                CompilerInput::File { name } => name.clone(),
+                CompilerInput::Memory { name, .. } => name.clone(),
+            })
+            .collect();
+        let mut program = compiler.parse(inputs.into_iter().chain(std_inputs).collect())?;
+        let mut imports = Vec::new();
+        for module in program.modules.items.values() {
+            let location = compiler
+                .get_location_info()
+                .get_item_location(&module.location_id);
+            if user_files.contains(&location.file_path.path) {
+                imports.push(format!("import {}", module.name));
+            }
+        }
+        // a module cannot be empty
+        imports.push(format!("{} = ()", VALUE_FUNCTION));
+        let input = CompilerInput::Memory {
+            name: "<repl>".to_string(),
+            content: generate_module(&imports.join("\n")),
+        };
+        let repl_module_id = compiler.parse_into(input, &mut program)?[0];
+        let mut resolver = Resolver::new();
+        let mut ir_program = resolver.resolve(&program)?;
+        let typechecker = Typechecker::new(false);
+        let type_info_provider =
+            typechecker.check_program(&mut ir_program, &mut Durations::new())?;
+        Interpreter::start_session(ir_program);
+        Ok((
+            program,
+            resolver,
+            typechecker,
+            type_info_provider,
+            repl_module_id,
+        ))
+    }
+
+    fn parse_input(&mut self, source: &str) -> Option<ModuleId> {
+        self.input_count += 1;
+        let input = CompilerInput::Memory {
+            name: format!("<repl:{}>", self.input_count),
+            content: generate_module(source),
+        };
+        match self.compiler.parse_into(input, &mut self.program) {
+            Ok(module_ids) => Some(module_ids[0]),
+            Err(err) => {
+                self.compiler.report_error(err);
+                None
+            }
+        }
+    }
+
+    fn add_functions(&mut self, source: &str) -> Option<Addition> {
+        let module_id = self.parse_input(source)?;
+        let saved_module = self.resolver.save_module(REPL_MODULE_NAME);
+        let program = &self.program;
+        let resolver = &mut self.resolver;
+        let typechecker = &self.typechecker;
+        let type_info_provider = &mut self.type_info_provider;
+        let result = Interpreter::with_session_program(|ir_program| {
+            let existing_function_ids: BTreeSet<_> =
+                ir_program.functions.items.keys().cloned().collect();
+            let functions = resolver.resolve_additional_functions(
+                program,
+                module_id,
+                REPL_MODULE_NAME,
+                ir_program,
+            )?;
+            let function_ids: BTreeSet<_> = ir_program
+                .functions
+                .items
+                .keys()
+                .filter(|id| !existing_function_ids.contains(id))
+                .cloned()
+                .collect();
+            if let Err(err) =
+                typechecker.check_functions(ir_program, type_info_provider, &function_ids)
+            {
+                for function_id in &function_ids {
+                    ir_program.functions.items.remove(function_id);
+                }
+                return Err(Error::from(err));
+            }
+            Ok((functions, function_ids))
+        });
+        match result {
+            Ok((functions, function_ids)) => Some(Addition {
+                saved_module: saved_module,
+                functions: functions,
+                function_ids: function_ids,
+            }),
+            Err(err) => {
+                self.resolver.restore_module(saved_module);
+                self.compiler.report_error(err);
+                None
+            }
+        }
+    }
+
+    fn remove_functions(&mut self, addition: Addition) {
+        self.resolver.restore_module(addition.saved_module);
+        let function_ids = addition.function_ids;
+        Interpreter::with_session_program(|ir_program| {
+            for function_id in &function_ids {
+                ir_program.functions.items.remove(function_id);
+                ir_program.function_types.remove(function_id);
+            }
+        });
+    }
+
+    // returns the type of the function and whether it can be evaluated and shown
+    fn get_type_info(function_id: FunctionId) -> (String, bool, bool) {
+        Interpreter::with_session_program(|ir_program| {
+            let function = ir_program.functions.get(&function_id);
+            let location_id = match &function.info {
+                FunctionInfo::NamedFunction(info) => info.location_id,
+                _ => unreachable!(),
+            };
+            let arg_count = function.arg_count;
+            let ty = ir_program.get_function_type(&function_id).clone();
+            let ty_str = ty.get_resolved_type_string(ir_program);
+            // the interpreter only works with concrete types
+            let evaluated = arg_count == 0 && ty.is_concrete_type();
+            let show_class_id = ir_program.get_show_class_id();
+            let shown = evaluated
+                && ir_program.instance_resolver.check_instance(
+                    show_class_id,
+                    &ty,
+                    location_id,
+                    &mut Vec::new(),
+                );
+            (ty_str, evaluated, shown)
+        })
+    }
+
+    // Evaluates the function if it has no arguments, a failed evaluation removes
+    // it from the session.
+    fn evaluate(&mut self, name: &str, addition: Addition) -> Option<(String, Option<String>)> {
+        let function_id = *addition
+            .functions
+            .get(name)
+            .expect("Repl function not found");
+        let (ty, evaluated, shown) = Repl::get_type_info(function_id);
+        if !evaluated {
+            return Some((ty, None));
+        }
+        let result = Interpreter::evaluate_binding(function_id).and_then(|value| {
+            if shown {
+                Interpreter::show_value(value).map(Some)
+            } else {
+                Ok(None)
+            }
+        });
+        match result {
+            Ok(value) => Some((ty, value)),
+            Err(err) => {
+                self.compiler.report_error(Error::RuntimeError(err));
+                self.remove_functions(addition);
+                None
+            }
+        }
+    }
+
+    fn process_definition(&mut self, name: String, source: String) {
+        let source = match self.signature.take() {
+            Some(signature) if signature.name == name => {
+                format!("{}\n{}", signature.source, source)
+            }
+            _ => source,
+        };
+        if let Some(addition) = self.add_functions(&source) {
+            if let Some((ty, _)) = self.evaluate(&name, addition) {
+                println!("{} :: {}", name, ty);
+            }
+        }
+    }
+
+    fn process_import(&mut self, import: String) {
+        let module_id = match self.parse_input(&import) {
+            Some(module_id) => module_id,
+            None => return,
+        };
+        let imports: Vec<_> = self
+            .program
+            .modules
+            .get(&module_id)
+            .imports
+            .iter()
+            .filter(|id| !self.program.imports.get(id).implicit)
+            .cloned()
+            .collect();
+        let saved_module = self.resolver.save_module(REPL_MODULE_NAME);
+        let import_count = imports.len();
+        let repl_module = self.program.modules.get_mut(&self.repl_module_id);
+        repl_module.imports.extend(imports);
+        if let Err(err) = self
+            .resolver
+            .resolve_module_imports(&self.program, REPL_MODULE_NAME)
+        {
+            let repl_module = self.program.modules.get_mut(&self.repl_module_id);
+            let count = repl_module.imports.len() - import_count;
+            repl_module.imports.truncate(count);
+            self.resolver.restore_module(saved_module);
+            self.compiler.report_error(Error::from(err));
+        }
+    }
+
+    fn process_type_query(&mut self, expr: &str) {
+        let source = format!("{} =\n{}", VALUE_FUNCTION, indent(expr));
+        if let Some(addition) = self.add_functions(&source) {
+            let function_id = addition.functions[VALUE_FUNCTION];
+            let (ty, _, _) = Repl::get_type_info(function_id);
+            println!("{} :: {}", expr, ty);
+            self.remove_functions(addition);
+        }
+    }
+
+    // the value is shown if its type has a Show instance, otherwise it is
+    // still evaluated for its effects
+    fn process_expr(&mut self, expr: &str) {
+        let source = format!("{} =\n{}", VALUE_FUNCTION, indent(expr));
+        if let Some(addition) = self.add_functions(&source) {
+            match self.evaluate(VALUE_FUNCTION, addition) {
+                Some((ty, Some(value))) => println!("{} :: {}", value, ty),
+                Some((ty, None)) => println!("<value> :: {}", ty),
+                None => {}
+            }
+        }
+    }
+
+    // returns false if the session should end
+    fn process(&mut self, text: &str) -> bool {
+        match classify(text) {
+            Input::Command(command, arg) => match command.as_ref() {
+                ":q" | ":quit" => return false,
+                ":h" | ":help" => println!("{}", HELP),
+                ":t" | ":type" if !arg.is_empty() => self.process_type_query(&arg),
+                _ => eprintln!("Unknown command {}, see :help", command),
+            },
+            Input::Import(import) => self.process_import(import),
+            Input::Signature(name) => {
+                self.signature = Some(Signature {
+                    name: name,
+                    source: text.trim().to_string(),
+                });
+            }
+            Input::Definition(name, source) => self.process_definition(name, source),
+            Input::Unsupported(keyword) => {
+                eprintln!("{} items can be defined only in a loaded module", keyword)
+            }
+            Input::Expr(expr) => self.process_expr(&expr),
+        }
+        true
+    }
+
+    pub fn run(&mut self, reader: &mut dyn BufRead) {
+        println!("Siko repl, enter :help for help");
+        loop {
+            print!("> ");
+            let _ = stdout().flush();
+            let mut text = String::new();
+            if reader.read_line(&mut text).unwrap_or(0) == 0 {
+                break;
+            }
+            if needs_more_lines(&text) {
+                loop {
+                    print!("| ");
+                    let _ = stdout().flush();
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+                        break;
+                    }
+                    text.push_str(&line);
+                }
+            }
+            if text.trim().is_empty() {
+                continue;
+            }
+            if !self.process(text.trim_end()) {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,571 @@
+use siko_ir::data::TypeDef as IrTypeDef;
+use siko_ir::data::TypeDefId as IrTypeDefId;
+use siko_ir::expr::Expr as IrExpr;
+use siko_ir::expr::ExprId as IrExprId;
+use siko_ir::function::FunctionId as IrFunctionId;
+use siko_ir::function_dep_processor::FunctionDependencyProcessor;
+use siko_ir::pattern::Pattern as IrPattern;
+use siko_ir::pattern::PatternId as IrPatternId;
+use siko_ir::program::Program as IrProgram;
+use siko_mir::data::TypeDef as MirTypeDef;
+use siko_mir::data::TypeDefId as MirTypeDefId;
+use siko_mir::expr::Expr as MirExpr;
+use siko_mir::expr::ExprId as MirExprId;
+use siko_mir::function::FunctionId as MirFunctionId;
+use siko_mir::function::FunctionInfo as MirFunctionInfo;
+use siko_mir::pattern::Pattern as MirPattern;
+use siko_mir::pattern::PatternId as MirPatternId;
+use siko_mir::program::Program as MirProgram;
+use siko_util::dependency_processor::DependencyCollector;
+use siko_util::dot::Graph;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Result as IoResult;
+use std::path::Path;
+
+fn get_file_name(name: &str, id: usize) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}_{}", name, id)
+}
+
+fn get_ir_typedef_name(program: &IrProgram, id: &IrTypeDefId) -> String {
+    match program.typedefs.get(id) {
+        IrTypeDef::Adt(adt) => adt.name.clone(),
+        IrTypeDef::Record(record) => record.name.clone(),
+    }
+}
+
+fn get_mir_typedef_name(program: &MirProgram, id: &MirTypeDefId) -> String {
+    match program.typedefs.get(id) {
+        MirTypeDef::Adt(adt) => adt.name.clone(),
+        MirTypeDef::Record(record) => record.name.clone(),
+    }
+}
+
+pub fn create_dependency_group_graph(program: &IrProgram) -> Graph {
+    let mut graph = Graph::new(format!("dependency_groups"));
+    let mut function_groups = BTreeMap::new();
+    let mut nodes = Vec::new();
+    for (index, group) in program.function_dependency_groups.iter().enumerate() {
+        let node = graph.add_node(format!("Group {}", index));
+        for function_id in &group.items {
+            let function = program.functions.get(function_id);
+            graph.add_element(node, format!("{}", function.info));
+            function_groups.insert(*function_id, node);
+        }
+        nodes.push(node);
+    }
+    let dep_processor = FunctionDependencyProcessor::new(program);
+    for (group, node) in program.function_dependency_groups.iter().zip(nodes) {
+        let mut deps = BTreeSet::new();
+        for function_id in &group.items {
+            for dep in dep_processor.collect(*function_id) {
+                if let Some(dep_node) = function_groups.get(&dep) {
+                    if *dep_node != node {
+                        deps.insert(*dep_node);
+                    }
+                }
+            }
+        }
+        for dep_node in deps {
+            graph.add_edge(None, node, dep_node);
+        }
+    }
+    graph
+}
+
+pub fn create_class_graph(program: &IrProgram) -> Graph {
+    let mut graph = Graph::new(format!("classes"));
+    let mut nodes = BTreeMap::new();
+    for (class_id, class) in &program.classes.items {
+        let node = graph.add_node(format!("{}.{}", class.module, class.name));
+        for member in class.members.keys() {
+            graph.add_element(node, member.clone());
+        }
+        nodes.insert(*class_id, node);
+    }
+    for (class_id, class) in &program.classes.items {
+        for superclass in &class.constraints {
+            graph.add_edge(None, nodes[class_id], nodes[superclass]);
+        }
+    }
+    graph
+}
+
+pub fn create_import_graph(imports: &BTreeMap<String, BTreeSet<String>>) -> Graph {
+    let mut graph = Graph::new(format!("imports"));
+    let mut nodes = BTreeMap::new();
+    for name in imports.keys() {
+        nodes.insert(name.clone(), graph.add_node(name.clone()));
+    }
+    for (name, sources) in imports {
+        for source in sources {
+            if let Some(source_node) = nodes.get(source) {
+                graph.add_edge(None, nodes[name], *source_node);
+            }
+        }
+    }
+    graph
+}
+
+struct IrTreeBuilder<'a> {
+    program: &'a IrProgram,
+    graph: Graph,
+}
+
+impl<'a> IrTreeBuilder<'a> {
+    fn add_child(&mut self, parent: usize, child: usize, name: Option<String>) {
+        self.graph.add_edge(name, parent, child);
+    }
+
+    fn add_exprs(&mut self, parent: usize, exprs: &[IrExprId]) {
+        for expr in exprs {
+            let child = self.add_expr(expr);
+            self.add_child(parent, child, None);
+        }
+    }
+
+    fn add_pattern(&mut self, pattern_id: &IrPatternId) -> usize {
+        let pattern = &self.program.patterns.get(pattern_id).item;
+        let (name, details) = match pattern {
+            IrPattern::Binding(name) => (format!("Binding"), vec![name.clone()]),
+            IrPattern::Tuple(_) => (format!("Tuple"), vec![]),
+            IrPattern::Record(id, _) => (
+                format!("Record"),
+                vec![get_ir_typedef_name(self.program, id)],
+            ),
+            IrPattern::Variant(id, index, _) => {
+                let adt = self.program.typedefs.get(id).get_adt();
+                (format!("Variant"), vec![adt.variants[*index].name.clone()])
+            }
+            IrPattern::Guarded(..) => (format!("Guarded"), vec![]),
+            IrPattern::Wildcard => (format!("Wildcard"), vec![]),
+            IrPattern::IntegerLiteral(v) => (format!("IntegerLiteral"), vec![format!("{}", v)]),
+            IrPattern::StringLiteral(v) => (format!("StringLiteral"), vec![format!("{:?}", v)]),
+            IrPattern::CharLiteral(v) => (format!("CharLiteral"), vec![format!("{:?}", v)]),
+            IrPattern::CharRange(start, end, _) => (
+                format!("CharRange"),
+                vec![format!("{:?}..{:?}", start, end)],
+            ),
+            IrPattern::IntegerRange(start, end, _) => {
+                (format!("IntegerRange"), vec![format!("{}..{}", start, end)])
+            }
+            IrPattern::Typed(..) => (format!("Typed"), vec![]),
+            IrPattern::List(_) => (format!("List"), vec![]),
+            IrPattern::Cons(..) => (format!("Cons"), vec![]),
+        };
+        let node = self.graph.add_node(name);
+        for detail in details {
+            self.graph.add_element(node, detail);
+        }
+        if let Some(ty) = self.program.pattern_types.get(pattern_id) {
+            self.graph
+                .add_element(node, ty.get_resolved_type_string(self.program));
+        }
+        match pattern {
+            IrPattern::Tuple(items)
+            | IrPattern::Record(_, items)
+            | IrPattern::Variant(_, _, items)
+            | IrPattern::List(items) => {
+                for item in items {
+                    let child = self.add_pattern(item);
+                    self.add_child(node, child, None);
+                }
+            }
+            IrPattern::Guarded(inner, guard) => {
+                let child = self.add_pattern(inner);
+                self.add_child(node, child, None);
+                let child = self.add_expr(guard);
+                self.add_child(node, child, Some(format!("guard")));
+            }
+            IrPattern::Typed(inner, _) => {
+                let child = self.add_pattern(inner);
+                self.add_child(node, child, None);
+            }
+            IrPattern::Cons(head, tail) => {
+                let child = self.add_pattern(head);
+                self.add_child(node, child, Some(format!("head")));
+                let child = self.add_pattern(tail);
+                self.add_child(node, child, Some(format!("tail")));
+            }
+            _ => {}
+        }
+        node
+    }
+
+    fn add_expr(&mut self, expr_id: &IrExprId) -> usize {
+        let expr = &self.program.exprs.get(expr_id).item;
+        let (name, details) = match expr {
+            IrExpr::StaticFunctionCall(id, _) => (
+                format!("StaticFunctionCall"),
+                vec![format!("{}", self.program.functions.get(id).info)],
+            ),
+            IrExpr::DynamicFunctionCall(..) => (format!("DynamicFunctionCall"), vec![]),
+            IrExpr::ClassFunctionCall(id, _) => (
+                format!("ClassFunctionCall"),
+                vec![self.program.class_members.get(id).name.clone()],
+            ),
+            IrExpr::If(..) => (format!("If"), vec![]),
+            IrExpr::Tuple(_) => (format!("Tuple"), vec![]),
+            IrExpr::List(_) => (format!("List"), vec![]),
+            IrExpr::IntegerLiteral(v) => (format!("IntegerLiteral"), vec![format!("{}", v)]),
+            IrExpr::FloatLiteral(v) => (format!("FloatLiteral"), vec![format!("{}", v)]),
+            IrExpr::StringLiteral(v) => (format!("StringLiteral"), vec![format!("{:?}", v)]),
+            IrExpr::CharLiteral(v) => (format!("CharLiteral"), vec![format!("{:?}", v)]),
+            IrExpr::Do(_) => (format!("Do"), vec![]),
+            IrExpr::Bind(..) => (format!("Bind"), vec![]),
+            IrExpr::ArgRef(arg_ref) => (format!("ArgRef"), vec![format!("{}", arg_ref.index)]),
+            IrExpr::ExprValue(_, pattern_id) => {
+                (format!("ExprValue"), vec![format!("{}", pattern_id)])
+            }
+            IrExpr::FieldAccess(infos, _) => {
+                let info = &infos[0];
+                let record = self.program.typedefs.get(&info.record_id).get_record();
+                (
+                    format!("FieldAccess"),
+                    vec![record.fields[info.index].name.clone()],
+                )
+            }
+            IrExpr::TupleFieldAccess(index, _) => {
+                (format!("TupleFieldAccess"), vec![format!("{}", index)])
+            }
+            IrExpr::Formatter(fmt, _) => (format!("Formatter"), vec![format!("{:?}", fmt)]),
+            IrExpr::CaseOf(..) => (format!("CaseOf"), vec![]),
+            IrExpr::RecordInitialization(id, _) => (
+                format!("RecordInitialization"),
+                vec![get_ir_typedef_name(self.program, id)],
+            ),
+            IrExpr::RecordUpdate(..) => (format!("RecordUpdate"), vec![]),
+            IrExpr::Return(_) => (format!("Return"), vec![]),
+            IrExpr::Loop(..) => (format!("Loop"), vec![]),
+            IrExpr::Continue(_) => (format!("Continue"), vec![]),
+            IrExpr::Break(_) => (format!("Break"), vec![]),
+            IrExpr::Hole(info) => (format!("Hole"), vec![info.name.clone()]),
+        };
+        let node = self.graph.add_node(name);
+        for detail in details {
+            self.graph.add_element(node, detail);
+        }
+        if let Some(ty) = self.program.expr_types.get(expr_id) {
+            self.graph
+                .add_element(node, ty.get_resolved_type_string(self.program));
+        }
+        match expr {
+            IrExpr::StaticFunctionCall(_, args)
+            | IrExpr::ClassFunctionCall(_, args)
+            | IrExpr::Tuple(args)
+            | IrExpr::List(args)
+            | IrExpr::Do(args)
+            | IrExpr::Formatter(_, args) => self.add_exprs(node, args),
+            IrExpr::DynamicFunctionCall(callable, args) => {
+                let child = self.add_expr(callable);
+                self.add_child(node, child, Some(format!("callable")));
+                self.add_exprs(node, args);
+            }
+            IrExpr::If(cond, true_branch, false_branch) => {
+                let child = self.add_expr(cond);
+                self.add_child(node, child, Some(format!("cond")));
+                let child = self.add_expr(true_branch);
+                self.add_child(node, child, Some(format!("then")));
+                let child = self.add_expr(false_branch);
+                self.add_child(node, child, Some(format!("else")));
+            }
+            IrExpr::Bind(pattern_id, rhs) => {
+                let child = self.add_pattern(pattern_id);
+                self.add_child(node, child, None);
+                self.add_exprs(node, &[*rhs]);
+            }
+            IrExpr::FieldAccess(_, receiver)
+            | IrExpr::TupleFieldAccess(_, receiver)
+            | IrExpr::Return(receiver)
+            | IrExpr::Continue(receiver)
+            | IrExpr::Break(receiver) => self.add_exprs(node, &[*receiver]),
+            IrExpr::CaseOf(body, cases, _) => {
+                self.add_exprs(node, &[*body]);
+                for case in cases {
+                    let pattern = self.add_pattern(&case.pattern_id);
+                    self.add_child(node, pattern, Some(format!("case")));
+                    let child = self.add_expr(&case.body);
+                    self.add_child(pattern, child, None);
+                }
+            }
+            IrExpr::RecordInitialization(_, items) => {
+                let items: Vec<_> = items.iter().map(|item| item.expr_id).collect();
+                self.add_exprs(node, &items);
+            }
+            IrExpr::RecordUpdate(receiver, updates) => {
+                self.add_exprs(node, &[*receiver]);
+                for update in updates {
+                    let items: Vec<_> = update.items.iter().map(|item| item.expr_id).collect();
+                    self.add_exprs(node, &items);
+                }
+            }
+            IrExpr::Loop(pattern_id, initializer, items, _, _) => {
+                let child = self.add_pattern(pattern_id);
+                self.add_child(node, child, None);
+                let child = self.add_expr(initializer);
+                self.add_child(node, child, Some(format!("init")));
+                self.add_exprs(node, items);
+            }
+            IrExpr::Hole(info) => {
+                for (name, binding) in &info.bindings {
+                    let child = self.add_expr(binding);
+                    self.add_child(node, child, Some(name.clone()));
+                }
+            }
+            _ => {}
+        }
+        node
+    }
+}
+
+pub fn create_ir_function_graph(program: &IrProgram, function_id: IrFunctionId) -> Option<Graph> {
+    let function = program.functions.get(&function_id);
+    let body = function.get_body()?;
+    let name = format!("{}", function.info);
+    let mut builder = IrTreeBuilder {
+        program: program,
+        graph: Graph::new(get_file_name(&name, function_id.id)),
+    };
+    let root = builder.graph.add_node(name);
+    let child = builder.add_expr(&body);
+    builder.add_child(root, child, None);
+    Some(builder.graph)
+}
+
+struct MirTreeBuilder<'a> {
+    program: &'a MirProgram,
+    graph: Graph,
+}
+
+impl<'a> MirTreeBuilder<'a> {
+    fn add_child(&mut self, parent: usize, child: usize, name: Option<String>) {
+        self.graph.add_edge(name, parent, child);
+    }
+
+    fn add_exprs(&mut self, parent: usize, exprs: &[MirExprId]) {
+        for expr in exprs {
+            let child = self.add_expr(expr);
+            self.add_child(parent, child, None);
+        }
+    }
+
+    fn add_pattern(&mut self, pattern_id: &MirPatternId) -> usize {
+        let pattern = &self.program.patterns.get(pattern_id).item;
+        let (name, details) = match pattern {
+            MirPattern::Binding(name) => (format!("Binding"), vec![name.clone()]),
+            MirPattern::Record(id, _) => (
+                format!("Record"),
+                vec![get_mir_typedef_name(self.program, id)],
+            ),
+            MirPattern::Variant(id, index, _) => {
+                let adt = self.program.typedefs.get(id).get_adt();
+                (format!("Variant"), vec![adt.variants[*index].name.clone()])
+            }
+            MirPattern::Guarded(..) => (format!("Guarded"), vec![]),
+            MirPattern::Wildcard => (format!("Wildcard"), vec![]),
+            MirPattern::IntegerLiteral(v) => (format!("IntegerLiteral"), vec![format!("{}", v)]),
+            MirPattern::StringLiteral(v) => (format!("StringLiteral"), vec![format!("{:?}", v)]),
+            MirPattern::CharLiteral(v) => (format!("CharLiteral"), vec![format!("{:?}", v)]),
+            MirPattern::CharRange(start, end, _) => (
+                format!("CharRange"),
+                vec![format!("{:?}..{:?}", start, end)],
+            ),
+            MirPattern::IntegerRange(start, end, _) => {
+                (format!("IntegerRange"), vec![format!("{}..{}", start, end)])
+            }
+            MirPattern::List(_) => (format!("List"), vec![]),
+            MirPattern::Cons(..) => (format!("Cons"), vec![]),
+        };
+        let node = self.graph.add_node(name);
+        for detail in details {
+            self.graph.add_element(node, detail);
+        }
+        if let Some(ty) = self.program.pattern_types.get(pattern_id) {
+            self.graph.add_element(node, ty.to_string(self.program));
+        }
+        match pattern {
+            MirPattern::Record(_, items)
+            | MirPattern::Variant(_, _, items)
+            | MirPattern::List(items) => {
+                for item in items {
+                    let child = self.add_pattern(item);
+                    self.add_child(node, child, None);
+                }
+            }
+            MirPattern::Guarded(inner, guard) => {
+                let child = self.add_pattern(inner);
+                self.add_child(node, child, None);
+                let child = self.add_expr(guard);
+                self.add_child(node, child, Some(format!("guard")));
+            }
+            MirPattern::Cons(head, tail) => {
+                let child = self.add_pattern(head);
+                self.add_child(node, child, Some(format!("head")));
+                let child = self.add_pattern(tail);
+                self.add_child(node, child, Some(format!("tail")));
+            }
+            _ => {}
+        }
+        node
+    }
+
+    fn add_expr(&mut self, expr_id: &MirExprId) -> usize {
+        let expr = &self.program.exprs.get(expr_id).item;
+        let (name, details) = match expr {
+            MirExpr::ArgRef(index) => (format!("ArgRef"), vec![format!("{}", index)]),
+            MirExpr::Bind(..) => (format!("Bind"), vec![]),
+            MirExpr::CaseOf(..) => (format!("CaseOf"), vec![]),
+            MirExpr::CharLiteral(v) => (format!("CharLiteral"), vec![format!("{:?}", v)]),
+            MirExpr::Clone(_) => (format!("Clone"), vec![]),
+            MirExpr::Deref(_) => (format!("Deref"), vec![]),
+            MirExpr::Do(_) => (format!("Do"), vec![]),
+            MirExpr::DynamicFunctionCall(..) => (format!("DynamicFunctionCall"), vec![]),
+            MirExpr::ExprValue(_, pattern_id) => {
+                (format!("ExprValue"), vec![format!("{}", pattern_id)])
+            }
+            MirExpr::FieldAccess(index, _) => (format!("FieldAccess"), vec![format!("{}", index)]),
+            MirExpr::FloatLiteral(v) => (format!("FloatLiteral"), vec![format!("{}", v)]),
+            MirExpr::Formatter(fmt, _) => (format!("Formatter"), vec![format!("{:?}", fmt)]),
+            MirExpr::If(..) => (format!("If"), vec![]),
+            MirExpr::IntegerLiteral(v) => (format!("IntegerLiteral"), vec![format!("{}", v)]),
+            MirExpr::List(_) => (format!("List"), vec![]),
+            MirExpr::RecordInitialization(id, _) => (
+                format!("RecordInitialization"),
+                vec![get_mir_typedef_name(self.program, id)],
+            ),
+            MirExpr::RecordUpdate(..) => (format!("RecordUpdate"), vec![]),
+            MirExpr::StaticFunctionCall(id, _) => {
+                let function = self.program.functions.get(id);
+                (
+                    format!("StaticFunctionCall"),
+                    vec![format!("{}.{}", function.module, function.name)],
+                )
+            }
+            MirExpr::PartialFunctionCall(id, _) => {
+                (format!("PartialFunctionCall"), vec![format!("{}", id)])
+            }
+            MirExpr::StringLiteral(v) => (format!("StringLiteral"), vec![format!("{:?}", v)]),
+            MirExpr::Return(_) => (format!("Return"), vec![]),
+            MirExpr::Loop(..) => (format!("Loop"), vec![]),
+            MirExpr::Continue(_) => (format!("Continue"), vec![]),
+            MirExpr::Break(_) => (format!("Break"), vec![]),
+        };
+        let node = self.graph.add_node(name);
+        for detail in details {
+            self.graph.add_element(node, detail);
+        }
+        if let Some(ty) = self.program.expr_types.get(expr_id) {
+            self.graph.add_element(node, ty.to_string(self.program));
+        }
+        match expr {
+            MirExpr::StaticFunctionCall(_, args)
+            | MirExpr::PartialFunctionCall(_, args)
+            | MirExpr::List(args)
+            | MirExpr::Do(args)
+            | MirExpr::Formatter(_, args) => self.add_exprs(node, args),
+            MirExpr::DynamicFunctionCall(callable, args) => {
+                let child = self.add_expr(callable);
+                self.add_child(node, child, Some(format!("callable")));
+                self.add_exprs(node, args);
+            }
+            MirExpr::If(cond, true_branch, false_branch) => {
+                let child = self.add_expr(cond);
+                self.add_child(node, child, Some(format!("cond")));
+                let child = self.add_expr(true_branch);
+                self.add_child(node, child, Some(format!("then")));
+                let child = self.add_expr(false_branch);
+                self.add_child(node, child, Some(format!("else")));
+            }
+            MirExpr::Bind(pattern_id, rhs) => {
+                let child = self.add_pattern(pattern_id);
+                self.add_child(node, child, None);
+                self.add_exprs(node, &[*rhs]);
+            }
+            MirExpr::Clone(receiver)
+            | MirExpr::Deref(receiver)
+            | MirExpr::FieldAccess(_, receiver)
+            | MirExpr::Return(receiver)
+            | MirExpr::Continue(receiver)
+            | MirExpr::Break(receiver) => self.add_exprs(node, &[*receiver]),
+            MirExpr::CaseOf(body, cases) => {
+                self.add_exprs(node, &[*body]);
+                for case in cases {
+                    let pattern = self.add_pattern(&case.pattern_id);
+                    self.add_child(node, pattern, Some(format!("case")));
+                    let child = self.add_expr(&case.body);
+                    self.add_child(pattern, child, None);
+                }
+            }
+            MirExpr::RecordInitialization(_, items) => {
+                let items: Vec<_> = items.iter().map(|(expr_id, _)| *expr_id).collect();
+                self.add_exprs(node, &items);
+            }
+            MirExpr::RecordUpdate(receiver, items) => {
+                self.add_exprs(node, &[*receiver]);
+                let items: Vec<_> = items.iter().map(|(expr_id, _)| *expr_id).collect();
+                self.add_exprs(node, &items);
+            }
+            MirExpr::Loop(pattern_id, initializer, items) => {
+                let child = self.add_pattern(pattern_id);
+                self.add_child(node, child, None);
+                let child = self.add_expr(initializer);
+                self.add_child(node, child, Some(format!("init")));
+                self.add_exprs(node, items);
+            }
+            _ => {}
+        }
+        node
+    }
+}
+
+pub fn create_mir_function_graph(
+    program: &MirProgram,
+    function_id: MirFunctionId,
+) -> Option<Graph> {
+    let function = program.functions.get(&function_id);
+    let body = match &function.info {
+        MirFunctionInfo::Normal(body) => *body,
+        MirFunctionInfo::ExternClassImpl(_, _, body) => *body,
+        _ => return None,
+    };
+    let name = format!("{}.{}", function.module, function.name);
+    let mut builder = MirTreeBuilder {
+        program: program,
+        graph: Graph::new(get_file_name(&name, function_id.id)),
+    };
+    let root = builder.graph.add_node(name);
+    let child = builder.add_expr(&body);
+    builder.add_child(root, child, None);
+    Some(builder.graph)
+}
+
+pub fn visualize_ir(
+    program: &IrProgram,
+    imports: &BTreeMap<String, BTreeSet<String>>,
+    dir: &Path,
+) -> IoResult<()> {
+    create_dependency_group_graph(program).generate_dot(dir)?;
+    create_class_graph(program).generate_dot(dir)?;
+    create_import_graph(imports).generate_dot(dir)?;
+    let ir_dir = dir.join("ir");
+    for function_id in program.functions.items.keys() {
+        if let Some(graph) = create_ir_function_graph(program, *function_id) {
+            graph.generate_dot(&ir_dir)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn visualize_mir(program: &MirProgram, dir: &Path) -> IoResult<()> {
+    let mir_dir = dir.join("mir");
+    for function_id in program.functions.items.keys() {
+        if let Some(graph) = create_mir_function_graph(program, *function_id) {
+            graph.generate_dot(&mir_dir)?;
+        }
+    }
+    Ok(())
+}
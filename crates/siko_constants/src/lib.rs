@@ -4,6 +4,13 @@ pub enum BuiltinOperator {
     Sub,
     Mul,
     Div,
+    Mod,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     PipeForward,
     And,
     Or,
@@ -26,6 +33,13 @@ impl BuiltinOperator {
             BuiltinOperator::Sub => format!("Std.Ops.opSub"),
             BuiltinOperator::Mul => format!("Std.Ops.opMul"),
             BuiltinOperator::Div => format!("Std.Ops.opDiv"),
+            BuiltinOperator::Mod => format!("Std.Ops.opMod"),
+            BuiltinOperator::Rem => format!("Std.Ops.opRem"),
+            BuiltinOperator::BitAnd => format!("Std.Ops.opBitAnd"),
+            BuiltinOperator::BitOr => format!("Std.Ops.opBitOr"),
+            BuiltinOperator::BitXor => format!("Std.Ops.opBitXor"),
+            BuiltinOperator::ShiftLeft => format!("Std.Ops.opShiftLeft"),
+            BuiltinOperator::ShiftRight => format!("Std.Ops.opShiftRight"),
             BuiltinOperator::Equals => format!("Std.Ops.opEq"),
             BuiltinOperator::NotEquals => format!("Std.Ops.opNotEq"),
             BuiltinOperator::LessThan => format!("Std.Ops.opLessThan"),
@@ -49,6 +63,7 @@ pub const INT_TYPE_NAME: &str = "Int";
 pub const FLOAT_MODULE_NAME: &str = "Float";
 pub const FLOAT_TYPE_NAME: &str = "Float";
 pub const CHAR_MODULE_NAME: &str = "Char";
+pub const MATH_MODULE_NAME: &str = "Math";
 pub const CHAR_TYPE_NAME: &str = "Char";
 pub const OPTION_MODULE_NAME: &str = "Option";
 pub const OPTION_TYPE_NAME: &str = "Option";
@@ -56,6 +71,8 @@ pub const RESULT_MODULE_NAME: &str = "Result";
 pub const RESULT_TYPE_NAME: &str = "Result";
 pub const MAP_MODULE_NAME: &str = "Map";
 pub const MAP_TYPE_NAME: &str = "Map";
+pub const SET_MODULE_NAME: &str = "Set";
+pub const SET_TYPE_NAME: &str = "Set";
 pub const ORDERING_MODULE_NAME: &str = "Ordering";
 pub const ORDERING_TYPE_NAME: &str = "Ordering";
 pub const STRING_MODULE_NAME: &str = "String";
@@ -79,8 +96,15 @@ pub const ORD_CLASS_NAME: &str = "Ord";
 pub const ORD_OP_NAME: &str = "cmp";
 pub const STD_OPS_MODULE_NAME: &str = "Std.Ops";
 pub const STD_UTIL_BASIC_MODULE_NAME: &str = "Std.Util.Basic";
+pub const STD_ACTOR_MODULE_NAME: &str = "Std.Actor";
+pub const STD_IO_MODULE_NAME: &str = "Std.IO";
+pub const STD_ENV_MODULE_NAME: &str = "Std.Env";
+pub const IO_ERROR_TYPE_NAME: &str = "IOError";
+pub const ACTOR_CLASS_NAME: &str = "Actor";
+pub const PROTOCOL_CLASS_NAME: &str = "Protocol";
 pub const MIR_INTERNAL_MODULE_NAME: &str = "__siko__";
 pub const MIR_FUNCTION_TRAIT_NAME: &str = "Function";
+pub const MIR_ITERATOR_TRAIT_NAME: &str = "SikoIterator";
 
 pub fn get_qualified_list_type_name() -> String {
     format!("{}.{}", LIST_MODULE_NAME, LIST_TYPE_NAME)
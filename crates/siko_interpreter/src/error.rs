@@ -0,0 +1,14 @@
+use siko_location_info::location_id::LocationId;
+
+#[derive(Debug, Clone)]
+pub struct CallStackFrame {
+    pub function: String,
+    pub call_site: Option<LocationId>,
+}
+
+#[derive(Debug)]
+pub struct RuntimeError {
+    pub msg: String,
+    pub location_id: LocationId,
+    pub call_stack: Vec<CallStackFrame>,
+}
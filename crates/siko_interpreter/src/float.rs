@@ -121,6 +121,196 @@ impl ExternFunction for FloatShow {
     }
 }
 
+pub struct FloatRound {}
+
+impl ExternFunction for FloatRound {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Int(value.round() as i64), ty);
+    }
+}
+
+pub struct FloatFloor {}
+
+impl ExternFunction for FloatFloor {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Int(value.floor() as i64), ty);
+    }
+}
+
+pub struct FloatCeil {}
+
+impl ExternFunction for FloatCeil {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Int(value.ceil() as i64), ty);
+    }
+}
+
+pub struct FloatTruncate {}
+
+impl ExternFunction for FloatTruncate {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Int(value.trunc() as i64), ty);
+    }
+}
+
+pub struct FloatAbs {}
+
+impl ExternFunction for FloatAbs {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.abs()), ty);
+    }
+}
+
+pub struct FloatNegate {}
+
+impl ExternFunction for FloatNegate {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(-value), ty);
+    }
+}
+
+pub struct FloatIsNaN {}
+
+impl ExternFunction for FloatIsNaN {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Interpreter::get_bool_value(value.is_nan());
+    }
+}
+
+pub struct FloatIsInfinite {}
+
+impl ExternFunction for FloatIsInfinite {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Interpreter::get_bool_value(value.is_infinite());
+    }
+}
+
+pub struct FloatInfinity {}
+
+impl ExternFunction for FloatInfinity {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::INFINITY), ty);
+    }
+}
+
+pub struct FloatNaN {}
+
+impl ExternFunction for FloatNaN {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::NAN), ty);
+    }
+}
+
+pub struct FloatEpsilon {}
+
+impl ExternFunction for FloatEpsilon {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::EPSILON), ty);
+    }
+}
+
+pub struct FloatMaxValue {}
+
+impl ExternFunction for FloatMaxValue {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::MAX), ty);
+    }
+}
+
+pub struct FloatMinValue {}
+
+impl ExternFunction for FloatMinValue {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::MIN), ty);
+    }
+}
+
 pub fn register_extern_functions(interpreter: &mut Interpreter) {
     interpreter.add_extern_function(FLOAT_MODULE_NAME, "opAdd", Box::new(FloatAdd {}));
     interpreter.add_extern_function(FLOAT_MODULE_NAME, "opSub", Box::new(FloatSub {}));
@@ -133,4 +323,21 @@ pub fn register_extern_functions(interpreter: &mut Interpreter) {
         Box::new(FloatPartialOrd {}),
     );
     interpreter.add_extern_function(FLOAT_MODULE_NAME, "show", Box::new(FloatShow {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "round", Box::new(FloatRound {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "floor", Box::new(FloatFloor {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "ceil", Box::new(FloatCeil {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "truncate", Box::new(FloatTruncate {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "abs", Box::new(FloatAbs {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "negate", Box::new(FloatNegate {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "isNaN", Box::new(FloatIsNaN {}));
+    interpreter.add_extern_function(
+        FLOAT_MODULE_NAME,
+        "isInfinite",
+        Box::new(FloatIsInfinite {}),
+    );
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "infinity", Box::new(FloatInfinity {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "nan", Box::new(FloatNaN {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "epsilon", Box::new(FloatEpsilon {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "maxValue", Box::new(FloatMaxValue {}));
+    interpreter.add_extern_function(FLOAT_MODULE_NAME, "minValue", Box::new(FloatMinValue {}));
 }
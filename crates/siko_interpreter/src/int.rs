@@ -74,6 +74,124 @@ impl ExternFunction for IntDiv {
     }
 }
 
+pub struct IntMod {}
+
+impl ExternFunction for IntMod {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        let m = l % r;
+        let value = if m != 0 && ((m < 0) != (r < 0)) {
+            m + r
+        } else {
+            m
+        };
+        return Value::new(ValueCore::Int(value), ty);
+    }
+}
+
+pub struct IntRem {}
+
+impl ExternFunction for IntRem {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::Int(l % r), ty);
+    }
+}
+
+pub struct IntBitAnd {}
+
+impl ExternFunction for IntBitAnd {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::Int(l & r), ty);
+    }
+}
+
+pub struct IntBitOr {}
+
+impl ExternFunction for IntBitOr {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::Int(l | r), ty);
+    }
+}
+
+pub struct IntBitXor {}
+
+impl ExternFunction for IntBitXor {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::Int(l ^ r), ty);
+    }
+}
+
+pub struct IntShiftLeft {}
+
+impl ExternFunction for IntShiftLeft {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::Int(l.wrapping_shl(r as u32)), ty);
+    }
+}
+
+pub struct IntShiftRight {}
+
+impl ExternFunction for IntShiftRight {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let l = environment.get_arg_by_index(0).core.as_int();
+        let r = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::Int(l.wrapping_shr(r as u32)), ty);
+    }
+}
+
 pub struct IntPartialEq {}
 
 impl ExternFunction for IntPartialEq {
@@ -139,13 +257,88 @@ impl ExternFunction for IntShow {
     }
 }
 
+pub struct IntToFloat {}
+
+impl ExternFunction for IntToFloat {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_int();
+        return Value::new(ValueCore::Float(value as f64), ty);
+    }
+}
+
+pub struct IntAbs {}
+
+impl ExternFunction for IntAbs {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_int();
+        return Value::new(ValueCore::Int(value.abs()), ty);
+    }
+}
+
+pub struct IntNegate {}
+
+impl ExternFunction for IntNegate {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_int();
+        return Value::new(ValueCore::Int(-value), ty);
+    }
+}
+
+pub struct IntPow {}
+
+impl ExternFunction for IntPow {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let base = environment.get_arg_by_index(0).core.as_int();
+        let exp = environment.get_arg_by_index(1).core.as_int();
+        if exp < 0 {
+            panic!("Negative exponent {}", exp);
+        }
+        return Value::new(ValueCore::Int(base.pow(exp as u32)), ty);
+    }
+}
+
 pub fn register_extern_functions(interpreter: &mut Interpreter) {
     interpreter.add_extern_function(INT_MODULE_NAME, "opAdd", Box::new(IntAdd {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "opSub", Box::new(IntSub {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "opMul", Box::new(IntMul {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "opDiv", Box::new(IntDiv {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opMod", Box::new(IntMod {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opRem", Box::new(IntRem {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opBitAnd", Box::new(IntBitAnd {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opBitOr", Box::new(IntBitOr {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opBitXor", Box::new(IntBitXor {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opShiftLeft", Box::new(IntShiftLeft {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "opShiftRight", Box::new(IntShiftRight {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "opEq", Box::new(IntPartialEq {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "partialCmp", Box::new(IntPartialOrd {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "cmp", Box::new(IntOrd {}));
     interpreter.add_extern_function(INT_MODULE_NAME, "show", Box::new(IntShow {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "toFloat", Box::new(IntToFloat {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "abs", Box::new(IntAbs {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "negate", Box::new(IntNegate {}));
+    interpreter.add_extern_function(INT_MODULE_NAME, "pow", Box::new(IntPow {}));
 }
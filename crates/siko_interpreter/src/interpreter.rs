@@ -1,11 +1,18 @@
 use crate::char;
 use crate::environment::Environment;
+use crate::error::CallStackFrame;
+use crate::error::RuntimeError;
 use crate::extern_function::ExternFunction;
 use crate::float;
 use crate::int;
 use crate::iterator;
 use crate::list;
 use crate::map;
+use crate::math;
+use crate::set;
+use crate::std_actor;
+use crate::std_env;
+use crate::std_io;
 use crate::std_ops;
 use crate::std_util;
 use crate::std_util_basic;
@@ -17,10 +24,14 @@ use crate::value::Callable;
 use crate::value::CallableKind;
 use crate::value::Value;
 use crate::value::ValueCore;
+use siko_constants::IO_ERROR_TYPE_NAME;
 use siko_constants::OPTION_MODULE_NAME;
 use siko_constants::OPTION_TYPE_NAME;
 use siko_constants::ORDERING_MODULE_NAME;
 use siko_constants::ORDERING_TYPE_NAME;
+use siko_constants::RESULT_MODULE_NAME;
+use siko_constants::RESULT_TYPE_NAME;
+use siko_constants::STD_IO_MODULE_NAME;
 use siko_ir::class::ClassMember;
 use siko_ir::class::ClassMemberId;
 use siko_ir::data::Adt;
@@ -37,10 +48,13 @@ use siko_ir::pattern::RangeKind;
 use siko_ir::program::Program;
 use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
-use siko_location_info::error_context::ErrorContext;
+use siko_location_info::location_id::LocationId;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::panic;
+use std::panic::AssertUnwindSafe;
 use std::thread_local;
 
 thread_local! {
@@ -71,31 +85,57 @@ enum ExprResult {
     Return(Value),
     Continue(Value),
     Break(Value),
-    Abort,
+    Abort(RuntimeError),
 }
 
 #[derive(Clone)]
 pub struct TypeDefIdCache {
     pub option_id: TypeDefId,
     pub ordering_id: TypeDefId,
+    pub result_id: TypeDefId,
+    pub io_error_id: TypeDefId,
     pub option_variants: VariantCache,
     pub ordering_variants: VariantCache,
+    pub result_variants: VariantCache,
+    pub io_error_variants: VariantCache,
+}
+
+struct ActorScheduler {
+    actors: Vec<Value>,
+    messages: VecDeque<(usize, Value)>,
+}
+
+impl ActorScheduler {
+    fn new() -> ActorScheduler {
+        ActorScheduler {
+            actors: Vec::new(),
+            messages: VecDeque::new(),
+        }
+    }
 }
 
 pub struct Interpreter {
     program: Program,
-    error_context: ErrorContext,
     typedefid_cache: Option<TypeDefIdCache>,
     extern_functions: BTreeMap<(String, String), Box<dyn ExternFunction>>,
+    program_args: Vec<String>,
+    scheduler: RefCell<ActorScheduler>,
+    // the frames are formatted only when a runtime error is created
+    call_stack: RefCell<Vec<(FunctionId, Option<ExprId>)>>,
+    // values of the functions evaluated by evaluate_binding
+    bindings: RefCell<BTreeMap<FunctionId, Value>>,
 }
 
 impl Interpreter {
-    fn new(program: Program, error_context: ErrorContext) -> Interpreter {
+    fn new(program: Program, program_args: Vec<String>) -> Interpreter {
         Interpreter {
             program: program,
-            error_context: error_context,
             typedefid_cache: None,
             extern_functions: BTreeMap::new(),
+            program_args: program_args,
+            scheduler: RefCell::new(ActorScheduler::new()),
+            call_stack: RefCell::new(Vec::new()),
+            bindings: RefCell::new(BTreeMap::new()),
         }
     }
 
@@ -237,6 +277,9 @@ impl Interpreter {
                 if self.match_pattern(id, value, environment, unifier) {
                     let guard_value = match self.eval_expr(*guard_expr_id, environment, unifier) {
                         ExprResult::Ok(v) => v,
+                        // match_pattern cannot return an ExprResult, so a runtime error raised
+                        // by the guard unwinds to Interpreter::run like the ones in extern calls
+                        ExprResult::Abort(err) => panic::resume_unwind(Box::new(err)),
                         _ => unimplemented!(),
                     };
                     return guard_value.core.as_bool();
@@ -278,6 +321,22 @@ impl Interpreter {
                 };
                 return r;
             }
+            Pattern::IntegerRange(start, end, kind) => {
+                let r = match &value.core {
+                    ValueCore::Int(v) => match kind {
+                        RangeKind::Exclusive => {
+                            let range = std::ops::Range { start, end };
+                            range.contains(&v)
+                        }
+                        RangeKind::Inclusive => {
+                            let range = std::ops::RangeInclusive::new(start, end);
+                            range.contains(&v)
+                        }
+                    },
+                    _ => false,
+                };
+                return r;
+            }
             Pattern::StringLiteral(p_v) => {
                 let r = match &value.core {
                     ValueCore::String(v) => p_v == v,
@@ -285,6 +344,37 @@ impl Interpreter {
                 };
                 return r;
             }
+            Pattern::List(p_ids) => match &value.core {
+                ValueCore::List(vs) => {
+                    if vs.len() != p_ids.len() {
+                        return false;
+                    }
+                    for (p_id, v) in p_ids.iter().zip(vs.iter()) {
+                        if !self.match_pattern(p_id, v, environment, unifier) {
+                            return false;
+                        }
+                    }
+                    return true;
+                }
+                _ => {
+                    return false;
+                }
+            },
+            Pattern::Cons(head, tail) => match &value.core {
+                ValueCore::List(vs) => {
+                    if vs.is_empty() {
+                        return false;
+                    }
+                    if !self.match_pattern(head, &vs[0], environment, unifier) {
+                        return false;
+                    }
+                    let rest = Value::new(ValueCore::List(vs[1..].to_vec()), value.ty.clone());
+                    return self.match_pattern(tail, &rest, environment, unifier);
+                }
+                _ => {
+                    return false;
+                }
+            },
         }
     }
 
@@ -312,6 +402,24 @@ impl Interpreter {
         })
     }
 
+    pub fn get_int_type() -> Type {
+        INTERPRETER_CONTEXT.with(|i| {
+            let b = i.borrow();
+            let i = b.as_ref().expect("Interpreter not set");
+            let int_ty = i.program.get_int_type();
+            int_ty
+        })
+    }
+
+    pub fn get_float_type() -> Type {
+        INTERPRETER_CONTEXT.with(|i| {
+            let b = i.borrow();
+            let i = b.as_ref().expect("Interpreter not set");
+            let float_ty = i.program.get_float_type();
+            float_ty
+        })
+    }
+
     pub fn get_char_type() -> Type {
         INTERPRETER_CONTEXT.with(|i| {
             let b = i.borrow();
@@ -370,11 +478,8 @@ impl Interpreter {
                 .expect("Show not found");
             let class = i.program.classes.get(class_id);
             let class_member_id = class.members.get(member_name).expect("show not found");
-            if let ExprResult::Ok(v) = i.call_class_member(class_member_id, args, None, expr_ty) {
-                v
-            } else {
-                unreachable!()
-            }
+            let result = i.call_class_member(class_member_id, args, None, expr_ty);
+            Interpreter::unwrap_result(result)
         })
     }
 
@@ -382,23 +487,47 @@ impl Interpreter {
         INTERPRETER_CONTEXT.with(|i| {
             let b = i.borrow();
             let i = b.as_ref().expect("Interpreter not set");
-            if let ExprResult::Ok(v) = i.call(callable, args, expr_id) {
-                v
-            } else {
-                unreachable!()
-            }
+            let result = i.call(callable, args, expr_id);
+            Interpreter::unwrap_result(result)
         })
     }
 
-    pub fn call_abort(current_expr: ExprId) {
-        INTERPRETER_CONTEXT.with(|i| {
+    // Extern functions cannot return an ExprResult, so a runtime error raised by them
+    // (or by Siko code they call back into) unwinds to Interpreter::run instead.
+    fn unwrap_result(result: ExprResult) -> Value {
+        match result {
+            ExprResult::Ok(v) => v,
+            ExprResult::Abort(err) => panic::resume_unwind(Box::new(err)),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn call_abort(msg: &str, current_expr: ExprId) -> ! {
+        let err = INTERPRETER_CONTEXT.with(|i| {
             let b = i.borrow();
             let i = b.as_ref().expect("Interpreter not set");
             let location_id = i.program.exprs.get(&current_expr).location_id;
-            i.error_context
-                .report_error(format!("Assertion failed"), location_id);
-            panic!("Abort not implemented");
-        })
+            i.create_runtime_error(msg.to_string(), location_id)
+        });
+        panic::resume_unwind(Box::new(err))
+    }
+
+    fn create_runtime_error(&self, msg: String, location_id: LocationId) -> RuntimeError {
+        let call_stack = self
+            .call_stack
+            .borrow()
+            .iter()
+            .rev()
+            .map(|(function_id, call_expr)| CallStackFrame {
+                function: format!("{}", self.program.functions.get(function_id).info),
+                call_site: call_expr.map(|expr_id| self.program.exprs.get(&expr_id).location_id),
+            })
+            .collect();
+        RuntimeError {
+            msg: msg,
+            location_id: location_id,
+            call_stack: call_stack,
+        }
     }
 
     pub fn call_op_eq(arg1: Value, arg2: Value) -> Value {
@@ -556,6 +685,13 @@ impl Interpreter {
                             .default_implementation
                             .expect("Default implementation not found")
                     };
+                // the type args of the instance only appear in the type of the member function
+                let func_ty = self
+                    .program
+                    .get_function_type(&member_function_id)
+                    .remove_fixed_types();
+                let call_unifier = self.get_call_unifier(&arg_values, &func_ty, &expr_ty);
+                let function_type = call_unifier.apply(&func_ty);
                 let callable = Value::new(
                     ValueCore::Callable(Callable {
                         kind: CallableKind::FunctionId(member_function_id),
@@ -883,6 +1019,7 @@ impl Interpreter {
                             }
                             ExprResult::Continue(v) => {
                                 loop_val = v;
+                                break;
                             }
                             ExprResult::Break(v) => {
                                 return ExprResult::Ok(v);
@@ -906,6 +1043,7 @@ impl Interpreter {
                     return r;
                 }
             },
+            Expr::Hole(_) => unreachable!(),
         }
     }
 
@@ -919,7 +1057,10 @@ impl Interpreter {
         ty: Type,
     ) -> ExprResult {
         if (module, name) == ("Std.Util.Basic", "abort") {
-            return ExprResult::Abort;
+            let current_expr = current_expr.expect("No current expr");
+            let location_id = self.program.exprs.get(&current_expr).location_id;
+            let err = self.create_runtime_error(format!("Abort called"), location_id);
+            return ExprResult::Abort(err);
         }
         if let Some(f) = self
             .extern_functions
@@ -1114,8 +1255,25 @@ impl Interpreter {
         current_expr: Option<ExprId>,
         unifier: &Unifier,
         expr_ty: Type,
+    ) -> ExprResult {
+        self.call_stack.borrow_mut().push((id, current_expr));
+        let result = self.execute_function(id, environment, current_expr, unifier, expr_ty);
+        self.call_stack.borrow_mut().pop();
+        result
+    }
+
+    fn execute_function(
+        &self,
+        id: FunctionId,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        unifier: &Unifier,
+        expr_ty: Type,
     ) -> ExprResult {
         assert!(expr_ty.is_concrete_type());
+        if let Some(value) = self.bindings.borrow().get(&id) {
+            return ExprResult::Ok(value.clone());
+        }
         let function = self.program.functions.get(&id);
         match &function.info {
             FunctionInfo::NamedFunction(info) => match info.body {
@@ -1136,6 +1294,9 @@ impl Interpreter {
             FunctionInfo::Lambda(info) => {
                 return self.eval_expr(info.body, environment, unifier);
             }
+            FunctionInfo::LocalFunction(info) => {
+                return self.eval_expr(info.body, environment, unifier);
+            }
             FunctionInfo::VariantConstructor(info) => {
                 let adt = self.program.typedefs.get(&info.type_id).get_adt();
                 let variant = &adt.variants[info.index];
@@ -1171,15 +1332,33 @@ impl Interpreter {
         let ordering = self
             .program
             .get_adt_by_name(ORDERING_MODULE_NAME, ORDERING_TYPE_NAME);
+        let result = self
+            .program
+            .get_adt_by_name(RESULT_MODULE_NAME, RESULT_TYPE_NAME);
+        let io_error = self
+            .program
+            .get_adt_by_name(STD_IO_MODULE_NAME, IO_ERROR_TYPE_NAME);
         let cache = TypeDefIdCache {
             option_id: option.id,
             ordering_id: ordering.id,
+            result_id: result.id,
+            io_error_id: io_error.id,
             option_variants: VariantCache::new(option),
             ordering_variants: VariantCache::new(ordering),
+            result_variants: VariantCache::new(result),
+            io_error_variants: VariantCache::new(io_error),
         };
         self.typedefid_cache = Some(cache);
     }
 
+    pub fn get_program_args() -> Vec<String> {
+        INTERPRETER_CONTEXT.with(|i| {
+            let b = i.borrow();
+            let i = b.as_ref().expect("Interpreter not set");
+            i.program_args.clone()
+        })
+    }
+
     pub fn get_typedef_id_cache() -> TypeDefIdCache {
         INTERPRETER_CONTEXT.with(|i| {
             let i = i.borrow();
@@ -1191,7 +1370,7 @@ impl Interpreter {
         })
     }
 
-    fn execute_main(interpreter: &Interpreter) -> Value {
+    fn execute_main(interpreter: &Interpreter) -> Result<Value, RuntimeError> {
         let main_id = interpreter.program.get_main().expect("Main does not exist");
         let mut environment = Environment::new(CallableKind::FunctionId(main_id), vec![]);
         let unifier = interpreter.program.get_unifier();
@@ -1202,14 +1381,121 @@ impl Interpreter {
             &unifier,
             Type::Tuple(vec![]),
         ) {
-            ExprResult::Ok(v) => v,
-            ExprResult::Return(v) => v,
-            ExprResult::Abort => panic!("Abort called"),
+            ExprResult::Ok(v) => Ok(v),
+            ExprResult::Return(v) => Ok(v),
+            ExprResult::Abort(err) => Err(err),
+            ExprResult::Continue(_) => panic!("Continue outside loop"),
+            ExprResult::Break(_) => panic!("Break outside loop"),
+        }
+    }
+
+    pub fn spawn_actor(actor: Value) -> usize {
+        INTERPRETER_CONTEXT.with(|i| {
+            let b = i.borrow();
+            let i = b.as_ref().expect("Interpreter not set");
+            let mut scheduler = i.scheduler.borrow_mut();
+            scheduler.actors.push(actor);
+            scheduler.actors.len() - 1
+        })
+    }
+
+    // A message the actor has no handler for is reported at the send, where the
+    // call stack still shows where it came from.
+    pub fn send_message(pid: usize, message: Value, current_expr: ExprId) {
+        let missing_handler = INTERPRETER_CONTEXT.with(|i| {
+            let b = i.borrow();
+            let i = b.as_ref().expect("Interpreter not set");
+            let actor_ty = i.scheduler.borrow().actors[pid].ty.clone();
+            let actor = i
+                .program
+                .get_actor_by_type(actor_ty.get_typedef_id())
+                .expect("Actor not found");
+            let protocol = i
+                .program
+                .get_protocol_by_type(message.ty.get_typedef_id())
+                .expect("Protocol not found");
+            if actor.get_handler(protocol.id).is_none() {
+                return Some(format!(
+                    "actor {} has no handler for protocol {}",
+                    actor.name, protocol.name
+                ));
+            }
+            i.scheduler.borrow_mut().messages.push_back((pid, message));
+            None
+        });
+        if let Some(msg) = missing_handler {
+            Interpreter::call_abort(&msg, current_expr);
+        }
+    }
+
+    fn call_function(
+        &self,
+        function_id: FunctionId,
+        args: Vec<Value>,
+        result_ty: Type,
+    ) -> Result<Value, RuntimeError> {
+        let func_ty = self
+            .program
+            .get_function_type(&function_id)
+            .remove_fixed_types();
+        let call_unifier = self.get_call_unifier(&args, &func_ty, &result_ty);
+        let function_type = call_unifier.apply(&func_ty);
+        let callable = Value::new(
+            ValueCore::Callable(Callable {
+                kind: CallableKind::FunctionId(function_id),
+                values: vec![],
+                unifier: call_unifier,
+            }),
+            function_type,
+        );
+        match self.call(callable, args, None) {
+            ExprResult::Ok(v) => Ok(v),
+            ExprResult::Return(v) => Ok(v),
+            ExprResult::Abort(err) => Err(err),
             ExprResult::Continue(_) => panic!("Continue outside loop"),
             ExprResult::Break(_) => panic!("Break outside loop"),
         }
     }
 
+    // Messages are delivered one by one in the order they were sent, which keeps
+    // the execution of actor programs deterministic in the interpreter.
+    fn run_actors(interpreter: &Interpreter) -> Result<(), RuntimeError> {
+        loop {
+            let next = interpreter.scheduler.borrow_mut().messages.pop_front();
+            let (pid, message) = match next {
+                Some(next) => next,
+                None => break,
+            };
+            let actor_value = interpreter.scheduler.borrow().actors[pid].clone();
+            let actor = interpreter
+                .program
+                .get_actor_by_type(actor_value.ty.get_typedef_id())
+                .expect("Actor not found");
+            let protocol = interpreter
+                .program
+                .get_protocol_by_type(message.ty.get_typedef_id())
+                .expect("Protocol not found");
+            let handler = actor
+                .get_handler(protocol.id)
+                .expect("Protocol handler not found");
+            let (state, payload) = match (actor_value.core, message.core) {
+                (ValueCore::Record(_, mut state), ValueCore::Record(_, mut payload)) => {
+                    (state.remove(0), payload.remove(0))
+                }
+                _ => unreachable!(),
+            };
+            let state_ty = state.ty.clone();
+            let new_state =
+                interpreter.call_function(handler.function_id, vec![state, payload], state_ty)?;
+            let new_actor_value = Value::new(
+                ValueCore::Record(actor.typedef_id, vec![new_state]),
+                actor_value.ty,
+            );
+            interpreter.scheduler.borrow_mut().actors[pid] = new_actor_value;
+        }
+        Ok(())
+    }
+
     pub fn add_extern_function(
         &mut self,
         module: &str,
@@ -1220,27 +1506,92 @@ impl Interpreter {
             .insert((module.to_string(), name.to_string()), extern_function);
     }
 
-    pub fn run(program: Program, error_context: ErrorContext) -> Value {
-        let mut interpreter = Interpreter::new(program, error_context);
+    fn create(program: Program, program_args: Vec<String>) -> Interpreter {
+        let mut interpreter = Interpreter::new(program, program_args);
         int::register_extern_functions(&mut interpreter);
         char::register_extern_functions(&mut interpreter);
         float::register_extern_functions(&mut interpreter);
+        math::register_extern_functions(&mut interpreter);
         string::register_extern_functions(&mut interpreter);
         map::register_extern_functions(&mut interpreter);
+        set::register_extern_functions(&mut interpreter);
         list::register_extern_functions(&mut interpreter);
         std_util_basic::register_extern_functions(&mut interpreter);
         std_util::register_extern_functions(&mut interpreter);
         std_ops::register_extern_functions(&mut interpreter);
         iterator::register_extern_functions(&mut interpreter);
+        std_actor::register_extern_functions(&mut interpreter);
+        std_io::register_extern_functions(&mut interpreter);
+        std_env::register_extern_functions(&mut interpreter);
         interpreter.build_typedefid_cache();
+        interpreter
+    }
+
+    fn with_interpreter<F, R>(f: F) -> Result<R, RuntimeError>
+    where
+        F: FnOnce(&Interpreter) -> Result<R, RuntimeError>,
+    {
+        INTERPRETER_CONTEXT.with(|c| {
+            let p = c.borrow();
+            let i = p.as_ref().expect("Interpreter not set");
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(i)));
+            match result {
+                Ok(result) => result,
+                Err(payload) => match payload.downcast::<RuntimeError>() {
+                    Ok(err) => Err(*err),
+                    Err(payload) => panic::resume_unwind(payload),
+                },
+            }
+        })
+    }
+
+    pub fn run(program: Program, program_args: Vec<String>) -> Result<Value, RuntimeError> {
+        let interpreter = Interpreter::create(program, program_args);
         INTERPRETER_CONTEXT.with(|c| {
             let mut p = c.borrow_mut();
             *p = Some(interpreter);
         });
+        Interpreter::with_interpreter(|i| {
+            let value = Interpreter::execute_main(i)?;
+            Interpreter::run_actors(i)?;
+            Ok(value)
+        })
+    }
+
+    // A session keeps the interpreter and its program between evaluations, the
+    // program can be extended with with_session_program.
+    pub fn start_session(program: Program) {
+        let interpreter = Interpreter::create(program, Vec::new());
         INTERPRETER_CONTEXT.with(|c| {
-            let p = c.borrow();
-            let i = p.as_ref().expect("Interpreter not set");
-            Interpreter::execute_main(i)
+            let mut p = c.borrow_mut();
+            *p = Some(interpreter);
+        });
+    }
+
+    pub fn with_session_program<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Program) -> R,
+    {
+        INTERPRETER_CONTEXT.with(|c| {
+            let mut p = c.borrow_mut();
+            let i = p.as_mut().expect("Interpreter not set");
+            f(&mut i.program)
         })
     }
+
+    // The function is evaluated only once, its later calls return the same value.
+    pub fn evaluate_binding(function_id: FunctionId) -> Result<Value, RuntimeError> {
+        Interpreter::with_interpreter(|i| {
+            i.call_stack.borrow_mut().clear();
+            let result_ty = i.program.get_function_type(&function_id).clone();
+            let value = i.call_function(function_id, vec![], result_ty)?;
+            Interpreter::run_actors(i)?;
+            i.bindings.borrow_mut().insert(function_id, value.clone());
+            Ok(value)
+        })
+    }
+
+    pub fn show_value(value: Value) -> Result<String, RuntimeError> {
+        Interpreter::with_interpreter(|_| Ok(Interpreter::call_show(value)))
+    }
 }
@@ -8,6 +8,22 @@ use siko_ir::expr::ExprId;
 use siko_ir::function::NamedFunctionKind;
 use siko_ir::types::Type;
 
+fn get_item_type(ty: &Type) -> Type {
+    match ty {
+        Type::Named(_, _, args) => args[0].clone(),
+        _ => unreachable!(),
+    }
+}
+
+fn get_count(value: &Value) -> usize {
+    let count = value.core.as_int();
+    if count < 0 {
+        0
+    } else {
+        count as usize
+    }
+}
+
 pub struct Map {}
 
 impl ExternFunction for Map {
@@ -27,6 +43,276 @@ impl ExternFunction for Map {
     }
 }
 
+pub struct Filter {}
+
+impl ExternFunction for Filter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let iterator = environment.get_arg_by_index(1);
+        return Value::new(
+            ValueCore::IteratorFilter(Box::new(iterator), Box::new(func)),
+            ty,
+        );
+    }
+}
+
+pub struct Fold {}
+
+impl ExternFunction for Fold {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let init = environment.get_arg_by_index(1);
+        let iterator = environment.get_arg_by_index(2).core.as_iterator();
+        iterator.fold(init, |acc, elem| {
+            Interpreter::call_func(func.clone(), vec![acc, elem], expr_id)
+        })
+    }
+}
+
+pub struct Zip {}
+
+impl ExternFunction for Zip {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let iterator1 = environment.get_arg_by_index(0);
+        let iterator2 = environment.get_arg_by_index(1);
+        let item_ty = get_item_type(&ty);
+        return Value::new(
+            ValueCore::IteratorZip(Box::new(iterator1), Box::new(iterator2), item_ty),
+            ty,
+        );
+    }
+}
+
+pub struct Enumerate {}
+
+impl ExternFunction for Enumerate {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let iterator = environment.get_arg_by_index(0);
+        let item_ty = get_item_type(&ty);
+        return Value::new(
+            ValueCore::IteratorEnumerate(Box::new(iterator), item_ty),
+            ty,
+        );
+    }
+}
+
+pub struct Take {}
+
+impl ExternFunction for Take {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let count = get_count(&environment.get_arg_by_index(0));
+        let iterator = environment.get_arg_by_index(1);
+        return Value::new(ValueCore::IteratorTake(Box::new(iterator), count), ty);
+    }
+}
+
+pub struct Skip {}
+
+impl ExternFunction for Skip {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let count = get_count(&environment.get_arg_by_index(0));
+        let iterator = environment.get_arg_by_index(1);
+        return Value::new(ValueCore::IteratorSkip(Box::new(iterator), count), ty);
+    }
+}
+
+pub struct TakeWhile {}
+
+impl ExternFunction for TakeWhile {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let iterator = environment.get_arg_by_index(1);
+        return Value::new(
+            ValueCore::IteratorTakeWhile(Box::new(iterator), Box::new(func)),
+            ty,
+        );
+    }
+}
+
+pub struct FlatMap {}
+
+impl ExternFunction for FlatMap {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let iterator = environment.get_arg_by_index(1);
+        return Value::new(
+            ValueCore::IteratorFlatMap(Box::new(iterator), Box::new(func)),
+            ty,
+        );
+    }
+}
+
+pub struct Chain {}
+
+impl ExternFunction for Chain {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let iterator1 = environment.get_arg_by_index(0);
+        let iterator2 = environment.get_arg_by_index(1);
+        return Value::new(
+            ValueCore::IteratorChain(Box::new(iterator1), Box::new(iterator2)),
+            ty,
+        );
+    }
+}
+
+pub struct Any {}
+
+impl ExternFunction for Any {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let mut iterator = environment.get_arg_by_index(1).core.as_iterator();
+        let result = iterator.any(|elem| {
+            Interpreter::call_func(func.clone(), vec![elem], expr_id)
+                .core
+                .as_bool()
+        });
+        return Interpreter::get_bool_value(result);
+    }
+}
+
+pub struct All {}
+
+impl ExternFunction for All {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let mut iterator = environment.get_arg_by_index(1).core.as_iterator();
+        let result = iterator.all(|elem| {
+            Interpreter::call_func(func.clone(), vec![elem], expr_id)
+                .core
+                .as_bool()
+        });
+        return Interpreter::get_bool_value(result);
+    }
+}
+
+pub struct Count {}
+
+impl ExternFunction for Count {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let iterator = environment.get_arg_by_index(0).core.as_iterator();
+        return Value::new(ValueCore::Int(iterator.count() as i64), ty);
+    }
+}
+
+pub struct Sum {}
+
+impl ExternFunction for Sum {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let iterator = environment.get_arg_by_index(0).core.as_iterator();
+        let sum = iterator.map(|elem| elem.core.as_int()).sum();
+        return Value::new(ValueCore::Int(sum), ty);
+    }
+}
+
+pub struct Range {}
+
+impl ExternFunction for Range {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let start = environment.get_arg_by_index(0).core.as_int();
+        let end = environment.get_arg_by_index(1).core.as_int();
+        return Value::new(ValueCore::IteratorRange(start, end), ty);
+    }
+}
+
+pub struct Repeat {}
+
+impl ExternFunction for Repeat {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0);
+        return Value::new(ValueCore::IteratorRepeat(Box::new(value)), ty);
+    }
+}
+
 pub struct ForEach {}
 
 impl ExternFunction for ForEach {
@@ -48,5 +334,20 @@ impl ExternFunction for ForEach {
 
 pub fn register_extern_functions(interpreter: &mut Interpreter) {
     interpreter.add_extern_function(ITERATOR_MODULE_NAME, "map", Box::new(Map {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "filter", Box::new(Filter {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "fold", Box::new(Fold {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "zip", Box::new(Zip {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "enumerate", Box::new(Enumerate {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "take", Box::new(Take {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "skip", Box::new(Skip {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "takeWhile", Box::new(TakeWhile {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "flatMap", Box::new(FlatMap {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "chain", Box::new(Chain {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "any", Box::new(Any {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "all", Box::new(All {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "count", Box::new(Count {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "sum", Box::new(Sum {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "range", Box::new(Range {}));
+    interpreter.add_extern_function(ITERATOR_MODULE_NAME, "repeat", Box::new(Repeat {}));
     interpreter.add_extern_function(ITERATOR_MODULE_NAME, "forEach", Box::new(ForEach {}));
 }
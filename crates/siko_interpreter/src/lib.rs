@@ -1,5 +1,6 @@
 pub mod char;
 pub mod environment;
+pub mod error;
 pub mod extern_function;
 pub mod float;
 pub mod int;
@@ -7,6 +8,11 @@ pub mod interpreter;
 pub mod iterator;
 pub mod list;
 pub mod map;
+pub mod math;
+pub mod set;
+pub mod std_actor;
+pub mod std_env;
+pub mod std_io;
 pub mod std_ops;
 pub mod std_util;
 pub mod std_util_basic;
@@ -99,9 +99,281 @@ impl ExternFunction for Get {
     }
 }
 
+pub struct Iter {}
+
+impl ExternFunction for Iter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0);
+        return Value::new(ValueCore::Iterator(Box::new(map)), ty);
+    }
+}
+
+pub struct Size {}
+
+impl ExternFunction for Size {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        return Value::new(ValueCore::Int(map.len() as i64), ty);
+    }
+}
+
+pub struct Contains {}
+
+impl ExternFunction for Contains {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let key = environment.get_arg_by_index(1);
+        return Interpreter::get_bool_value(map.contains_key(&key));
+    }
+}
+
+pub struct Keys {}
+
+impl ExternFunction for Keys {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let keys = map.into_iter().map(|(k, _)| k).collect();
+        return Value::new(ValueCore::List(keys), ty);
+    }
+}
+
+pub struct Values {}
+
+impl ExternFunction for Values {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        let values = map.into_iter().map(|(_, v)| v).collect();
+        return Value::new(ValueCore::List(values), ty);
+    }
+}
+
+pub struct Fold {}
+
+impl ExternFunction for Fold {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let init = environment.get_arg_by_index(1);
+        let map = environment.get_arg_by_index(2).core.as_map();
+        map.into_iter().fold(init, |acc, (k, v)| {
+            Interpreter::call_func(func.clone(), vec![acc, k, v], expr_id)
+        })
+    }
+}
+
+pub struct Union {}
+
+impl ExternFunction for Union {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut map = environment.get_arg_by_index(0).core.as_map();
+        let other = environment.get_arg_by_index(1).core.as_map();
+        for (k, v) in other {
+            map.entry(k).or_insert(v);
+        }
+        return Value::new(ValueCore::Map(map), ty);
+    }
+}
+
+pub struct Filter {}
+
+impl ExternFunction for Filter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let map = environment.get_arg_by_index(1).core.as_map();
+        let map = map
+            .into_iter()
+            .filter(|(k, v)| {
+                Interpreter::call_func(func.clone(), vec![k.clone(), v.clone()], expr_id)
+                    .core
+                    .as_bool()
+            })
+            .collect();
+        return Value::new(ValueCore::Map(map), ty);
+    }
+}
+
+pub struct FromList {}
+
+impl ExternFunction for FromList {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let items = environment.get_arg_by_index(0).core.as_list();
+        let mut map = BTreeMap::new();
+        for item in items {
+            if let ValueCore::Tuple(mut pair) = item.core {
+                let value = pair.remove(1);
+                let key = pair.remove(0);
+                map.insert(key, value);
+            }
+        }
+        return Value::new(ValueCore::Map(map), ty);
+    }
+}
+
+pub struct Alter {}
+
+impl ExternFunction for Alter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let key = environment.get_arg_by_index(1);
+        let mut map = environment.get_arg_by_index(2).core.as_map();
+        let mut map_type_args = ty.get_type_args();
+        let current = match map.remove(&key) {
+            Some(v) => create_some(v),
+            None => create_none(map_type_args.remove(1)),
+        };
+        let result = Interpreter::call_func(func, vec![current], expr_id);
+        let cache = Interpreter::get_typedef_id_cache();
+        let some_index = cache.option_variants.get_index("Some");
+        let none_index = cache.option_variants.get_index("None");
+        if let Some(v) = result.core.as_option(some_index, none_index) {
+            map.insert(key, v);
+        }
+        return Value::new(ValueCore::Map(map), ty);
+    }
+}
+
+pub struct Range {}
+
+impl ExternFunction for Range {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut first_arg = environment.get_arg_by_index(0);
+        let map = first_arg.core.as_map();
+        let start = environment.get_arg_by_index(1);
+        let end = environment.get_arg_by_index(2);
+        let map = if start < end {
+            map.range(start..end)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        first_arg.core = ValueCore::Map(map);
+        return Value::new(ValueCore::Iterator(Box::new(first_arg)), ty);
+    }
+}
+
+fn create_optional_entry(entry: Option<(&Value, &Value)>, ty: Type) -> Value {
+    let mut option_type_args = ty.get_type_args();
+    match entry {
+        Some((k, v)) => create_some(Value::new(
+            ValueCore::Tuple(vec![k.clone(), v.clone()]),
+            option_type_args.remove(0),
+        )),
+        None => create_none(option_type_args.remove(0)),
+    }
+}
+
+pub struct First {}
+
+impl ExternFunction for First {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        return create_optional_entry(map.iter().next(), ty);
+    }
+}
+
+pub struct Last {}
+
+impl ExternFunction for Last {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let map = environment.get_arg_by_index(0).core.as_map();
+        return create_optional_entry(map.iter().next_back(), ty);
+    }
+}
+
 pub fn register_extern_functions(interpreter: &mut Interpreter) {
     interpreter.add_extern_function(MAP_MODULE_NAME, "empty", Box::new(Empty {}));
     interpreter.add_extern_function(MAP_MODULE_NAME, "insert", Box::new(Insert {}));
     interpreter.add_extern_function(MAP_MODULE_NAME, "remove", Box::new(Remove {}));
     interpreter.add_extern_function(MAP_MODULE_NAME, "get", Box::new(Get {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "iter", Box::new(Iter {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "size", Box::new(Size {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "contains", Box::new(Contains {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "keys", Box::new(Keys {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "values", Box::new(Values {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "fold", Box::new(Fold {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "union", Box::new(Union {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "filter", Box::new(Filter {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "fromList", Box::new(FromList {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "alter", Box::new(Alter {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "range", Box::new(Range {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "first", Box::new(First {}));
+    interpreter.add_extern_function(MAP_MODULE_NAME, "last", Box::new(Last {}));
 }
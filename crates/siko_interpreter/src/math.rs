@@ -0,0 +1,303 @@
+use crate::environment::Environment;
+use crate::extern_function::ExternFunction;
+use crate::interpreter::Interpreter;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::MATH_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+
+pub struct MathPi {}
+
+impl ExternFunction for MathPi {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::consts::PI), ty);
+    }
+}
+
+pub struct MathE {}
+
+impl ExternFunction for MathE {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Float(std::f64::consts::E), ty);
+    }
+}
+
+pub struct MathSqrt {}
+
+impl ExternFunction for MathSqrt {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.sqrt()), ty);
+    }
+}
+
+pub struct MathPow {}
+
+impl ExternFunction for MathPow {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let base = environment.get_arg_by_index(0).core.as_float();
+        let exp = environment.get_arg_by_index(1).core.as_float();
+        return Value::new(ValueCore::Float(base.powf(exp)), ty);
+    }
+}
+
+pub struct MathExp {}
+
+impl ExternFunction for MathExp {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.exp()), ty);
+    }
+}
+
+pub struct MathLn {}
+
+impl ExternFunction for MathLn {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.ln()), ty);
+    }
+}
+
+pub struct MathLog2 {}
+
+impl ExternFunction for MathLog2 {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.log2()), ty);
+    }
+}
+
+pub struct MathLog10 {}
+
+impl ExternFunction for MathLog10 {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.log10()), ty);
+    }
+}
+
+pub struct MathLogBase {}
+
+impl ExternFunction for MathLogBase {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let base = environment.get_arg_by_index(0).core.as_float();
+        let value = environment.get_arg_by_index(1).core.as_float();
+        return Value::new(ValueCore::Float(value.log(base)), ty);
+    }
+}
+
+pub struct MathSin {}
+
+impl ExternFunction for MathSin {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.sin()), ty);
+    }
+}
+
+pub struct MathCos {}
+
+impl ExternFunction for MathCos {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.cos()), ty);
+    }
+}
+
+pub struct MathTan {}
+
+impl ExternFunction for MathTan {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.tan()), ty);
+    }
+}
+
+pub struct MathAsin {}
+
+impl ExternFunction for MathAsin {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.asin()), ty);
+    }
+}
+
+pub struct MathAcos {}
+
+impl ExternFunction for MathAcos {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.acos()), ty);
+    }
+}
+
+pub struct MathAtan {}
+
+impl ExternFunction for MathAtan {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_float();
+        return Value::new(ValueCore::Float(value.atan()), ty);
+    }
+}
+
+pub struct MathAtan2 {}
+
+impl ExternFunction for MathAtan2 {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let y = environment.get_arg_by_index(0).core.as_float();
+        let x = environment.get_arg_by_index(1).core.as_float();
+        return Value::new(ValueCore::Float(y.atan2(x)), ty);
+    }
+}
+
+pub struct MathMin {}
+
+impl ExternFunction for MathMin {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let a = environment.get_arg_by_index(0).core.as_float();
+        let b = environment.get_arg_by_index(1).core.as_float();
+        return Value::new(ValueCore::Float(a.min(b)), ty);
+    }
+}
+
+pub struct MathMax {}
+
+impl ExternFunction for MathMax {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let a = environment.get_arg_by_index(0).core.as_float();
+        let b = environment.get_arg_by_index(1).core.as_float();
+        return Value::new(ValueCore::Float(a.max(b)), ty);
+    }
+}
+
+pub fn register_extern_functions(interpreter: &mut Interpreter) {
+    interpreter.add_extern_function(MATH_MODULE_NAME, "pi", Box::new(MathPi {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "e", Box::new(MathE {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "sqrt", Box::new(MathSqrt {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "pow", Box::new(MathPow {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "exp", Box::new(MathExp {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "ln", Box::new(MathLn {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "log2", Box::new(MathLog2 {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "log10", Box::new(MathLog10 {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "logBase", Box::new(MathLogBase {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "sin", Box::new(MathSin {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "cos", Box::new(MathCos {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "tan", Box::new(MathTan {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "asin", Box::new(MathAsin {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "acos", Box::new(MathAcos {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "atan", Box::new(MathAtan {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "atan2", Box::new(MathAtan2 {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "min", Box::new(MathMin {}));
+    interpreter.add_extern_function(MATH_MODULE_NAME, "max", Box::new(MathMax {}));
+}
@@ -0,0 +1,295 @@
+use crate::environment::Environment;
+use crate::extern_function::ExternFunction;
+use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::SET_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+use std::collections::BTreeSet;
+
+pub struct Empty {}
+
+impl ExternFunction for Empty {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        return Value::new(ValueCore::Set(BTreeSet::new()), ty);
+    }
+}
+
+pub struct Insert {}
+
+impl ExternFunction for Insert {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut set = environment.get_arg_by_index(0).core.as_set();
+        let value = environment.get_arg_by_index(1);
+        set.insert(value);
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Remove {}
+
+impl ExternFunction for Remove {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut set = environment.get_arg_by_index(0).core.as_set();
+        let value = environment.get_arg_by_index(1);
+        set.remove(&value);
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Contains {}
+
+impl ExternFunction for Contains {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        let value = environment.get_arg_by_index(1);
+        return Interpreter::get_bool_value(set.contains(&value));
+    }
+}
+
+pub struct Size {}
+
+impl ExternFunction for Size {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        return Value::new(ValueCore::Int(set.len() as i64), ty);
+    }
+}
+
+pub struct Iter {}
+
+impl ExternFunction for Iter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0);
+        return Value::new(ValueCore::Iterator(Box::new(set)), ty);
+    }
+}
+
+pub struct FromList {}
+
+impl ExternFunction for FromList {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let items = environment.get_arg_by_index(0).core.as_list();
+        let set = items.into_iter().collect();
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Union {}
+
+impl ExternFunction for Union {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut set = environment.get_arg_by_index(0).core.as_set();
+        let other = environment.get_arg_by_index(1).core.as_set();
+        set.extend(other);
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Intersection {}
+
+impl ExternFunction for Intersection {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        let other = environment.get_arg_by_index(1).core.as_set();
+        let set = set.intersection(&other).cloned().collect();
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Difference {}
+
+impl ExternFunction for Difference {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        let other = environment.get_arg_by_index(1).core.as_set();
+        let set = set.difference(&other).cloned().collect();
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Filter {}
+
+impl ExternFunction for Filter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let set = environment.get_arg_by_index(1).core.as_set();
+        let set = set
+            .into_iter()
+            .filter(|v| {
+                Interpreter::call_func(func.clone(), vec![v.clone()], expr_id)
+                    .core
+                    .as_bool()
+            })
+            .collect();
+        return Value::new(ValueCore::Set(set), ty);
+    }
+}
+
+pub struct Fold {}
+
+impl ExternFunction for Fold {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        expr_id: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let func = environment.get_arg_by_index(0);
+        let init = environment.get_arg_by_index(1);
+        let set = environment.get_arg_by_index(2).core.as_set();
+        set.into_iter().fold(init, |acc, v| {
+            Interpreter::call_func(func.clone(), vec![acc, v], expr_id)
+        })
+    }
+}
+
+pub struct Range {}
+
+impl ExternFunction for Range {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut first_arg = environment.get_arg_by_index(0);
+        let set = first_arg.core.as_set();
+        let start = environment.get_arg_by_index(1);
+        let end = environment.get_arg_by_index(2);
+        let set = if start < end {
+            set.range(start..end).cloned().collect()
+        } else {
+            BTreeSet::new()
+        };
+        first_arg.core = ValueCore::Set(set);
+        return Value::new(ValueCore::Iterator(Box::new(first_arg)), ty);
+    }
+}
+
+fn create_optional_value(value: Option<&Value>, ty: Type) -> Value {
+    match value {
+        Some(v) => create_some(v.clone()),
+        None => create_none(ty.get_type_args().remove(0)),
+    }
+}
+
+pub struct First {}
+
+impl ExternFunction for First {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        return create_optional_value(set.iter().next(), ty);
+    }
+}
+
+pub struct Last {}
+
+impl ExternFunction for Last {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let set = environment.get_arg_by_index(0).core.as_set();
+        return create_optional_value(set.iter().next_back(), ty);
+    }
+}
+
+pub fn register_extern_functions(interpreter: &mut Interpreter) {
+    interpreter.add_extern_function(SET_MODULE_NAME, "empty", Box::new(Empty {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "insert", Box::new(Insert {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "remove", Box::new(Remove {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "contains", Box::new(Contains {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "size", Box::new(Size {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "iter", Box::new(Iter {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "fromList", Box::new(FromList {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "union", Box::new(Union {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "intersection", Box::new(Intersection {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "difference", Box::new(Difference {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "filter", Box::new(Filter {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "fold", Box::new(Fold {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "range", Box::new(Range {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "first", Box::new(First {}));
+    interpreter.add_extern_function(SET_MODULE_NAME, "last", Box::new(Last {}));
+}
@@ -0,0 +1,52 @@
+use crate::environment::Environment;
+use crate::extern_function::ExternFunction;
+use crate::interpreter::Interpreter;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::STD_ACTOR_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+
+pub struct Spawn {}
+
+impl ExternFunction for Spawn {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let actor = environment.get_arg_by_index(0);
+        let pid = Interpreter::spawn_actor(actor);
+        let id = Value::new(ValueCore::Int(pid as i64), Interpreter::get_int_type());
+        return Value::new(ValueCore::Record(ty.get_typedef_id(), vec![id]), ty);
+    }
+}
+
+pub struct Send {}
+
+impl ExternFunction for Send {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        current_expr: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let pid = environment.get_arg_by_index(0);
+        let message = environment.get_arg_by_index(1);
+        let id = match pid.core {
+            ValueCore::Record(_, fields) => fields[0].core.as_int(),
+            _ => unreachable!(),
+        };
+        Interpreter::send_message(id as usize, message, current_expr.expect("No current expr"));
+        return Value::new(ValueCore::Tuple(vec![]), ty);
+    }
+}
+
+pub fn register_extern_functions(interpreter: &mut Interpreter) {
+    interpreter.add_extern_function(STD_ACTOR_MODULE_NAME, "spawn", Box::new(Spawn {}));
+    interpreter.add_extern_function(STD_ACTOR_MODULE_NAME, "send", Box::new(Send {}));
+}
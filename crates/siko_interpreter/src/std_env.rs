@@ -0,0 +1,73 @@
+use crate::environment::Environment;
+use crate::extern_function::ExternFunction;
+use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::STD_ENV_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+use std::env;
+use std::io::Write;
+
+pub struct Args {}
+
+impl ExternFunction for Args {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let string_ty = Interpreter::get_string_type();
+        let args = Interpreter::get_program_args()
+            .into_iter()
+            .map(|arg| Value::new(ValueCore::String(arg), string_ty.clone()))
+            .collect();
+        return Value::new(ValueCore::List(args), ty);
+    }
+}
+
+pub struct GetEnv {}
+
+impl ExternFunction for GetEnv {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let name = environment.get_arg_by_index(0).core.as_string();
+        let string_ty = Interpreter::get_string_type();
+        match env::var(name) {
+            Ok(value) => create_some(Value::new(ValueCore::String(value), string_ty)),
+            Err(_) => create_none(string_ty),
+        }
+    }
+}
+
+pub struct Exit {}
+
+impl ExternFunction for Exit {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let code = environment.get_arg_by_index(0).core.as_int();
+        let _ = std::io::stdout().flush();
+        std::process::exit(code as i32);
+    }
+}
+
+pub fn register_extern_functions(interpreter: &mut Interpreter) {
+    interpreter.add_extern_function(STD_ENV_MODULE_NAME, "args", Box::new(Args {}));
+    interpreter.add_extern_function(STD_ENV_MODULE_NAME, "getEnv", Box::new(GetEnv {}));
+    interpreter.add_extern_function(STD_ENV_MODULE_NAME, "exit", Box::new(Exit {}));
+}
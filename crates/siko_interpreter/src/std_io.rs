@@ -0,0 +1,155 @@
+use crate::environment::Environment;
+use crate::extern_function::ExternFunction;
+use crate::interpreter::Interpreter;
+use crate::util::create_err;
+use crate::util::create_none;
+use crate::util::create_ok;
+use crate::util::create_some;
+use crate::value::Value;
+use crate::value::ValueCore;
+use siko_constants::STD_IO_MODULE_NAME;
+use siko_ir::expr::ExprId;
+use siko_ir::function::NamedFunctionKind;
+use siko_ir::types::Type;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+
+fn create_io_error(err: io::Error, result_ty: Type) -> Value {
+    let cache = Interpreter::get_typedef_id_cache();
+    let variant = match err.kind() {
+        io::ErrorKind::NotFound => "NotFound",
+        io::ErrorKind::PermissionDenied => "PermissionDenied",
+        io::ErrorKind::AlreadyExists => "AlreadyExists",
+        io::ErrorKind::InvalidData => "InvalidData",
+        _ => "Other",
+    };
+    let message = Value::new(
+        ValueCore::String(err.to_string()),
+        Interpreter::get_string_type(),
+    );
+    let error_ty = result_ty.get_type_args().remove(1);
+    let error = Value::new(
+        ValueCore::Variant(
+            cache.io_error_id,
+            cache.io_error_variants.get_index(variant),
+            vec![message],
+        ),
+        error_ty,
+    );
+    create_err(error, result_ty)
+}
+
+pub struct ReadLine {}
+
+impl ExternFunction for ReadLine {
+    fn call(
+        &self,
+        _: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let mut line = String::new();
+        let stdin = io::stdin();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => {
+                let string_ty = Interpreter::get_string_type();
+                create_ok(create_none(string_ty), ty)
+            }
+            Ok(_) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                let line = Value::new(ValueCore::String(line), Interpreter::get_string_type());
+                create_ok(create_some(line), ty)
+            }
+            Err(err) => create_io_error(err, ty),
+        }
+    }
+}
+
+pub struct ReadFile {}
+
+impl ExternFunction for ReadFile {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let content =
+                    Value::new(ValueCore::String(content), Interpreter::get_string_type());
+                create_ok(content, ty)
+            }
+            Err(err) => create_io_error(err, ty),
+        }
+    }
+}
+
+pub struct WriteFile {}
+
+impl ExternFunction for WriteFile {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        let content = environment.get_arg_by_index(1).core.as_string();
+        match fs::write(path, content) {
+            Ok(()) => {
+                let unit_ty = ty.get_type_args().remove(0);
+                create_ok(Value::new(ValueCore::Tuple(vec![]), unit_ty), ty)
+            }
+            Err(err) => create_io_error(err, ty),
+        }
+    }
+}
+
+pub struct ListDir {}
+
+impl ExternFunction for ListDir {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let path = environment.get_arg_by_index(0).core.as_string();
+        match fs::read_dir(path) {
+            Ok(entries) => {
+                let mut names: Vec<_> = entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .collect();
+                names.sort();
+                let string_ty = Interpreter::get_string_type();
+                let names = names
+                    .into_iter()
+                    .map(|name| Value::new(ValueCore::String(name), string_ty.clone()))
+                    .collect();
+                let list_ty = ty.get_type_args().remove(0);
+                create_ok(Value::new(ValueCore::List(names), list_ty), ty)
+            }
+            Err(err) => create_io_error(err, ty),
+        }
+    }
+}
+
+pub fn register_extern_functions(interpreter: &mut Interpreter) {
+    interpreter.add_extern_function(STD_IO_MODULE_NAME, "readLine", Box::new(ReadLine {}));
+    interpreter.add_extern_function(STD_IO_MODULE_NAME, "readFile", Box::new(ReadFile {}));
+    interpreter.add_extern_function(STD_IO_MODULE_NAME, "writeFile", Box::new(WriteFile {}));
+    interpreter.add_extern_function(STD_IO_MODULE_NAME, "listDir", Box::new(ListDir {}));
+}
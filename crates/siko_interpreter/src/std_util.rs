@@ -19,7 +19,7 @@ impl ExternFunction for Assert {
     ) -> Value {
         let v = environment.get_arg_by_index(0).core.as_bool();
         if !v {
-            Interpreter::call_abort(current_expr.expect("No current expr"));
+            Interpreter::call_abort("Assertion failed", current_expr.expect("No current expr"));
         }
         return Value::new(ValueCore::Tuple(vec![]), ty);
     }
@@ -1,6 +1,8 @@
 use crate::environment::Environment;
 use crate::extern_function::ExternFunction;
 use crate::interpreter::Interpreter;
+use crate::util::create_none;
+use crate::util::create_some;
 use crate::util::get_opt_ordering_value;
 use crate::util::get_ordering_value;
 use crate::value::Value;
@@ -110,6 +112,338 @@ impl ExternFunction for StringChars {
     }
 }
 
+fn create_string_list<'a>(items: impl Iterator<Item = &'a str>, ty: Type) -> Value {
+    let string_ty = ty.get_type_args().remove(0);
+    let items = items
+        .map(|item| Value::new(ValueCore::String(item.to_string()), string_ty.clone()))
+        .collect();
+    Value::new(ValueCore::List(items), ty)
+}
+
+fn create_optional(value: Option<Value>, ty: Type) -> Value {
+    match value {
+        Some(v) => create_some(v),
+        None => create_none(ty.get_type_args().remove(0)),
+    }
+}
+
+// Char indices are used everywhere so that the results do not depend on the utf-8 encoding.
+fn get_char_index(value: &Value) -> usize {
+    let index = value.core.as_int();
+    if index < 0 {
+        0
+    } else {
+        index as usize
+    }
+}
+
+pub struct StringIter {}
+
+impl ExternFunction for StringIter {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0);
+        return Value::new(ValueCore::Iterator(Box::new(value)), ty);
+    }
+}
+
+pub struct StringFromChars {}
+
+impl ExternFunction for StringFromChars {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let chars = environment.get_arg_by_index(0).core.as_list();
+        let value: String = chars.iter().map(|c| c.core.as_char()).collect();
+        return Value::new(ValueCore::String(value), ty);
+    }
+}
+
+pub struct StringGetLength {}
+
+impl ExternFunction for StringGetLength {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        return Value::new(ValueCore::Int(value.chars().count() as i64), ty);
+    }
+}
+
+pub struct StringSplit {}
+
+impl ExternFunction for StringSplit {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let sep = environment.get_arg_by_index(0).core.as_string();
+        let value = environment.get_arg_by_index(1).core.as_string();
+        return create_string_list(value.split(sep.as_str()), ty);
+    }
+}
+
+pub struct StringLines {}
+
+impl ExternFunction for StringLines {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        return create_string_list(value.lines(), ty);
+    }
+}
+
+pub struct StringWords {}
+
+impl ExternFunction for StringWords {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        return create_string_list(value.split_whitespace(), ty);
+    }
+}
+
+pub struct StringJoin {}
+
+impl ExternFunction for StringJoin {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let sep = environment.get_arg_by_index(0).core.as_string();
+        let parts: Vec<_> = environment
+            .get_arg_by_index(1)
+            .core
+            .as_list()
+            .iter()
+            .map(|part| part.core.as_string())
+            .collect();
+        return Value::new(ValueCore::String(parts.join(sep.as_str())), ty);
+    }
+}
+
+pub struct StringTrim {}
+
+impl ExternFunction for StringTrim {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        return Value::new(ValueCore::String(value.trim().to_string()), ty);
+    }
+}
+
+pub struct StringReplace {}
+
+impl ExternFunction for StringReplace {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let from = environment.get_arg_by_index(0).core.as_string();
+        let to = environment.get_arg_by_index(1).core.as_string();
+        let value = environment.get_arg_by_index(2).core.as_string();
+        return Value::new(
+            ValueCore::String(value.replace(from.as_str(), to.as_str())),
+            ty,
+        );
+    }
+}
+
+pub struct StringContains {}
+
+impl ExternFunction for StringContains {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let pattern = environment.get_arg_by_index(0).core.as_string();
+        let value = environment.get_arg_by_index(1).core.as_string();
+        return Interpreter::get_bool_value(value.contains(pattern.as_str()));
+    }
+}
+
+pub struct StringStartsWith {}
+
+impl ExternFunction for StringStartsWith {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let prefix = environment.get_arg_by_index(0).core.as_string();
+        let value = environment.get_arg_by_index(1).core.as_string();
+        return Interpreter::get_bool_value(value.starts_with(prefix.as_str()));
+    }
+}
+
+pub struct StringEndsWith {}
+
+impl ExternFunction for StringEndsWith {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        _: Type,
+    ) -> Value {
+        let suffix = environment.get_arg_by_index(0).core.as_string();
+        let value = environment.get_arg_by_index(1).core.as_string();
+        return Interpreter::get_bool_value(value.ends_with(suffix.as_str()));
+    }
+}
+
+pub struct StringIndexOf {}
+
+impl ExternFunction for StringIndexOf {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let pattern = environment.get_arg_by_index(0).core.as_string();
+        let value = environment.get_arg_by_index(1).core.as_string();
+        let index = value.find(pattern.as_str()).map(|index| {
+            let index = value[..index].chars().count() as i64;
+            Value::new(ValueCore::Int(index), Interpreter::get_int_type())
+        });
+        return create_optional(index, ty);
+    }
+}
+
+pub struct StringSubstring {}
+
+impl ExternFunction for StringSubstring {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let start = get_char_index(&environment.get_arg_by_index(0));
+        let end = get_char_index(&environment.get_arg_by_index(1));
+        let value = environment.get_arg_by_index(2).core.as_string();
+        let value = value
+            .chars()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect();
+        return Value::new(ValueCore::String(value), ty);
+    }
+}
+
+pub struct StringToUpper {}
+
+impl ExternFunction for StringToUpper {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        return Value::new(ValueCore::String(value.to_uppercase()), ty);
+    }
+}
+
+pub struct StringToLower {}
+
+impl ExternFunction for StringToLower {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        return Value::new(ValueCore::String(value.to_lowercase()), ty);
+    }
+}
+
+pub struct StringParseInt {}
+
+impl ExternFunction for StringParseInt {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        let value = value
+            .parse::<i64>()
+            .ok()
+            .map(|v| Value::new(ValueCore::Int(v), Interpreter::get_int_type()));
+        return create_optional(value, ty);
+    }
+}
+
+pub struct StringParseFloat {}
+
+impl ExternFunction for StringParseFloat {
+    fn call(
+        &self,
+        environment: &mut Environment,
+        _: Option<ExprId>,
+        _: &NamedFunctionKind,
+        ty: Type,
+    ) -> Value {
+        let value = environment.get_arg_by_index(0).core.as_string();
+        let value = value
+            .parse::<f64>()
+            .ok()
+            .map(|v| Value::new(ValueCore::Float(v), Interpreter::get_float_type()));
+        return create_optional(value, ty);
+    }
+}
+
 pub fn register_extern_functions(interpreter: &mut Interpreter) {
     interpreter.add_extern_function(STRING_MODULE_NAME, "opAdd", Box::new(StringAdd {}));
     interpreter.add_extern_function(STRING_MODULE_NAME, "opEq", Box::new(StringPartialEq {}));
@@ -121,4 +455,42 @@ pub fn register_extern_functions(interpreter: &mut Interpreter) {
     interpreter.add_extern_function(STRING_MODULE_NAME, "cmp", Box::new(StringOrd {}));
     interpreter.add_extern_function(STRING_MODULE_NAME, "show", Box::new(StringShow {}));
     interpreter.add_extern_function(STRING_MODULE_NAME, "chars", Box::new(StringChars {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "iter", Box::new(StringIter {}));
+    interpreter.add_extern_function(
+        STRING_MODULE_NAME,
+        "fromChars",
+        Box::new(StringFromChars {}),
+    );
+    interpreter.add_extern_function(
+        STRING_MODULE_NAME,
+        "getLength",
+        Box::new(StringGetLength {}),
+    );
+    interpreter.add_extern_function(STRING_MODULE_NAME, "split", Box::new(StringSplit {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "lines", Box::new(StringLines {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "words", Box::new(StringWords {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "join", Box::new(StringJoin {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "trim", Box::new(StringTrim {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "replace", Box::new(StringReplace {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "contains", Box::new(StringContains {}));
+    interpreter.add_extern_function(
+        STRING_MODULE_NAME,
+        "startsWith",
+        Box::new(StringStartsWith {}),
+    );
+    interpreter.add_extern_function(STRING_MODULE_NAME, "endsWith", Box::new(StringEndsWith {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "indexOf", Box::new(StringIndexOf {}));
+    interpreter.add_extern_function(
+        STRING_MODULE_NAME,
+        "substring",
+        Box::new(StringSubstring {}),
+    );
+    interpreter.add_extern_function(STRING_MODULE_NAME, "toUpper", Box::new(StringToUpper {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "toLower", Box::new(StringToLower {}));
+    interpreter.add_extern_function(STRING_MODULE_NAME, "parseInt", Box::new(StringParseInt {}));
+    interpreter.add_extern_function(
+        STRING_MODULE_NAME,
+        "parseFloat",
+        Box::new(StringParseFloat {}),
+    );
 }
@@ -60,6 +60,26 @@ pub fn create_none(value_ty: Type) -> Value {
     none_value
 }
 
+pub fn create_ok(value: Value, result_ty: Type) -> Value {
+    let cache = Interpreter::get_typedef_id_cache();
+    let core = ValueCore::Variant(
+        cache.result_id,
+        cache.result_variants.get_index("Ok"),
+        vec![value],
+    );
+    Value::new(core, result_ty)
+}
+
+pub fn create_err(value: Value, result_ty: Type) -> Value {
+    let cache = Interpreter::get_typedef_id_cache();
+    let core = ValueCore::Variant(
+        cache.result_id,
+        cache.result_variants.get_index("Err"),
+        vec![value],
+    );
+    Value::new(core, result_ty)
+}
+
 pub fn create_ordering(index: usize) -> Value {
     let cache = Interpreter::get_typedef_id_cache();
     let concrete_type = Type::Named(ORDERING_TYPE_NAME.to_string(), cache.ordering_id, vec![]);
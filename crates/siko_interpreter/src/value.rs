@@ -6,6 +6,7 @@ use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy)]
@@ -85,8 +86,19 @@ pub enum ValueCore {
     Record(TypeDefId, Vec<Value>),
     List(Vec<Value>),
     Map(BTreeMap<Value, Value>),
+    Set(BTreeSet<Value>),
     Iterator(Box<Value>),
     IteratorMap(Box<Value>, Box<Value>),
+    IteratorFilter(Box<Value>, Box<Value>),
+    IteratorZip(Box<Value>, Box<Value>, Type),
+    IteratorEnumerate(Box<Value>, Type),
+    IteratorTake(Box<Value>, usize),
+    IteratorSkip(Box<Value>, usize),
+    IteratorTakeWhile(Box<Value>, Box<Value>),
+    IteratorFlatMap(Box<Value>, Box<Value>),
+    IteratorChain(Box<Value>, Box<Value>),
+    IteratorRange(i64, i64),
+    IteratorRepeat(Box<Value>),
 }
 
 impl ValueCore {
@@ -162,6 +174,13 @@ impl ValueCore {
         }
     }
 
+    pub fn as_set(&self) -> BTreeSet<Value> {
+        match self {
+            ValueCore::Set(s) => s.clone(),
+            _ => unreachable!(),
+        }
+    }
+
     pub fn as_list(&self) -> Vec<Value> {
         match self {
             ValueCore::List(l) => l.clone(),
@@ -207,7 +226,24 @@ impl ValueCore {
         match self {
             ValueCore::Iterator(v) => match v.core.clone() {
                 ValueCore::List(v) => Box::new(v.into_iter()),
-                //ValueCore::Map(v) => Box::new(v.into_iter()),
+                ValueCore::Map(m) => {
+                    let tuple_ty = Type::Tuple(v.ty.get_type_args());
+                    let iterator = m.into_iter().map(move |(key, value)| {
+                        Value::new(ValueCore::Tuple(vec![key, value]), tuple_ty.clone())
+                    });
+                    Box::new(iterator)
+                }
+                ValueCore::Set(s) => Box::new(s.into_iter()),
+                ValueCore::String(s) => {
+                    let char_ty = Interpreter::get_char_type();
+                    let mut offset = 0;
+                    let iterator = std::iter::from_fn(move || {
+                        let c = s[offset..].chars().next()?;
+                        offset += c.len_utf8();
+                        Some(Value::new(ValueCore::Char(c), char_ty.clone()))
+                    });
+                    Box::new(iterator)
+                }
                 _ => unreachable!(),
             },
             ValueCore::IteratorMap(v, func) => {
@@ -217,6 +253,65 @@ impl ValueCore {
                     iterator.map(move |x| Interpreter::call_func(func.clone(), vec![x], None));
                 Box::new(iterator)
             }
+            ValueCore::IteratorFilter(v, func) => {
+                let func = *func.clone();
+                let iterator = v.core.as_iterator();
+                let iterator = iterator.filter(move |x| {
+                    Interpreter::call_func(func.clone(), vec![x.clone()], None)
+                        .core
+                        .as_bool()
+                });
+                Box::new(iterator)
+            }
+            ValueCore::IteratorZip(v1, v2, ty) => {
+                let ty = ty.clone();
+                let iterator = v1.core.as_iterator().zip(v2.core.as_iterator());
+                let iterator = iterator
+                    .map(move |(a, b)| Value::new(ValueCore::Tuple(vec![a, b]), ty.clone()));
+                Box::new(iterator)
+            }
+            ValueCore::IteratorEnumerate(v, ty) => {
+                let ty = ty.clone();
+                let int_ty = Interpreter::get_int_type();
+                let iterator = v.core.as_iterator().enumerate();
+                let iterator = iterator.map(move |(index, x)| {
+                    let index = Value::new(ValueCore::Int(index as i64), int_ty.clone());
+                    Value::new(ValueCore::Tuple(vec![index, x]), ty.clone())
+                });
+                Box::new(iterator)
+            }
+            ValueCore::IteratorTake(v, count) => Box::new(v.core.as_iterator().take(*count)),
+            ValueCore::IteratorSkip(v, count) => Box::new(v.core.as_iterator().skip(*count)),
+            ValueCore::IteratorTakeWhile(v, func) => {
+                let func = *func.clone();
+                let iterator = v.core.as_iterator();
+                let iterator = iterator.take_while(move |x| {
+                    Interpreter::call_func(func.clone(), vec![x.clone()], None)
+                        .core
+                        .as_bool()
+                });
+                Box::new(iterator)
+            }
+            ValueCore::IteratorFlatMap(v, func) => {
+                let func = *func.clone();
+                let iterator = v.core.as_iterator();
+                let iterator = iterator.flat_map(move |x| {
+                    Interpreter::call_func(func.clone(), vec![x], None)
+                        .core
+                        .as_iterator()
+                });
+                Box::new(iterator)
+            }
+            ValueCore::IteratorChain(v1, v2) => {
+                Box::new(v1.core.as_iterator().chain(v2.core.as_iterator()))
+            }
+            ValueCore::IteratorRange(start, end) => {
+                let int_ty = Interpreter::get_int_type();
+                let iterator =
+                    (*start..*end).map(move |i| Value::new(ValueCore::Int(i), int_ty.clone()));
+                Box::new(iterator)
+            }
+            ValueCore::IteratorRepeat(v) => Box::new(std::iter::repeat(*v.clone())),
             _ => unreachable!(),
         }
     }
@@ -253,8 +348,30 @@ impl fmt::Display for ValueCore {
                     .collect();
                 write!(f, "{{{}}}", ss.join(", "))
             }
+            ValueCore::Set(vs) => {
+                let ss: Vec<_> = vs.iter().map(|v| format!("{}", v.core)).collect();
+                write!(f, "{{{}}}", ss.join(", "))
+            }
             ValueCore::Iterator(v) => write!(f, "Iterator({})", v.core),
             ValueCore::IteratorMap(v, func) => write!(f, "IteratorMap({}, {})", v.core, func.core),
+            ValueCore::IteratorFilter(v, func) => {
+                write!(f, "IteratorFilter({}, {})", v.core, func.core)
+            }
+            ValueCore::IteratorZip(v1, v2, _) => write!(f, "IteratorZip({}, {})", v1.core, v2.core),
+            ValueCore::IteratorEnumerate(v, _) => write!(f, "IteratorEnumerate({})", v.core),
+            ValueCore::IteratorTake(v, count) => write!(f, "IteratorTake({}, {})", v.core, count),
+            ValueCore::IteratorSkip(v, count) => write!(f, "IteratorSkip({}, {})", v.core, count),
+            ValueCore::IteratorTakeWhile(v, func) => {
+                write!(f, "IteratorTakeWhile({}, {})", v.core, func.core)
+            }
+            ValueCore::IteratorFlatMap(v, func) => {
+                write!(f, "IteratorFlatMap({}, {})", v.core, func.core)
+            }
+            ValueCore::IteratorChain(v1, v2) => {
+                write!(f, "IteratorChain({}, {})", v1.core, v2.core)
+            }
+            ValueCore::IteratorRange(start, end) => write!(f, "IteratorRange({}, {})", start, end),
+            ValueCore::IteratorRepeat(v) => write!(f, "IteratorRepeat({})", v.core),
         }
     }
 }
@@ -0,0 +1,59 @@
+use crate::data::TypeDefId;
+use crate::function::FunctionId;
+use siko_location_info::location_id::LocationId;
+
+#[derive(Debug, Clone)]
+pub struct Protocol {
+    pub id: ProtocolId,
+    pub name: String,
+    pub module: String,
+    pub typedef_id: TypeDefId,
+    pub location_id: LocationId,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ProtocolId {
+    pub id: usize,
+}
+
+impl From<usize> for ProtocolId {
+    fn from(id: usize) -> ProtocolId {
+        ProtocolId { id: id }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtocolHandler {
+    pub protocol_id: ProtocolId,
+    pub function_id: FunctionId,
+    pub location_id: LocationId,
+}
+
+#[derive(Debug, Clone)]
+pub struct Actor {
+    pub id: ActorId,
+    pub name: String,
+    pub module: String,
+    pub typedef_id: TypeDefId,
+    pub handlers: Vec<ProtocolHandler>,
+    pub location_id: LocationId,
+}
+
+impl Actor {
+    pub fn get_handler(&self, protocol_id: ProtocolId) -> Option<&ProtocolHandler> {
+        self.handlers
+            .iter()
+            .find(|handler| handler.protocol_id == protocol_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ActorId {
+    pub id: usize,
+}
+
+impl From<usize> for ActorId {
+    fn from(id: usize) -> ActorId {
+        ActorId { id: id }
+    }
+}
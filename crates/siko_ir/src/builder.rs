@@ -707,6 +707,7 @@ impl<'a> Builder<'a> {
             kind: NamedFunctionKind::ExternClassImpl(class_member_name, ty.clone()),
             location_id: location,
             type_signature: None,
+            multi_param_constraints: Vec::new(),
             module: module.clone(),
             name: format!("{:?}", name),
         };
@@ -739,6 +740,7 @@ impl<'a> Builder<'a> {
             kind: NamedFunctionKind::ExternClassImpl(EQ_CLASS_NAME.to_string(), ty.clone()),
             location_id: location,
             type_signature: None,
+            multi_param_constraints: Vec::new(),
             module: module.clone(),
             name: format!("{:?}", name),
         };
@@ -10,12 +10,35 @@ pub struct Class {
     pub name: String,
     pub module: String,
     pub type_signature: Option<TypeSignatureId>,
+    pub arg_arities: Vec<usize>,
+    pub functional_dependencies: Vec<FunctionalDependency>,
     pub constraints: Vec<ClassId>,
     pub members: BTreeMap<String, ClassMemberId>,
     pub location_id: LocationId,
     pub auto_derivable: bool,
 }
 
+impl Class {
+    pub fn is_multi_param(&self) -> bool {
+        self.arg_arities.len() > 1
+    }
+}
+
+// the args listed in 'to' are uniquely determined by the args listed in 'from'
+#[derive(Debug, Clone)]
+pub struct FunctionalDependency {
+    pub from: Vec<usize>,
+    pub to: Vec<usize>,
+}
+
+// constraint of a multi parameter class, its type signature is a tuple of the class args
+#[derive(Debug, Clone)]
+pub struct MultiParamConstraint {
+    pub class_id: ClassId,
+    pub type_signature: TypeSignatureId,
+    pub location_id: LocationId,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 pub struct ClassId {
     pub id: usize,
@@ -84,6 +107,7 @@ pub struct Instance {
     pub name: Option<String>,
     pub class_id: ClassId,
     pub type_signature: TypeSignatureId,
+    pub multi_param_constraints: Vec<MultiParamConstraint>,
     pub members: BTreeMap<String, InstanceMember>,
     pub location_id: LocationId,
 }
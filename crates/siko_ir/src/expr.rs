@@ -6,7 +6,7 @@ use crate::pattern::PatternId;
 use siko_util::format_list;
 use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FunctionArgumentRef {
     pub captured: bool,
     pub id: FunctionId,
@@ -94,6 +94,20 @@ impl fmt::Display for RecordUpdateInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct HoleInfo {
+    pub name: String,
+    pub bindings: Vec<(String, ExprId)>,
+    pub candidates: Vec<(String, FunctionId)>,
+}
+
+impl fmt::Display for HoleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bindings: Vec<_> = self.bindings.iter().map(|(_, id)| id).collect();
+        write!(f, "?{}, {}", self.name, format_list(&bindings[..]))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Expr {
     StaticFunctionCall(FunctionId, Vec<ExprId>),
@@ -120,6 +134,7 @@ pub enum Expr {
     Loop(PatternId, ExprId, Vec<ExprId>, Vec<ExprId>, Vec<ExprId>),
     Continue(ExprId),
     Break(ExprId),
+    Hole(HoleInfo),
 }
 
 impl fmt::Display for Expr {
@@ -173,6 +188,7 @@ impl fmt::Display for Expr {
             }
             Expr::Continue(expr) => write!(f, "Continue({})", expr),
             Expr::Break(expr) => write!(f, "Break({})", expr),
+            Expr::Hole(info) => write!(f, "Hole({})", info),
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::class::ClassMemberId;
+use crate::class::MultiParamConstraint;
 use crate::data::TypeDefId;
 use crate::expr::ExprId;
 use crate::type_signature::TypeSignatureId;
@@ -37,6 +38,7 @@ pub struct NamedFunctionInfo {
     pub module: String,
     pub name: String,
     pub type_signature: Option<TypeSignatureId>,
+    pub multi_param_constraints: Vec<MultiParamConstraint>,
     pub location_id: LocationId,
     pub kind: NamedFunctionKind,
 }
@@ -63,6 +65,21 @@ impl fmt::Display for LambdaInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct LocalFunctionInfo {
+    pub body: ExprId,
+    pub module: String,
+    pub name: String,
+    pub host_info: String,
+    pub location_id: LocationId,
+}
+
+impl fmt::Display for LocalFunctionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.host_info, self.name)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RecordConstructorInfo {
     pub type_id: TypeDefId,
@@ -89,6 +106,7 @@ impl fmt::Display for VariantConstructorInfo {
 #[derive(Debug, Clone)]
 pub enum FunctionInfo {
     Lambda(LambdaInfo),
+    LocalFunction(LocalFunctionInfo),
     NamedFunction(NamedFunctionInfo),
     RecordConstructor(RecordConstructorInfo),
     VariantConstructor(VariantConstructorInfo),
@@ -98,6 +116,7 @@ impl fmt::Display for FunctionInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             FunctionInfo::Lambda(i) => write!(f, "lambda{}", i),
+            FunctionInfo::LocalFunction(i) => write!(f, "{}", i),
             FunctionInfo::NamedFunction(i) => write!(f, "{}", i),
             FunctionInfo::RecordConstructor(i) => write!(f, "{}", i),
             FunctionInfo::VariantConstructor(i) => write!(f, "{}", i),
@@ -124,6 +143,7 @@ impl Function {
     pub fn get_body(&self) -> Option<ExprId> {
         match &self.info {
             FunctionInfo::Lambda(i) => Some(i.body),
+            FunctionInfo::LocalFunction(i) => Some(i.body),
             FunctionInfo::NamedFunction(i) => i.body.clone(),
             FunctionInfo::RecordConstructor(_) => None,
             FunctionInfo::VariantConstructor(_) => None,
@@ -133,6 +153,7 @@ impl Function {
     pub fn is_typed(&self) -> bool {
         match &self.info {
             FunctionInfo::Lambda(_) => false,
+            FunctionInfo::LocalFunction(_) => false,
             FunctionInfo::NamedFunction(i) => i.type_signature.is_some(),
             FunctionInfo::RecordConstructor(_) => true,
             FunctionInfo::VariantConstructor(_) => true,
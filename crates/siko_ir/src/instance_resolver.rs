@@ -1,5 +1,6 @@
 use crate::class::ClassId;
 use crate::class::InstanceId;
+use crate::substitution::Constraint;
 use crate::type_var_generator::TypeVarGenerator;
 use crate::types::BaseType;
 use crate::types::Type;
@@ -50,6 +51,7 @@ impl InstanceInfo {
 pub struct InstanceResolver {
     pub instance_map: BTreeMap<ClassId, BTreeMap<BaseType, Vec<InstanceInfo>>>,
     pub auto_derived_instances: Vec<AutoDerivedInstance>,
+    pub multi_param_constraints: BTreeMap<InstanceId, Vec<Constraint>>,
     cache: Rc<RefCell<BTreeMap<(ClassId, Type), ResolutionResult>>>,
     pub type_var_generator: TypeVarGenerator,
 }
@@ -59,6 +61,7 @@ impl InstanceResolver {
         InstanceResolver {
             instance_map: BTreeMap::new(),
             auto_derived_instances: Vec::new(),
+            multi_param_constraints: BTreeMap::new(),
             cache: Rc::new(RefCell::new(BTreeMap::new())),
             type_var_generator: type_var_generator,
         }
@@ -77,8 +80,11 @@ impl InstanceResolver {
         class_id: ClassId,
         instance_ty: Type,
         instance_id: InstanceId,
+        multi_param_constraints: Vec<Constraint>,
         location_id: LocationId,
     ) {
+        self.multi_param_constraints
+            .insert(instance_id, multi_param_constraints);
         let class_instances = self
             .instance_map
             .entry(class_id)
@@ -122,10 +128,13 @@ impl InstanceResolver {
             if let Some(instances) = class_instances.get(&base_type) {
                 for instance in instances {
                     let mut unifier = Unifier::new(self.type_var_generator.clone());
+                    // the type vars of the instance must not leak into the checked type
+                    let instance_ty = instance
+                        .get_type(self)
+                        .duplicate(&mut BTreeMap::new(), &mut self.type_var_generator.clone());
                     match instance {
-                        InstanceInfo::AutoDerived(index) => {
-                            let instance = self.get_auto_derived_instance(*index);
-                            if unifier.unify(ty, &instance.ty).is_ok() {
+                        InstanceInfo::AutoDerived(_) => {
+                            if unifier.unify(ty, &instance_ty).is_ok() {
                                 if ty.is_concrete_type() {
                                     let result = ResolutionResult::AutoDerived;
                                     let mut cache = self.cache.borrow_mut();
@@ -134,8 +143,8 @@ impl InstanceResolver {
                                 return Some(unifier);
                             }
                         }
-                        InstanceInfo::UserDefined(instance_ty, instance_id, _) => {
-                            if unifier.unify(ty, instance_ty).is_ok() {
+                        InstanceInfo::UserDefined(_, instance_id, _) => {
+                            if unifier.unify(ty, &instance_ty).is_ok() {
                                 if ty.is_concrete_type() {
                                     let result = ResolutionResult::UserDefined(*instance_id);
                                     let mut cache = self.cache.borrow_mut();
@@ -155,6 +164,12 @@ impl InstanceResolver {
         }
     }
 
+    pub fn get_multi_param_constraints(&self, instance_id: &InstanceId) -> &Vec<Constraint> {
+        self.multi_param_constraints
+            .get(instance_id)
+            .expect("Instance constraints not found")
+    }
+
     pub fn check_instance(
         &mut self,
         class_id: ClassId,
@@ -177,20 +192,44 @@ impl InstanceResolver {
                 return true;
             }
         }
+        if let Type::Never(_) = ty {
+            return true;
+        }
         if let Some(unifier) = self.has_instance(&ty, class_id) {
+            let first = unifiers.len();
+            // type vars of the checked type may have picked up constraints of the instance
+            let mut vars = Vec::new();
+            ty.collect_type_vars(&mut vars);
+            let mut var_unifier = Unifier::new(self.type_var_generator.clone());
+            let mut vars_changed = false;
+            for var in vars {
+                let resolved = unifier.apply(&var);
+                let renamed = match (&var, &resolved) {
+                    (Type::Var(_, constraints1), Type::Var(_, constraints2)) => {
+                        constraints2.iter().all(|c| constraints1.contains(c))
+                    }
+                    _ => false,
+                };
+                if !renamed {
+                    let r = var_unifier.unify(&var, &resolved);
+                    assert!(r.is_ok());
+                    vars_changed = true;
+                }
+            }
+            if vars_changed {
+                unifiers.push((var_unifier, location_id));
+            }
+            // the remaining constraints of the instance have to be satisfied as well,
+            // generic ones are propagated to the context of the checked type
             let constraints = unifier.get_substitution().get_constraints();
             for constraint in constraints {
-                if constraint.ty.get_base_type() == BaseType::Generic {
-                    unimplemented!();
-                } else {
-                    if !self.check_instance(
-                        constraint.class_id,
-                        &constraint.ty,
-                        location_id,
-                        unifiers,
-                    ) {
-                        return false;
-                    }
+                let mut constraint_ty = unifier.apply(&constraint.ty);
+                for (unifier, _) in &unifiers[first..] {
+                    constraint_ty.apply(unifier);
+                }
+                if !self.check_instance(constraint.class_id, &constraint_ty, location_id, unifiers)
+                {
+                    return false;
                 }
             }
             return true;
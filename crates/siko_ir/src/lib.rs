@@ -1,3 +1,4 @@
+pub mod actor;
 pub mod builder;
 pub mod class;
 pub mod data;
@@ -38,7 +38,10 @@ pub enum Pattern {
     StringLiteral(String),
     CharLiteral(char),
     CharRange(char, char, RangeKind),
+    IntegerRange(i64, i64, RangeKind),
     Typed(PatternId, TypeSignatureId),
+    List(Vec<PatternId>),
+    Cons(PatternId, PatternId),
 }
 
 #[derive(Debug, Clone)]
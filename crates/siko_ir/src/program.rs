@@ -1,3 +1,7 @@
+use crate::actor::Actor;
+use crate::actor::ActorId;
+use crate::actor::Protocol;
+use crate::actor::ProtocolId;
 use crate::class::Class;
 use crate::class::ClassId;
 use crate::class::ClassMember;
@@ -63,6 +67,8 @@ pub struct Program {
     pub classes: ItemContainer<ClassId, Class>,
     pub class_members: ItemContainer<ClassMemberId, ClassMember>,
     pub instances: ItemContainer<InstanceId, Instance>,
+    pub protocols: ItemContainer<ProtocolId, Protocol>,
+    pub actors: ItemContainer<ActorId, Actor>,
     pub expr_types: BTreeMap<ExprId, Type>,
     pub pattern_types: BTreeMap<PatternId, Type>,
     pub function_types: BTreeMap<FunctionId, Type>,
@@ -87,6 +93,8 @@ impl Program {
             classes: ItemContainer::new(),
             class_members: ItemContainer::new(),
             instances: ItemContainer::new(),
+            protocols: ItemContainer::new(),
+            actors: ItemContainer::new(),
             expr_types: BTreeMap::new(),
             pattern_types: BTreeMap::new(),
             function_types: BTreeMap::new(),
@@ -259,6 +267,20 @@ impl Program {
         (module, name)
     }
 
+    pub fn get_protocol_by_type(&self, typedef_id: TypeDefId) -> Option<&Protocol> {
+        self.protocols
+            .items
+            .values()
+            .find(|protocol| protocol.typedef_id == typedef_id)
+    }
+
+    pub fn get_actor_by_type(&self, typedef_id: TypeDefId) -> Option<&Actor> {
+        self.actors
+            .items
+            .values()
+            .find(|actor| actor.typedef_id == typedef_id)
+    }
+
     pub fn get_unifier(&self) -> Unifier {
         Unifier::new(self.type_var_generator.clone())
     }
@@ -2,7 +2,7 @@ use crate::class::ClassId;
 use crate::types::Type;
 use std::collections::BTreeMap;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Constraint {
     pub class_id: ClassId,
     pub ty: Type,
@@ -85,6 +85,11 @@ impl Substitution {
                 Some(ty) => self.apply(ty),
                 None => ty.clone(),
             },
+            Type::Applied(head, items) => {
+                let head = self.apply(head);
+                let items = items.iter().map(|ty| self.apply(ty)).collect();
+                Type::apply_args(head, items)
+            }
             Type::Ref(item) => {
                 let item = self.apply(item);
                 Type::Ref(Box::new(item))
@@ -17,6 +17,7 @@ pub enum TypeSignature {
     Tuple(Vec<TypeSignatureId>),
     Function(TypeSignatureId, TypeSignatureId),
     TypeArgument(usize, String, Vec<ClassId>),
+    Applied(TypeSignatureId, Vec<TypeSignatureId>),
     Named(String, TypeDefId, Vec<TypeSignatureId>),
     Variant(String, Vec<TypeSignatureId>),
     Ref(TypeSignatureId),
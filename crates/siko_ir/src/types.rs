@@ -76,11 +76,33 @@ pub enum Type {
     Function(Box<Type>, Box<Type>),
     Var(usize, Vec<ClassId>),
     FixedTypeArg(String, usize, Vec<ClassId>),
+    Applied(Box<Type>, Vec<Type>),
     Ref(Box<Type>),
     Never(usize),
 }
 
 impl Type {
+    pub fn apply_args(head: Type, args: Vec<Type>) -> Type {
+        // a type constructor applied to its missing args becomes a named type again
+        match head {
+            Type::Named(name, id, mut items) => {
+                items.extend(args);
+                Type::Named(name, id, items)
+            }
+            Type::Applied(head, mut items) => {
+                items.extend(args);
+                Type::Applied(head, items)
+            }
+            head => {
+                if args.is_empty() {
+                    head
+                } else {
+                    Type::Applied(Box::new(head), args)
+                }
+            }
+        }
+    }
+
     pub fn contains(&self, index: usize) -> bool {
         match self {
             Type::Tuple(items) => {
@@ -114,6 +136,17 @@ impl Type {
             Type::FixedTypeArg(_, i, _) => {
                 return *i == index;
             }
+            Type::Applied(head, items) => {
+                if head.contains(index) {
+                    return true;
+                }
+                for item in items {
+                    if item.contains(index) {
+                        return true;
+                    }
+                }
+                return false;
+            }
             Type::Ref(item) => item.contains(index),
             Type::Never(_) => false,
         }
@@ -142,6 +175,7 @@ impl Type {
             Type::Function(..) => BaseType::Function,
             Type::Var(..) => BaseType::Generic,
             Type::FixedTypeArg(..) => BaseType::Generic,
+            Type::Applied(..) => BaseType::Generic,
             Type::Ref(item) => item.get_base_type(),
             Type::Never(_) => BaseType::Generic,
         }
@@ -164,6 +198,11 @@ impl Type {
             }
             Type::Var(..) => self.clone(),
             Type::FixedTypeArg(_, index, constraints) => Type::Var(*index, constraints.clone()),
+            Type::Applied(head, items) => {
+                let head = head.remove_fixed_types();
+                let new_items: Vec<_> = items.iter().map(|i| i.remove_fixed_types()).collect();
+                Type::Applied(Box::new(head), new_items)
+            }
             Type::Ref(item) => {
                 let item = item.remove_fixed_types();
                 Type::Ref(Box::new(item))
@@ -207,6 +246,14 @@ impl Type {
                 let new_index = arg_map.entry(*index).or_insert_with(|| gen.get_new_index());
                 Type::FixedTypeArg(name.clone(), *new_index, constraints.clone())
             }
+            Type::Applied(head, items) => {
+                let head = head.duplicate(arg_map, type_var_generator);
+                let new_items: Vec<_> = items
+                    .iter()
+                    .map(|i| i.duplicate(arg_map, type_var_generator))
+                    .collect();
+                Type::Applied(Box::new(head), new_items)
+            }
             Type::Ref(item) => {
                 let item = item.duplicate(arg_map, type_var_generator);
                 Type::Ref(Box::new(item))
@@ -226,6 +273,7 @@ impl Type {
             Type::Function(_, to) => 1 + to.get_arg_count(),
             Type::Var(..) => 0,
             Type::FixedTypeArg(..) => 0,
+            Type::Applied(..) => 0,
             Type::Ref(..) => 0,
             Type::Never(_) => 0,
         }
@@ -241,6 +289,7 @@ impl Type {
             }
             Type::Var(..) => {}
             Type::FixedTypeArg(..) => {}
+            Type::Applied(..) => {}
             Type::Ref(..) => {}
             Type::Never(_) => {}
         }
@@ -263,6 +312,7 @@ impl Type {
             }
             Type::Var(..) => self.clone(),
             Type::FixedTypeArg(..) => self.clone(),
+            Type::Applied(..) => self.clone(),
             Type::Ref(..) => self.clone(),
             Type::Never(_) => self.clone(),
         }
@@ -297,6 +347,12 @@ impl Type {
                     args.add(*index, *c);
                 }
             }
+            Type::Applied(head, items) => {
+                head.collect(args, context);
+                for item in items {
+                    item.collect(args, context);
+                }
+            }
             Type::Ref(item) => item.collect(args, context),
             Type::Never(_) => {}
         }
@@ -323,11 +379,55 @@ impl Type {
             Type::Function(from, to) => from.is_concrete_type() && to.is_concrete_type(),
             Type::Var(..) => false,
             Type::FixedTypeArg(..) => false,
+            Type::Applied(..) => false,
             Type::Ref(item) => item.is_concrete_type(),
             Type::Never(_) => true,
         }
     }
 
+    pub fn has_type_var(&self) -> bool {
+        match self {
+            Type::Tuple(items) => items.iter().any(|item| item.has_type_var()),
+            Type::Named(_, _, items) => items.iter().any(|item| item.has_type_var()),
+            Type::Function(from, to) => from.has_type_var() || to.has_type_var(),
+            Type::Var(..) => true,
+            Type::FixedTypeArg(..) => false,
+            Type::Applied(head, items) => {
+                head.has_type_var() || items.iter().any(|item| item.has_type_var())
+            }
+            Type::Ref(item) => item.has_type_var(),
+            Type::Never(_) => false,
+        }
+    }
+
+    pub fn collect_type_vars(&self, vars: &mut Vec<Type>) {
+        match self {
+            Type::Tuple(items) | Type::Named(_, _, items) => {
+                for item in items {
+                    item.collect_type_vars(vars);
+                }
+            }
+            Type::Function(from, to) => {
+                from.collect_type_vars(vars);
+                to.collect_type_vars(vars);
+            }
+            Type::Var(..) => {
+                if !vars.contains(self) {
+                    vars.push(self.clone());
+                }
+            }
+            Type::FixedTypeArg(..) => {}
+            Type::Applied(head, items) => {
+                head.collect_type_vars(vars);
+                for item in items {
+                    item.collect_type_vars(vars);
+                }
+            }
+            Type::Ref(item) => item.collect_type_vars(vars),
+            Type::Never(_) => {}
+        }
+    }
+
     pub fn get_type_args(&self) -> Vec<Type> {
         match self {
             Type::Named(_, _, items) => items.clone(),
@@ -408,6 +508,22 @@ impl Type {
             }
             Type::Var(index, _) => resolver_context.get_type_arg_name(*index),
             Type::FixedTypeArg(name, _, _) => format!("{}", name),
+            Type::Applied(head, items) => {
+                let ss: Vec<_> = items
+                    .iter()
+                    .map(|item| item.as_string(true, resolver_context))
+                    .collect();
+                let applied_str = format!(
+                    "{} {}",
+                    head.as_string(true, resolver_context),
+                    ss.join(" ")
+                );
+                if need_parens {
+                    format!("({})", applied_str)
+                } else {
+                    applied_str
+                }
+            }
             Type::Named(name, id, items) => {
                 let ss: Vec<_> = items
                     .iter()
@@ -493,6 +609,10 @@ impl fmt::Display for Type {
                 };
                 write!(f, "f${}{}", id, c)
             }
+            Type::Applied(head, items) => {
+                let ss: Vec<_> = items.iter().map(|i| format!("{}", i)).collect();
+                write!(f, "{} ({})", head, ss.join(" "))
+            }
             Type::Ref(item) => write!(f, "&{}", item),
             Type::Never(_) => write!(f, "!"),
         }
@@ -25,7 +25,9 @@ impl Unifier {
         match (&type1, &type2) {
             (Type::Named(_, id1, items1), Type::Named(_, id2, items2)) => {
                 if id1 == id2 {
-                    assert_eq!(items1.len(), items2.len());
+                    if items1.len() != items2.len() {
+                        return Err(Error::Fail);
+                    }
                     for (item1, item2) in items1.iter().zip(items2.iter()) {
                         self.unify(item1, item2)?;
                     }
@@ -119,10 +121,45 @@ impl Unifier {
             (type1, Type::Ref(ty)) => {
                 return self.unify(type1, ty);
             }
+            (Type::Applied(head, items), type2) => {
+                return self.unify_applied(head, items, type2);
+            }
+            (type1, Type::Applied(head, items)) => {
+                return self.unify_applied(head, items, type1);
+            }
             _ => return Err(Error::Fail),
         }
     }
 
+    fn unify_applied(&mut self, head: &Type, items: &Vec<Type>, other: &Type) -> Result<(), Error> {
+        // the head takes the leading args of the other type, the args take the rest
+        let (other_head, other_items) = match other {
+            Type::Named(name, id, other_items) => (
+                Type::Named(name.clone(), *id, Vec::new()),
+                other_items.clone(),
+            ),
+            Type::Applied(other_head, other_items) => (*other_head.clone(), other_items.clone()),
+            _ => return Err(Error::Fail),
+        };
+        if other_items.len() < items.len() {
+            if let Type::Applied(..) = other {
+                return self.unify_applied(
+                    &other_head,
+                    &other_items,
+                    &Type::Applied(Box::new(head.clone()), items.clone()),
+                );
+            }
+            return Err(Error::Fail);
+        }
+        let split = other_items.len() - items.len();
+        let other_head = Type::apply_args(other_head, other_items[..split].to_vec());
+        self.unify(head, &other_head)?;
+        for (item, other_item) in items.iter().zip(other_items[split..].iter()) {
+            self.unify(item, other_item)?;
+        }
+        Ok(())
+    }
+
     pub fn apply(&self, ty: &Type) -> Type {
         self.substitution.apply(ty)
     }
@@ -111,6 +111,11 @@ pub fn walk_expr(expr_id: &ExprId, visitor: &mut dyn Visitor) {
         Expr::Break(expr) => {
             walk_expr(expr, visitor);
         }
+        Expr::Hole(info) => {
+            for (_, binding) in &info.bindings {
+                walk_expr(binding, visitor);
+            }
+        }
     }
     visitor.visit_expr(*expr_id, expr);
 }
@@ -144,9 +149,19 @@ fn walk_pattern(pattern_id: &PatternId, visitor: &mut dyn Visitor) {
         Pattern::StringLiteral(_) => {}
         Pattern::CharLiteral(_) => {}
         Pattern::CharRange(_, _, _) => {}
+        Pattern::IntegerRange(_, _, _) => {}
         Pattern::Typed(id, _) => {
             walk_pattern(id, visitor);
         }
+        Pattern::List(items) => {
+            for item in items {
+                walk_pattern(item, visitor);
+            }
+        }
+        Pattern::Cons(head, tail) => {
+            walk_pattern(head, visitor);
+            walk_pattern(tail, visitor);
+        }
     }
     visitor.visit_pattern(*pattern_id, pattern);
 }
@@ -47,4 +47,31 @@ impl LocationSet {
             });
         }
     }
+
+    pub fn get_start(&self) -> (usize, usize) {
+        let (line, ranges) = self.lines.iter().next().expect("Empty location set");
+        let column = ranges.iter().map(|r| r.start).min().expect("Empty line");
+        (*line, column)
+    }
+
+    pub fn get_end(&self) -> (usize, usize) {
+        let (line, ranges) = self.lines.iter().next_back().expect("Empty location set");
+        let column = ranges.iter().map(|r| r.end).max().expect("Empty line");
+        (*line, column)
+    }
+
+    pub fn contains(&self, line: usize, column: usize) -> bool {
+        match self.lines.get(&line) {
+            Some(ranges) => ranges.iter().any(|r| r.start <= column && column <= r.end),
+            None => false,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.lines
+            .values()
+            .flat_map(|ranges| ranges.iter())
+            .map(|r| r.end - r.start)
+            .sum()
+    }
 }
@@ -0,0 +1,218 @@
+use siko_compiler::compiler::Compiler;
+use siko_compiler::compiler::CompilerInput;
+use siko_compiler::config::Config;
+use siko_compiler::diagnostic::get_diagnostics;
+use siko_compiler::diagnostic::Diagnostic;
+use siko_ir::data::TypeDef;
+use siko_ir::expr::Expr;
+use siko_ir::expr::ExprId;
+use siko_ir::function::FunctionId;
+use siko_ir::function::FunctionInfo;
+use siko_ir::program::Program;
+use siko_location_info::filepath::FilePath;
+use siko_location_info::location_id::LocationId;
+use siko_location_info::location_info::LocationInfo;
+use siko_location_info::location_set::LocationSet;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+
+#[derive(Debug, Clone)]
+pub struct SourceRange {
+    pub file_path: FilePath,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl SourceRange {
+    pub fn new(location_set: &LocationSet) -> SourceRange {
+        SourceRange {
+            file_path: location_set.file_path.clone(),
+            start: location_set.get_start(),
+            end: location_set.get_end(),
+        }
+    }
+}
+
+pub struct Analysis {
+    program: Program,
+    location_info: LocationInfo,
+}
+
+impl Analysis {
+    // The checked files are not necessarily part of a program, so main is not
+    // required. A compiler panic only loses this analysis, not the whole server.
+    pub fn run(inputs: Vec<CompilerInput>) -> (Option<Analysis>, Vec<Diagnostic>) {
+        let mut config = Config::new();
+        config.main_required = false;
+        let mut compiler = Compiler::new(config);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match compiler.parse(inputs) {
+            Ok(program) => compiler.check(&program),
+            Err(err) => Err(err),
+        }));
+        let result = match result {
+            Ok(result) => result,
+            Err(_) => {
+                eprintln!("siko_lsp: compiler panicked, analysis skipped");
+                return (None, Vec::new());
+            }
+        };
+        match result {
+            Ok(program) => {
+                let analysis = Analysis {
+                    program: program,
+                    location_info: compiler.get_location_info().clone(),
+                };
+                (Some(analysis), Vec::new())
+            }
+            Err(err) => {
+                let diagnostics = get_diagnostics(&err, &compiler.context());
+                (None, diagnostics)
+            }
+        }
+    }
+
+    fn get_size_at(
+        &self,
+        location_id: LocationId,
+        file_path: &FilePath,
+        line: usize,
+        column: usize,
+    ) -> Option<usize> {
+        let location_set = self.location_info.get_item_location(&location_id);
+        if location_set.file_path == *file_path && location_set.contains(line, column) {
+            Some(location_set.get_size())
+        } else {
+            None
+        }
+    }
+
+    fn find_expr(&self, file_path: &FilePath, line: usize, column: usize) -> Option<ExprId> {
+        let mut found: Option<(usize, ExprId)> = None;
+        for (expr_id, expr_info) in &self.program.exprs.items {
+            if let Some(size) = self.get_size_at(expr_info.location_id, file_path, line, column) {
+                let smaller = match found {
+                    Some((found_size, _)) => size < found_size,
+                    None => true,
+                };
+                if smaller {
+                    found = Some((size, *expr_id));
+                }
+            }
+        }
+        found.map(|(_, expr_id)| expr_id)
+    }
+
+    fn find_function(
+        &self,
+        file_path: &FilePath,
+        line: usize,
+        column: usize,
+    ) -> Option<FunctionId> {
+        for (function_id, function) in &self.program.functions.items {
+            if let FunctionInfo::NamedFunction(info) = &function.info {
+                if self
+                    .get_size_at(info.location_id, file_path, line, column)
+                    .is_some()
+                {
+                    return Some(*function_id);
+                }
+            }
+        }
+        None
+    }
+
+    fn get_function_description(&self, function_id: FunctionId) -> Option<String> {
+        let function = self.program.functions.get(&function_id);
+        let name = match &function.info {
+            FunctionInfo::NamedFunction(info) => format!("{}", info),
+            FunctionInfo::RecordConstructor(info) => self
+                .program
+                .typedefs
+                .get(&info.type_id)
+                .get_record()
+                .name
+                .clone(),
+            FunctionInfo::VariantConstructor(info) => {
+                let adt = self.program.typedefs.get(&info.type_id).get_adt();
+                adt.variants[info.index].name.clone()
+            }
+            FunctionInfo::LocalFunction(info) => info.name.clone(),
+            FunctionInfo::Lambda(_) => return None,
+        };
+        let ty = self.program.function_types.get(&function_id)?;
+        Some(format!(
+            "{} :: {}",
+            name,
+            ty.get_resolved_type_string(&self.program)
+        ))
+    }
+
+    pub fn hover(&self, file_path: &FilePath, line: usize, column: usize) -> Option<String> {
+        if let Some(expr_id) = self.find_expr(file_path, line, column) {
+            let expr = &self.program.exprs.get(&expr_id).item;
+            return match expr {
+                Expr::StaticFunctionCall(function_id, _) => {
+                    self.get_function_description(*function_id)
+                }
+                Expr::ClassFunctionCall(class_member_id, _) => {
+                    let class_member = self.program.class_members.get(class_member_id);
+                    let (ty, _) = self.program.class_member_types.get(class_member_id)?;
+                    Some(format!(
+                        "{} :: {}",
+                        class_member.name,
+                        ty.get_resolved_type_string(&self.program)
+                    ))
+                }
+                _ => {
+                    let ty = self.program.expr_types.get(&expr_id)?;
+                    Some(ty.get_resolved_type_string(&self.program))
+                }
+            };
+        }
+        let function_id = self.find_function(file_path, line, column)?;
+        self.get_function_description(function_id)
+    }
+
+    fn get_function_location(&self, function_id: FunctionId) -> Option<LocationId> {
+        let function = self.program.functions.get(&function_id);
+        match &function.info {
+            FunctionInfo::NamedFunction(info) => Some(info.location_id),
+            FunctionInfo::RecordConstructor(info) => {
+                self.get_typedef_location(self.program.typedefs.get(&info.type_id))
+            }
+            FunctionInfo::VariantConstructor(info) => {
+                self.get_typedef_location(self.program.typedefs.get(&info.type_id))
+            }
+            FunctionInfo::LocalFunction(info) => Some(info.location_id),
+            FunctionInfo::Lambda(_) => None,
+        }
+    }
+
+    // adts do not keep their location in the ir
+    fn get_typedef_location(&self, typedef: &TypeDef) -> Option<LocationId> {
+        match typedef {
+            TypeDef::Adt(_) => None,
+            TypeDef::Record(record) => Some(record.location_id),
+        }
+    }
+
+    pub fn definition(
+        &self,
+        file_path: &FilePath,
+        line: usize,
+        column: usize,
+    ) -> Option<SourceRange> {
+        let expr_id = self.find_expr(file_path, line, column)?;
+        let expr = &self.program.exprs.get(&expr_id).item;
+        let location_id = match expr {
+            Expr::StaticFunctionCall(function_id, _) => self.get_function_location(*function_id)?,
+            Expr::ClassFunctionCall(class_member_id, _) => {
+                self.program.class_members.get(class_member_id).location_id
+            }
+            Expr::ExprValue(_, pattern_id) => self.program.patterns.get(pattern_id).location_id,
+            _ => return None,
+        };
+        let location_set = self.location_info.get_item_location(&location_id);
+        Some(SourceRange::new(location_set))
+    }
+}
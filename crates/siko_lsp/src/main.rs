@@ -0,0 +1,22 @@
+mod analysis;
+mod server;
+mod symbols;
+mod transport;
+
+use server::Server;
+use std::io::stdin;
+use std::io::stdout;
+
+fn main() {
+    // diagnostics are rendered by the error printer, escape codes must not leak into them
+    colored::control::set_override(false);
+    let stdin = stdin();
+    let stdout = stdout();
+    let mut reader = stdin.lock();
+    let mut writer = stdout.lock();
+    let mut server = Server::new();
+    if let Err(err) = server.run(&mut reader, &mut writer) {
+        eprintln!("siko_lsp: {}", err);
+        std::process::exit(1);
+    }
+}
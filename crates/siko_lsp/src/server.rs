@@ -0,0 +1,458 @@
+use crate::analysis::Analysis;
+use crate::analysis::SourceRange;
+use crate::symbols::get_document_symbols;
+use crate::symbols::Symbol;
+use crate::transport::read_message;
+use crate::transport::write_message;
+use serde_json::json;
+use serde_json::Value;
+use siko_compiler::compiler::CompilerInput;
+use siko_compiler::diagnostic::Diagnostic;
+use siko_location_info::filepath::FilePath;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::env;
+use std::io::BufRead;
+use std::io::Result;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use walkdir::WalkDir;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+
+fn uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut result = Vec::new();
+    let bytes = path.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&path[index + 1..index + 3], 16) {
+                result.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        result.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&result).to_string()
+}
+
+fn path_to_uri(path: &str) -> String {
+    format!("file://{}", path.replace('%', "%25").replace(' ', "%20"))
+}
+
+fn to_lsp_range(start: (usize, usize), end: (usize, usize)) -> Value {
+    json!({
+        "start": { "line": start.0, "character": start.1 },
+        "end": { "line": end.0, "character": end.1 },
+    })
+}
+
+fn to_lsp_symbol(symbol: &Symbol) -> Value {
+    let range = to_lsp_range(symbol.range.start, symbol.range.end);
+    let children: Vec<_> = symbol.children.iter().map(to_lsp_symbol).collect();
+    json!({
+        "name": symbol.name,
+        "kind": symbol.kind.get_lsp_kind(),
+        "range": range,
+        "selectionRange": range,
+        "children": children,
+    })
+}
+
+fn to_lsp_location(range: &SourceRange) -> Value {
+    json!({
+        "uri": path_to_uri(&range.file_path.path),
+        "range": to_lsp_range(range.start, range.end),
+    })
+}
+
+fn collect_sources(dir: &Path, sources: &mut BTreeSet<String>) {
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        if entry
+            .path()
+            .extension()
+            .map(|ext| ext == "sk")
+            .unwrap_or(false)
+        {
+            let path = entry
+                .path()
+                .canonicalize()
+                .unwrap_or(entry.path().to_path_buf());
+            sources.insert(format!("{}", path.display()));
+        }
+    }
+}
+
+pub struct Server {
+    std_path: Option<PathBuf>,
+    documents: BTreeMap<String, String>,
+    analysis: Option<Analysis>,
+    published: BTreeSet<String>,
+    outgoing: Vec<Value>,
+    exit: bool,
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server {
+            std_path: None,
+            documents: BTreeMap::new(),
+            analysis: None,
+            published: BTreeSet::new(),
+            outgoing: Vec::new(),
+            exit: false,
+        }
+    }
+
+    fn respond(&mut self, id: Value, result: Value) {
+        self.outgoing.push(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+        }));
+    }
+
+    fn respond_error(&mut self, id: Value, code: i64, msg: String) {
+        self.outgoing.push(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": msg },
+        }));
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.outgoing.push(json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }));
+    }
+
+    // The std can be given in the initialization options, the SIKO_STD
+    // environment variable or found as a std folder in the workspace root.
+    fn initialize(&mut self, params: &Value) -> Value {
+        let std_path = match params["initializationOptions"]["stdPath"].as_str() {
+            Some(path) => Some(PathBuf::from(path)),
+            None => match env::var("SIKO_STD") {
+                Ok(path) => Some(PathBuf::from(path)),
+                Err(_) => params["rootUri"]
+                    .as_str()
+                    .map(|uri| Path::new(&uri_to_path(uri)).join("std")),
+            },
+        };
+        self.std_path = std_path.filter(|path| path.is_dir());
+        json!({
+            "capabilities": {
+                "textDocumentSync": {
+                    "openClose": true,
+                    "change": 1,
+                    "save": { "includeText": true },
+                },
+                "hoverProvider": true,
+                "definitionProvider": true,
+                "documentSymbolProvider": true,
+            },
+            "serverInfo": { "name": "siko_lsp" },
+        })
+    }
+
+    // A program consists of the std and every source file next to the checked
+    // document, the content of open documents is taken from the editor.
+    fn check(&mut self, path: &str) {
+        let mut sources = BTreeSet::new();
+        if let Some(std_path) = &self.std_path {
+            collect_sources(std_path, &mut sources);
+        }
+        if let Some(dir) = Path::new(path).parent() {
+            collect_sources(dir, &mut sources);
+        }
+        sources.insert(path.to_string());
+        let inputs = sources
+            .into_iter()
+            .map(|source| match self.documents.get(&source) {
+                Some(content) => CompilerInput::Memory {
+                    name: source,
+                    content: content.clone(),
+                },
+                None => CompilerInput::File { name: source },
+            })
+            .collect();
+        let (analysis, diagnostics) = Analysis::run(inputs);
+        self.analysis = analysis;
+        self.publish_diagnostics(path, diagnostics);
+    }
+
+    // files that had errors at the last check are cleared by publishing an empty list
+    fn publish_diagnostics(&mut self, path: &str, diagnostics: Vec<Diagnostic>) {
+        let mut files: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        files.insert(path.to_string(), Vec::new());
+        for path in &self.published {
+            files.insert(path.clone(), Vec::new());
+        }
+        for diagnostic in diagnostics {
+            let lsp_diagnostic = json!({
+                "range": to_lsp_range(diagnostic.start, diagnostic.end),
+                "severity": 1,
+                "source": "siko",
+                "message": diagnostic.message,
+            });
+            files
+                .entry(diagnostic.file_path.path.clone())
+                .or_default()
+                .push(lsp_diagnostic);
+        }
+        self.published.clear();
+        for (path, diagnostics) in files {
+            if !diagnostics.is_empty() {
+                self.published.insert(path.clone());
+            }
+            self.notify(
+                "textDocument/publishDiagnostics",
+                json!({
+                    "uri": path_to_uri(&path),
+                    "diagnostics": diagnostics,
+                }),
+            );
+        }
+    }
+
+    fn get_document_path(&self, params: &Value) -> Option<String> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let path = uri_to_path(uri);
+        let path = Path::new(&path)
+            .canonicalize()
+            .map(|path| format!("{}", path.display()))
+            .unwrap_or(path);
+        Some(path)
+    }
+
+    fn get_position(&self, params: &Value) -> Option<(String, usize, usize)> {
+        let path = self.get_document_path(params)?;
+        let line = params["position"]["line"].as_u64()? as usize;
+        let column = params["position"]["character"].as_u64()? as usize;
+        Some((path, line, column))
+    }
+
+    fn handle_request(&mut self, id: Value, method: &str, params: &Value) {
+        match method {
+            "initialize" => {
+                let result = self.initialize(params);
+                self.respond(id, result);
+            }
+            "shutdown" => {
+                self.respond(id, Value::Null);
+            }
+            "textDocument/hover" => {
+                let hover = match (self.get_position(params), &self.analysis) {
+                    (Some((path, line, column)), Some(analysis)) => {
+                        analysis.hover(&FilePath::new(path), line, column)
+                    }
+                    _ => None,
+                };
+                let result = match hover {
+                    Some(hover) => json!({
+                        "contents": { "kind": "plaintext", "value": hover },
+                    }),
+                    None => Value::Null,
+                };
+                self.respond(id, result);
+            }
+            "textDocument/definition" => {
+                let definition = match (self.get_position(params), &self.analysis) {
+                    (Some((path, line, column)), Some(analysis)) => {
+                        analysis.definition(&FilePath::new(path), line, column)
+                    }
+                    _ => None,
+                };
+                let result = match definition {
+                    Some(range) => to_lsp_location(&range),
+                    None => Value::Null,
+                };
+                self.respond(id, result);
+            }
+            "textDocument/documentSymbol" => {
+                let symbols = match self.get_document_path(params) {
+                    Some(path) => {
+                        let content = match self.documents.get(&path) {
+                            Some(content) => Some(content.clone()),
+                            None => std::fs::read_to_string(&path).ok(),
+                        };
+                        match content {
+                            Some(content) => get_document_symbols(&FilePath::new(path), &content),
+                            None => Vec::new(),
+                        }
+                    }
+                    None => Vec::new(),
+                };
+                let symbols: Vec<_> = symbols.iter().map(to_lsp_symbol).collect();
+                self.respond(id, json!(symbols));
+            }
+            _ => {
+                self.respond_error(id, METHOD_NOT_FOUND, format!("Unknown method {}", method));
+            }
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) {
+        match method {
+            "textDocument/didOpen" => {
+                if let Some(path) = self.get_document_path(params) {
+                    if let Some(text) = params["textDocument"]["text"].as_str() {
+                        self.documents.insert(path.clone(), text.to_string());
+                    }
+                    self.check(&path);
+                }
+            }
+            "textDocument/didChange" => {
+                if let Some(path) = self.get_document_path(params) {
+                    let changes = params["contentChanges"].as_array();
+                    if let Some(change) = changes.and_then(|changes| changes.last()) {
+                        if let Some(text) = change["text"].as_str() {
+                            self.documents.insert(path, text.to_string());
+                        }
+                    }
+                }
+            }
+            "textDocument/didSave" => {
+                if let Some(path) = self.get_document_path(params) {
+                    if let Some(text) = params["text"].as_str() {
+                        self.documents.insert(path.clone(), text.to_string());
+                    }
+                    self.check(&path);
+                }
+            }
+            "textDocument/didClose" => {
+                if let Some(path) = self.get_document_path(params) {
+                    self.documents.remove(&path);
+                }
+            }
+            "exit" => {
+                self.exit = true;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn run(&mut self, reader: &mut dyn BufRead, writer: &mut dyn Write) -> Result<()> {
+        while !self.exit {
+            let message = match read_message(reader)? {
+                Some(message) => message,
+                None => break,
+            };
+            let method = message["method"].as_str().unwrap_or("").to_string();
+            let params = message["params"].clone();
+            match message.get("id") {
+                Some(id) => self.handle_request(id.clone(), &method, &params),
+                None => self.handle_notification(&method, &params),
+            }
+            for message in std::mem::take(&mut self.outgoing) {
+                write_message(writer, &message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Server;
+    use crate::transport::read_message;
+    use crate::transport::write_message;
+    use serde_json::json;
+    use serde_json::Value;
+    use std::io::Cursor;
+
+    const LIB_PATH: &str = "/siko_lsp_test/Lib.sk";
+    const LIB: &str = "module Lib where
+
+double :: Int -> Int
+double x = x * 2
+
+quadruple :: Int -> Int
+quadruple x = double (double x)
+";
+
+    fn request(id: i64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    fn notification(method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "method": method, "params": params })
+    }
+
+    fn position(line: usize, character: usize) -> Value {
+        json!({
+            "textDocument": { "uri": format!("file://{}", LIB_PATH) },
+            "position": { "line": line, "character": character },
+        })
+    }
+
+    fn run_server(messages: Vec<Value>) -> Vec<Value> {
+        let mut input = Vec::new();
+        for message in &messages {
+            write_message(&mut input, message).expect("write failed");
+        }
+        let mut reader = Cursor::new(input);
+        let mut output = Vec::new();
+        let mut server = Server::new();
+        server.run(&mut reader, &mut output).expect("server failed");
+        let mut reader = Cursor::new(output);
+        let mut responses = Vec::new();
+        while let Some(message) = read_message(&mut reader).expect("read failed") {
+            responses.push(message);
+        }
+        responses
+    }
+
+    fn get_response(responses: &[Value], id: i64) -> &Value {
+        responses
+            .iter()
+            .find(|response| response["id"] == json!(id))
+            .expect("response not found")
+    }
+
+    #[test]
+    fn hover_and_definition_in_library_without_main() {
+        let std_path = format!("{}/../../std", env!("CARGO_MANIFEST_DIR"));
+        let responses = run_server(vec![
+            request(
+                1,
+                "initialize",
+                json!({ "initializationOptions": { "stdPath": std_path } }),
+            ),
+            notification(
+                "textDocument/didOpen",
+                json!({
+                    "textDocument": {
+                        "uri": format!("file://{}", LIB_PATH),
+                        "text": LIB,
+                    },
+                }),
+            ),
+            request(2, "textDocument/hover", position(6, 15)),
+            request(3, "textDocument/definition", position(6, 15)),
+            request(4, "shutdown", Value::Null),
+            notification("exit", Value::Null),
+        ]);
+        let diagnostics = responses
+            .iter()
+            .find(|message| message["method"] == json!("textDocument/publishDiagnostics"))
+            .expect("diagnostics not published");
+        assert_eq!(diagnostics["params"]["diagnostics"], json!([]));
+        let hover = get_response(&responses, 2);
+        assert_eq!(
+            hover["result"]["contents"]["value"],
+            json!("Lib/double :: Int -> Int")
+        );
+        let definition = get_response(&responses, 3);
+        assert_eq!(
+            definition["result"]["uri"],
+            json!(format!("file://{}", LIB_PATH))
+        );
+        assert_eq!(definition["result"]["range"]["start"]["line"], json!(3));
+    }
+}
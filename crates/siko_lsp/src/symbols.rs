@@ -0,0 +1,191 @@
+use crate::analysis::SourceRange;
+use siko_compiler::compiler::Compiler;
+use siko_compiler::compiler::CompilerInput;
+use siko_compiler::config::Config;
+use siko_location_info::filepath::FilePath;
+use siko_location_info::location_id::LocationId;
+use siko_location_info::location_info::LocationInfo;
+use siko_syntax::function::FunctionId;
+use siko_syntax::module::Module;
+use siko_syntax::program::Program;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+pub enum SymbolKind {
+    Module,
+    Actor,
+    Field,
+    Enum,
+    Class,
+    Function,
+    Instance,
+    Variant,
+    Record,
+    Protocol,
+}
+
+impl SymbolKind {
+    pub fn get_lsp_kind(&self) -> u32 {
+        match self {
+            SymbolKind::Module => 2,
+            SymbolKind::Actor => 5,
+            SymbolKind::Field => 8,
+            SymbolKind::Enum => 10,
+            SymbolKind::Class => 11,
+            SymbolKind::Function => 12,
+            SymbolKind::Instance => 19,
+            SymbolKind::Variant => 22,
+            SymbolKind::Record => 23,
+            SymbolKind::Protocol => 24,
+        }
+    }
+}
+
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub range: SourceRange,
+    pub children: Vec<Symbol>,
+}
+
+struct SymbolCollector<'a> {
+    program: &'a Program,
+    location_info: &'a LocationInfo,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> SymbolCollector<'a> {
+    fn get_range(&self, location_id: LocationId) -> SourceRange {
+        SourceRange::new(self.location_info.get_item_location(&location_id))
+    }
+
+    fn create_symbol(&self, name: String, kind: SymbolKind, location_id: LocationId) -> Symbol {
+        Symbol {
+            name: name,
+            kind: kind,
+            range: self.get_range(location_id),
+            children: Vec::new(),
+        }
+    }
+
+    fn get_function_symbols(&self, functions: &BTreeMap<String, Vec<FunctionId>>) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+        for (name, ids) in functions {
+            let function = self.program.functions.get(&ids[0]);
+            let symbol =
+                self.create_symbol(name.clone(), SymbolKind::Function, function.location_id);
+            symbols.push(symbol);
+        }
+        symbols
+    }
+
+    // instances have no name of their own, their header line is used instead
+    fn get_instance_name(&self, location_id: LocationId) -> String {
+        let range = self.get_range(location_id);
+        let line = self.lines.get(range.start.0).cloned().unwrap_or("");
+        let header: String = line.chars().skip(range.start.1).collect();
+        let header = header.trim();
+        let header = header.strip_suffix("where").unwrap_or(header);
+        header.trim().to_string()
+    }
+
+    fn get_module_symbol(&self, module: &Module) -> Symbol {
+        let mut symbol =
+            self.create_symbol(module.name.clone(), SymbolKind::Module, module.location_id);
+        symbol.range.end = (self.lines.len(), 0);
+        let mut message_records = BTreeSet::new();
+        for protocol_id in &module.protocols {
+            let protocol = self.program.protocols.get(protocol_id);
+            message_records.insert(protocol.record_id);
+            let child = self.create_symbol(
+                protocol.name.clone(),
+                SymbolKind::Protocol,
+                protocol.location_id,
+            );
+            symbol.children.push(child);
+        }
+        for actor_id in &module.actors {
+            let actor = self.program.actors.get(actor_id);
+            message_records.insert(actor.record_id);
+            let child =
+                self.create_symbol(actor.name.clone(), SymbolKind::Actor, actor.location_id);
+            symbol.children.push(child);
+        }
+        for adt_id in &module.adts {
+            let adt = self.program.adts.get(adt_id);
+            let mut child = self.create_symbol(adt.name.clone(), SymbolKind::Enum, adt.location_id);
+            for variant_id in &adt.variants {
+                let variant = self.program.variants.get(variant_id);
+                let variant_symbol = self.create_symbol(
+                    variant.name.clone(),
+                    SymbolKind::Variant,
+                    variant.location_id,
+                );
+                child.children.push(variant_symbol);
+            }
+            symbol.children.push(child);
+        }
+        for record_id in &module.records {
+            if message_records.contains(record_id) {
+                continue;
+            }
+            let record = self.program.records.get(record_id);
+            let mut child =
+                self.create_symbol(record.name.clone(), SymbolKind::Record, record.location_id);
+            for field_id in &record.fields {
+                let field = self.program.record_fields.get(field_id);
+                let field_symbol =
+                    self.create_symbol(field.name.clone(), SymbolKind::Field, field.location_id);
+                child.children.push(field_symbol);
+            }
+            symbol.children.push(child);
+        }
+        for class_id in &module.classes {
+            let class = self.program.classes.get(class_id);
+            let mut child =
+                self.create_symbol(class.name.clone(), SymbolKind::Class, class.location_id);
+            child.children = self.get_function_symbols(&class.member_functions);
+            symbol.children.push(child);
+        }
+        for instance_id in &module.instances {
+            let instance = self.program.instances.get(instance_id);
+            let name = match &instance.name {
+                Some(name) => name.clone(),
+                None => self.get_instance_name(instance.location_id),
+            };
+            let mut child = self.create_symbol(name, SymbolKind::Instance, instance.location_id);
+            child.children = self.get_function_symbols(&instance.member_functions);
+            symbol.children.push(child);
+        }
+        symbol
+            .children
+            .extend(self.get_function_symbols(&module.functions));
+        symbol.children.sort_by_key(|child| child.range.start);
+        symbol
+    }
+}
+
+// Symbols only need the syntax tree, so the document is parsed on its own and
+// symbols are available even if the rest of the program does not typecheck.
+pub fn get_document_symbols(file_path: &FilePath, content: &str) -> Vec<Symbol> {
+    let mut compiler = Compiler::new(Config::new());
+    let input = CompilerInput::Memory {
+        name: file_path.path.clone(),
+        content: content.to_string(),
+    };
+    let program = match compiler.parse(vec![input]) {
+        Ok(program) => program,
+        Err(_) => return Vec::new(),
+    };
+    let collector = SymbolCollector {
+        program: &program,
+        location_info: compiler.get_location_info(),
+        lines: content.lines().collect(),
+    };
+    program
+        .modules
+        .items
+        .values()
+        .map(|module| collector.get_module_symbol(module))
+        .collect()
+}
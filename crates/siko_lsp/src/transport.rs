@@ -0,0 +1,54 @@
+use serde_json::Value;
+use std::io::BufRead;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::io::Write;
+
+// Messages are framed by a Content-Length header followed by an empty line,
+// other headers are ignored.
+pub fn read_message(reader: &mut dyn BufRead) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(length) = line.strip_prefix("Content-Length:") {
+            let length = length
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            content_length = Some(length);
+        }
+    }
+    let content_length = match content_length {
+        Some(length) => length,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Missing Content-Length header",
+            ))
+        }
+    };
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    let message =
+        serde_json::from_slice(&content).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    Ok(Some(message))
+}
+
+pub fn write_message(writer: &mut dyn Write, message: &Value) -> Result<()> {
+    let content = message.to_string();
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()
+}
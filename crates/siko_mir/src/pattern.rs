@@ -35,5 +35,8 @@ pub enum Pattern {
     IntegerLiteral(i64),
     CharLiteral(char),
     CharRange(char, char, RangeKind),
+    IntegerRange(i64, i64, RangeKind),
     StringLiteral(String),
+    List(Vec<PatternId>),
+    Cons(PatternId, PatternId),
 }
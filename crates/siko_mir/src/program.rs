@@ -14,6 +14,7 @@ use siko_location_info::item::ItemInfo;
 use siko_location_info::location_id::LocationId;
 use siko_util::ItemContainer;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 pub struct Program {
     pub exprs: ItemContainer<ExprId, ItemInfo<Expr>>,
@@ -24,6 +25,8 @@ pub struct Program {
     pub typedefs: ItemContainer<TypeDefId, TypeDef>,
     pub closures: BTreeMap<Type, Closure>,
     pub partial_function_calls: ItemContainer<PartialFunctionCallId, PartialFunctionCall>,
+    pub actors: BTreeMap<TypeDefId, BTreeMap<TypeDefId, FunctionId>>,
+    pub protocols: BTreeSet<TypeDefId>,
 }
 
 impl Program {
@@ -37,6 +40,8 @@ impl Program {
             typedefs: ItemContainer::new(),
             closures: BTreeMap::new(),
             partial_function_calls: ItemContainer::new(),
+            actors: BTreeMap::new(),
+            protocols: BTreeSet::new(),
         }
     }
 
@@ -90,11 +90,24 @@ pub fn walk_expr(expr_id: &ExprId, visitor: &mut dyn Visitor) {
         Expr::Return(inner) => {
             walk_expr(inner, visitor);
         }
+        Expr::Loop(pattern, initializer, items) => {
+            walk_expr(initializer, visitor);
+            walk_pattern(pattern, visitor);
+            for item in items {
+                walk_expr(item, visitor);
+            }
+        }
+        Expr::Continue(inner) => {
+            walk_expr(inner, visitor);
+        }
+        Expr::Break(inner) => {
+            walk_expr(inner, visitor);
+        }
     }
     visitor.visit_expr(*expr_id, expr);
 }
 
-fn walk_pattern(pattern_id: &PatternId, visitor: &mut dyn Visitor) {
+pub fn walk_pattern(pattern_id: &PatternId, visitor: &mut dyn Visitor) {
     let program = visitor.get_program();
     let pattern = &program.patterns.get(pattern_id).item.clone(); // FIXME, reorganize stuff to remove this clone
     match pattern {
@@ -118,6 +131,16 @@ fn walk_pattern(pattern_id: &PatternId, visitor: &mut dyn Visitor) {
         Pattern::StringLiteral(_) => {}
         Pattern::CharLiteral(_) => {}
         Pattern::CharRange(_, _, _) => {}
+        Pattern::IntegerRange(_, _, _) => {}
+        Pattern::List(items) => {
+            for item in items {
+                walk_pattern(item, visitor);
+            }
+        }
+        Pattern::Cons(head, tail) => {
+            walk_pattern(head, visitor);
+            walk_pattern(tail, visitor);
+        }
     }
     visitor.visit_pattern(*pattern_id, pattern);
 }
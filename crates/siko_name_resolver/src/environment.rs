@@ -4,10 +4,11 @@ use siko_ir::function::FunctionId;
 use siko_ir::pattern::PatternId;
 use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum NamedRef {
     FunctionArg(FunctionArgumentRef),
     ExprValue(ExprId, PatternId),
+    LocalFunction(FunctionId, Vec<(NamedRef, usize)>),
 }
 
 pub struct Environment<'a> {
@@ -37,6 +38,23 @@ impl<'a> Environment<'a> {
         );
     }
 
+    pub fn add_captured_arg(&mut self, var: String, function_id: FunctionId, index: usize) {
+        self.variables.insert(
+            var,
+            NamedRef::FunctionArg(FunctionArgumentRef::new(true, function_id, index)),
+        );
+    }
+
+    pub fn add_local_function(
+        &mut self,
+        var: String,
+        function_id: FunctionId,
+        captures: Vec<(NamedRef, usize)>,
+    ) {
+        self.variables
+            .insert(var, NamedRef::LocalFunction(function_id, captures));
+    }
+
     pub fn get_ref(&self, var: &str) -> Option<(NamedRef, usize)> {
         if let Some(named_ref) = self.variables.get(var) {
             return Some((named_ref.clone(), self.level));
@@ -49,6 +67,13 @@ impl<'a> Environment<'a> {
         }
     }
 
+    pub fn collect_names(&self, names: &mut Vec<String>) {
+        names.extend(self.variables.keys().cloned());
+        if let Some(parent) = self.parent {
+            parent.collect_names(names);
+        }
+    }
+
     pub fn child(parent: &'a Environment<'a>) -> Environment<'a> {
         Environment {
             variables: BTreeMap::new(),
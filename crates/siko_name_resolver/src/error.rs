@@ -6,13 +6,13 @@ use std::collections::BTreeSet;
 pub enum ResolverError {
     ModuleConflict(BTreeMap<String, BTreeSet<LocationId>>),
     InternalModuleConflicts(String, String, Vec<LocationId>),
-    ImportedModuleNotFound(String, LocationId),
-    UnknownTypeName(String, LocationId),
+    ImportedModuleNotFound(String, Vec<String>, LocationId),
+    UnknownTypeName(String, Vec<String>, LocationId),
     UnknownTypeArg(String, LocationId),
     TypeArgumentConflict(Vec<String>, LocationId),
     ArgumentConflict(Vec<String>, LocationId),
     LambdaArgumentConflict(Vec<String>, LocationId),
-    UnknownFunction(String, LocationId),
+    UnknownFunction(String, Vec<String>, LocationId),
     AmbiguousName(String, LocationId),
     UnusedTypeArgument(String, LocationId),
     RecordFieldNotUnique(String, String, LocationId),
@@ -22,7 +22,7 @@ pub enum ResolverError {
     IncorrectTypeArgumentCount(String, usize, usize, LocationId),
     NameNotType(String, LocationId),
     UnusedHiddenItem(String, String, LocationId),
-    UnknownFieldName(String, LocationId),
+    UnknownFieldName(String, Vec<String>, LocationId),
     NotIrrefutablePattern(LocationId),
     NotRecordType(String, LocationId),
     NoSuchField(String, String, LocationId),
@@ -49,6 +49,15 @@ pub enum ResolverError {
     PatternBindNotPresent(String, LocationId),
     ContinueOutsideLoop(LocationId),
     BreakOutsideLoop(LocationId),
+    NotAProtocolName(String, LocationId),
+    ConflictingProtocolHandler(String, String, LocationId),
+    KindMismatch(String, usize, usize, LocationId),
+    InvalidInstanceKind(String, usize, LocationId),
+    ConflictingLocalFunction(String, Vec<LocationId>),
+    RecursiveLocalValue(Vec<String>, LocationId),
+    IncorrectClassArgumentCount(String, usize, usize, LocationId),
+    MultiParamConstraintNotAllowed(String, LocationId),
+    InstanceMemberTypeMismatch(String, String, String, LocationId),
 }
 
 #[derive(Debug)]
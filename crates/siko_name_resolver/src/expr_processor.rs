@@ -1,6 +1,7 @@
 use crate::environment::Environment;
 use crate::environment::NamedRef;
 use crate::error::ResolverError;
+use crate::free_var_collector::collect_free_vars;
 use crate::import::ImportedItemInfo;
 use crate::import::Namespace;
 use crate::item::DataMember;
@@ -8,6 +9,9 @@ use crate::item::Item;
 use crate::lambda_helper::LambdaHelper;
 use crate::loop_helper::LoopHelper;
 use crate::module::Module;
+use crate::suggestion::get_field_suggestions;
+use crate::suggestion::get_item_suggestions;
+use crate::suggestion::get_suggestions;
 use crate::type_arg_resolver::TypeArgResolver;
 use crate::type_processor::process_type_signature;
 use siko_constants::BuiltinOperator;
@@ -17,12 +21,15 @@ use siko_ir::expr::Case as IrCase;
 use siko_ir::expr::Expr as IrExpr;
 use siko_ir::expr::ExprId as IrExprId;
 use siko_ir::expr::FieldAccessInfo;
+use siko_ir::expr::FunctionArgumentRef;
+use siko_ir::expr::HoleInfo;
 use siko_ir::expr::RecordFieldValueExpr;
 use siko_ir::expr::RecordUpdateInfo;
 use siko_ir::function::Function as IrFunction;
 use siko_ir::function::FunctionId as IrFunctionId;
 use siko_ir::function::FunctionInfo;
 use siko_ir::function::LambdaInfo;
+use siko_ir::function::LocalFunctionInfo;
 use siko_ir::pattern::BindGroup;
 use siko_ir::pattern::Pattern as IrPattern;
 use siko_ir::pattern::PatternId as IrPatternId;
@@ -32,6 +39,8 @@ use siko_location_info::item::ItemInfo;
 use siko_location_info::location_id::LocationId;
 use siko_syntax::expr::Expr;
 use siko_syntax::expr::ExprId;
+use siko_syntax::function::FunctionBody;
+use siko_syntax::function::FunctionId;
 use siko_syntax::pattern::Pattern;
 use siko_syntax::pattern::PatternId;
 use siko_syntax::pattern::RangeKind;
@@ -42,6 +51,7 @@ use std::collections::BTreeSet;
 enum PathResolveResult {
     VariableRef(IrExprId),
     FunctionRef(IrFunctionId),
+    LocalFunctionRef(IrFunctionId, Vec<IrExprId>),
     ClassMemberRef(IrClassMemberId),
 }
 
@@ -57,6 +67,16 @@ fn resolve_item_path(
     location_id: LocationId,
 ) -> PathResolveResult {
     if let Some((named_ref, level)) = environment.get_ref(path) {
+        if let NamedRef::LocalFunction(ir_function_id, captures) = named_ref {
+            let captured_args: Vec<_> = captures
+                .into_iter()
+                .map(|(named_ref, level)| {
+                    let ir_expr = lambda_helper.process_named_ref(named_ref, level);
+                    add_expr(ir_expr, id, ir_program, program)
+                })
+                .collect();
+            return PathResolveResult::LocalFunctionRef(ir_function_id, captured_args);
+        }
         let ir_expr = lambda_helper.process_named_ref(named_ref.clone(), level);
         let ir_expr_id = add_expr(ir_expr, id, ir_program, program);
         return PathResolveResult::VariableRef(ir_expr_id);
@@ -89,13 +109,90 @@ fn resolve_item_path(
             },
         }
     }
-    let err = ResolverError::UnknownFunction(path.to_string(), location_id);
+    let mut candidates = Vec::new();
+    environment.collect_names(&mut candidates);
+    let mut suggestions = get_item_suggestions(path, module, Item::is_value_level);
+    suggestions.extend(get_suggestions(path, candidates.iter()));
+    suggestions.sort();
+    suggestions.dedup();
+    let err = ResolverError::UnknownFunction(path.to_string(), suggestions, location_id);
     errors.push(err);
     let ir_expr = IrExpr::Tuple(vec![]);
     let ir_expr_id = add_expr(ir_expr, id, ir_program, program);
     return PathResolveResult::VariableRef(ir_expr_id);
 }
 
+fn process_path(
+    path: &str,
+    module: &Module,
+    environment: &Environment,
+    lambda_helper: LambdaHelper,
+    program: &Program,
+    ir_program: &mut IrProgram,
+    id: ExprId,
+    errors: &mut Vec<ResolverError>,
+    location_id: LocationId,
+) -> IrExprId {
+    match resolve_item_path(
+        path,
+        module,
+        environment,
+        lambda_helper,
+        program,
+        ir_program,
+        id,
+        errors,
+        location_id,
+    ) {
+        PathResolveResult::FunctionRef(n) => {
+            let ir_expr = IrExpr::StaticFunctionCall(n, vec![]);
+            add_expr(ir_expr, id, ir_program, program)
+        }
+        PathResolveResult::LocalFunctionRef(n, captured_args) => {
+            let ir_expr = IrExpr::StaticFunctionCall(n, captured_args);
+            add_expr(ir_expr, id, ir_program, program)
+        }
+        PathResolveResult::VariableRef(ir_expr_id) => ir_expr_id,
+        PathResolveResult::ClassMemberRef(n) => {
+            let ir_expr = IrExpr::ClassFunctionCall(n, vec![]);
+            add_expr(ir_expr, id, ir_program, program)
+        }
+    }
+}
+
+// Every imported function and constructor, under the shortest name it is visible with.
+fn collect_hole_candidates(module: &Module, ir_program: &IrProgram) -> Vec<(String, IrFunctionId)> {
+    let mut candidates: BTreeMap<IrFunctionId, String> = BTreeMap::new();
+    for (name, items) in &module.imported_items {
+        for item in items {
+            let ir_function_id = match item.item {
+                Item::Function(_, ir_function_id) => ir_function_id,
+                Item::Record(_, ir_typedef_id) => {
+                    let ir_record = ir_program.typedefs.get(&ir_typedef_id).get_record();
+                    if ir_record.external {
+                        continue;
+                    }
+                    ir_record.constructor
+                }
+                Item::Variant(_, _, ir_typedef_id, index) => {
+                    ir_program.typedefs.get(&ir_typedef_id).get_adt().variants[index].constructor
+                }
+                _ => continue,
+            };
+            let shortest = candidates
+                .entry(ir_function_id)
+                .or_insert_with(|| name.clone());
+            if name.len() < shortest.len() {
+                *shortest = name.clone();
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .map(|(ir_function_id, name)| (name, ir_function_id))
+        .collect()
+}
+
 fn add_expr(
     ir_expr: IrExpr,
     ast_id: ExprId,
@@ -132,7 +229,8 @@ fn process_field_access(
             }
         }
         None => {
-            let err = ResolverError::UnknownFieldName(name.clone(), location_id);
+            let suggestions = get_field_suggestions(&name, module);
+            let err = ResolverError::UnknownFieldName(name.clone(), suggestions, location_id);
             errors.push(err);
         }
     }
@@ -172,7 +270,11 @@ fn resolve_pattern_type_constructor(
             },
         }
     };
-    let err = ResolverError::UnknownTypeName(name.to_string(), location_id);
+    let suggestions = get_item_suggestions(name, module, |item| match item {
+        Item::Record(..) | Item::Variant(..) => true,
+        _ => false,
+    });
+    let err = ResolverError::UnknownTypeName(name.to_string(), suggestions, location_id);
     errors.push(err);
     return IrPattern::Wildcard;
 }
@@ -204,7 +306,11 @@ fn resolve_record_type(
             },
         }
     };
-    let err = ResolverError::UnknownTypeName(name.to_string(), location_id);
+    let suggestions = get_item_suggestions(name, module, |item| match item {
+        Item::Record(..) => true,
+        _ => false,
+    });
+    let err = ResolverError::UnknownTypeName(name.to_string(), suggestions, location_id);
     errors.push(err);
     return None;
 }
@@ -447,6 +553,75 @@ fn process_pattern(
                 IrPattern::CharRange(*start, *end, ir_kind)
             }
         }
+        Pattern::IntegerRange(start, end, kind) => {
+            if irrefutable {
+                let err = ResolverError::NotIrrefutablePattern(location_id);
+                errors.push(err);
+                IrPattern::Wildcard
+            } else {
+                let ir_kind = match kind {
+                    RangeKind::Exclusive => IrRangeKind::Exclusive,
+                    RangeKind::Inclusive => IrRangeKind::Inclusive,
+                };
+                IrPattern::IntegerRange(*start, *end, ir_kind)
+            }
+        }
+        Pattern::List(patterns) => {
+            let ids: Vec<_> = patterns
+                .iter()
+                .map(|id| {
+                    process_pattern(
+                        case_expr_id,
+                        *id,
+                        program,
+                        ir_program,
+                        module,
+                        environment,
+                        bindings,
+                        errors,
+                        lambda_helper.clone(),
+                        irrefutable,
+                        type_arg_resolver,
+                        loop_helper.clone(),
+                    )
+                })
+                .collect();
+            if irrefutable {
+                let err = ResolverError::NotIrrefutablePattern(location_id);
+                errors.push(err);
+                IrPattern::Wildcard
+            } else {
+                IrPattern::List(ids)
+            }
+        }
+        Pattern::Cons(head, tail) => {
+            let ids: Vec<_> = [head, tail]
+                .iter()
+                .map(|id| {
+                    process_pattern(
+                        case_expr_id,
+                        **id,
+                        program,
+                        ir_program,
+                        module,
+                        environment,
+                        bindings,
+                        errors,
+                        lambda_helper.clone(),
+                        irrefutable,
+                        type_arg_resolver,
+                        loop_helper.clone(),
+                    )
+                })
+                .collect();
+            if irrefutable {
+                let err = ResolverError::NotIrrefutablePattern(location_id);
+                errors.push(err);
+                IrPattern::Wildcard
+            } else {
+                IrPattern::Cons(ids[0], ids[1])
+            }
+        }
     };
     let ir_pattern_info = ItemInfo {
         item: ir_pattern,
@@ -456,6 +631,364 @@ fn process_pattern(
     ir_pattern_id
 }
 
+// A captured variable of a local function, the let bound values are not
+// processed yet when the captures are calculated.
+#[derive(Clone, PartialEq)]
+enum Capture {
+    Value(String),
+    Outer(NamedRef, usize),
+}
+
+fn add_capture(captures: &mut Vec<Capture>, capture: Capture) -> bool {
+    if captures.contains(&capture) {
+        false
+    } else {
+        captures.push(capture);
+        true
+    }
+}
+
+fn resolve_captures(
+    captures: &[Capture],
+    value_refs: &BTreeMap<String, (NamedRef, usize)>,
+) -> Option<Vec<(NamedRef, usize)>> {
+    let mut resolved = Vec::new();
+    for capture in captures {
+        match capture {
+            Capture::Value(name) => resolved.push(value_refs.get(name)?.clone()),
+            Capture::Outer(named_ref, level) => resolved.push((named_ref.clone(), *level)),
+        }
+    }
+    Some(resolved)
+}
+
+fn is_recursive_value(
+    name: &String,
+    current: &String,
+    value_deps: &BTreeMap<String, BTreeSet<String>>,
+    visited: &mut BTreeSet<String>,
+) -> bool {
+    if !visited.insert(current.clone()) {
+        return false;
+    }
+    let deps = value_deps.get(current).expect("Value deps not found");
+    deps.contains(name)
+        || deps
+            .iter()
+            .any(|dep| is_recursive_value(name, dep, value_deps, visited))
+}
+
+// Local functions are lifted into standalone functions that receive their
+// captured variables as extra leading arguments, local values are bound once
+// before the body in the order of their dependencies.
+fn process_let(
+    id: ExprId,
+    functions: &Vec<FunctionId>,
+    body: ExprId,
+    program: &Program,
+    module: &Module,
+    environment: &mut Environment,
+    ir_program: &mut IrProgram,
+    errors: &mut Vec<ResolverError>,
+    lambda_helper: LambdaHelper,
+    type_arg_resolver: &mut TypeArgResolver,
+    loop_helper: Option<LoopHelper>,
+) -> IrExprId {
+    let mut locations: BTreeMap<String, Vec<LocationId>> = BTreeMap::new();
+    for function_id in functions {
+        let function = program.functions.get(function_id);
+        let function_locations = locations
+            .entry(function.name.clone())
+            .or_insert_with(|| Vec::new());
+        function_locations.push(function.location_id);
+    }
+    let mut conflict = false;
+    for (name, locations) in locations {
+        if locations.len() > 1 {
+            let err = ResolverError::ConflictingLocalFunction(name, locations);
+            errors.push(err);
+            conflict = true;
+        }
+    }
+    if conflict {
+        let ir_expr = IrExpr::Tuple(vec![]);
+        return add_expr(ir_expr, id, ir_program, program);
+    }
+
+    let mut values = Vec::new();
+    let mut local_functions = Vec::new();
+    let mut free_vars = BTreeMap::new();
+    for function_id in functions {
+        let function = program.functions.get(function_id);
+        let function_body = match function.body {
+            FunctionBody::Expr(function_body) => function_body,
+            FunctionBody::Extern => unreachable!(),
+        };
+        let bound: BTreeSet<_> = function.args.iter().map(|arg| arg.0.clone()).collect();
+        let mut free = BTreeSet::new();
+        collect_free_vars(function_body, program, &bound, &mut free);
+        free_vars.insert(function.name.clone(), free);
+        if function.args.is_empty() {
+            values.push((function, function_body));
+        } else {
+            local_functions.push((function, function_body, ir_program.functions.get_id()));
+        }
+    }
+    let value_names: BTreeSet<_> = values.iter().map(|(f, _)| f.name.clone()).collect();
+    let function_names: BTreeSet<_> = local_functions
+        .iter()
+        .map(|(f, _, _)| f.name.clone())
+        .collect();
+
+    let mut captures: BTreeMap<String, Vec<Capture>> = BTreeMap::new();
+    for (function, _, _) in &local_functions {
+        let mut function_captures = Vec::new();
+        for name in &free_vars[&function.name] {
+            if value_names.contains(name) {
+                add_capture(&mut function_captures, Capture::Value(name.clone()));
+            } else if function_names.contains(name) {
+                continue;
+            } else if let Some((named_ref, level)) = environment.get_ref(name) {
+                if let NamedRef::LocalFunction(_, outer_captures) = named_ref {
+                    for (named_ref, level) in outer_captures {
+                        add_capture(&mut function_captures, Capture::Outer(named_ref, level));
+                    }
+                } else {
+                    add_capture(&mut function_captures, Capture::Outer(named_ref, level));
+                }
+            }
+        }
+        captures.insert(function.name.clone(), function_captures);
+    }
+    // a local function also captures everything that the local functions it uses capture
+    loop {
+        let mut changed = false;
+        for (function, _, _) in &local_functions {
+            for name in &free_vars[&function.name] {
+                if function_names.contains(name) && *name != function.name {
+                    let used_captures = captures[name].clone();
+                    let function_captures = captures
+                        .get_mut(&function.name)
+                        .expect("Captures not found");
+                    for capture in used_captures {
+                        if add_capture(function_captures, capture) {
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut value_deps = BTreeMap::new();
+    for (value, _) in &values {
+        let mut deps = BTreeSet::new();
+        for name in &free_vars[&value.name] {
+            if value_names.contains(name) {
+                deps.insert(name.clone());
+            } else if function_names.contains(name) {
+                for capture in &captures[name] {
+                    if let Capture::Value(dep) = capture {
+                        deps.insert(dep.clone());
+                    }
+                }
+            }
+        }
+        value_deps.insert(value.name.clone(), deps);
+    }
+    let recursive_values: Vec<_> = values
+        .iter()
+        .filter(|(value, _)| {
+            is_recursive_value(&value.name, &value.name, &value_deps, &mut BTreeSet::new())
+        })
+        .collect();
+    if let Some((first, _)) = recursive_values.first() {
+        let names = recursive_values
+            .iter()
+            .map(|(value, _)| value.name.clone())
+            .collect();
+        let err = ResolverError::RecursiveLocalValue(names, first.location_id);
+        errors.push(err);
+        let ir_expr = IrExpr::Tuple(vec![]);
+        return add_expr(ir_expr, id, ir_program, program);
+    }
+
+    let mut let_environment = Environment::child(environment);
+    let mut value_refs = BTreeMap::new();
+    let mut ir_items = Vec::new();
+    let mut pending_values = values;
+    while !pending_values.is_empty() {
+        let index = pending_values
+            .iter()
+            .position(|(value, _)| {
+                value_deps[&value.name]
+                    .iter()
+                    .all(|dep| value_refs.contains_key(dep))
+            })
+            .expect("No value to bind");
+        let (value, value_body) = pending_values.remove(index);
+        for (function, _, ir_function_id) in &local_functions {
+            if let Some(resolved) = resolve_captures(&captures[&function.name], &value_refs) {
+                let_environment.add_local_function(
+                    function.name.clone(),
+                    *ir_function_id,
+                    resolved,
+                );
+            }
+        }
+        let ir_value_id = process_expr(
+            value_body,
+            program,
+            module,
+            &mut let_environment,
+            ir_program,
+            errors,
+            lambda_helper.clone(),
+            type_arg_resolver,
+            loop_helper.clone(),
+        );
+        let ir_pattern_id = ir_program.patterns.get_id();
+        let ir_pattern_info = ItemInfo {
+            item: IrPattern::Binding(value.name.clone()),
+            location_id: value.location_id,
+        };
+        ir_program.patterns.add_item(ir_pattern_id, ir_pattern_info);
+        let_environment.add_expr_value(value.name.clone(), ir_value_id, ir_pattern_id);
+        value_refs.insert(
+            value.name.clone(),
+            (
+                NamedRef::ExprValue(ir_value_id, ir_pattern_id),
+                let_environment.level(),
+            ),
+        );
+        let ir_expr = IrExpr::Bind(ir_pattern_id, ir_value_id);
+        ir_items.push(add_expr(ir_expr, value_body, ir_program, program));
+    }
+
+    let mut resolved_captures = BTreeMap::new();
+    for (function, _, ir_function_id) in &local_functions {
+        let resolved =
+            resolve_captures(&captures[&function.name], &value_refs).expect("Unresolved capture");
+        let_environment.add_local_function(
+            function.name.clone(),
+            *ir_function_id,
+            resolved.clone(),
+        );
+        resolved_captures.insert(function.name.clone(), resolved);
+    }
+
+    for (function, function_body, ir_function_id) in &local_functions {
+        let function_captures = &resolved_captures[&function.name];
+        let mut function_environment = Environment::child(&let_environment);
+        let level = function_environment.level();
+        let captured_arg = |named_ref: &NamedRef| {
+            let index = function_captures
+                .iter()
+                .position(|(captured, _)| captured == named_ref)
+                .expect("Captured variable not found");
+            NamedRef::FunctionArg(FunctionArgumentRef::new(true, *ir_function_id, index))
+        };
+        for name in &free_vars[&function.name] {
+            match let_environment.get_ref(name) {
+                Some((NamedRef::LocalFunction(used_function_id, used_captures), _)) => {
+                    let used_captures = used_captures
+                        .iter()
+                        .map(|(named_ref, _)| (captured_arg(named_ref), level))
+                        .collect();
+                    function_environment.add_local_function(
+                        name.clone(),
+                        used_function_id,
+                        used_captures,
+                    );
+                }
+                Some((named_ref, _)) => {
+                    if let NamedRef::FunctionArg(arg_ref) = captured_arg(&named_ref) {
+                        function_environment.add_captured_arg(
+                            name.clone(),
+                            arg_ref.id,
+                            arg_ref.index,
+                        );
+                    }
+                }
+                None => {}
+            }
+        }
+        let mut arg_names = BTreeSet::new();
+        let mut conflicting_names = BTreeSet::new();
+        for (index, arg) in function.args.iter().enumerate() {
+            if !arg_names.insert(arg.0.clone()) {
+                conflicting_names.insert(arg.0.clone());
+            }
+            function_environment.add_arg(arg.0.clone(), *ir_function_id, index);
+        }
+        if !conflicting_names.is_empty() {
+            let err = ResolverError::ArgumentConflict(
+                conflicting_names.into_iter().collect(),
+                function.location_id.clone(),
+            );
+            errors.push(err);
+        }
+        let host_info = lambda_helper.host_function_name();
+        let local_lambda_helper = LambdaHelper::new(
+            level,
+            format!("{}/{}", host_info, function.name),
+            lambda_helper.clone_counter(),
+            *ir_function_id,
+            *ir_function_id,
+            None,
+        );
+        let ir_body = process_expr(
+            *function_body,
+            program,
+            module,
+            &mut function_environment,
+            ir_program,
+            errors,
+            local_lambda_helper.clone(),
+            type_arg_resolver,
+            None,
+        );
+        assert!(local_lambda_helper.captures().is_empty());
+        let local_function_info = LocalFunctionInfo {
+            body: ir_body,
+            module: module.name.clone(),
+            name: function.name.clone(),
+            host_info: host_info,
+            location_id: function.location_id,
+        };
+        let arg_locations: Vec<_> = function.args.iter().map(|arg| arg.1).collect();
+        let ir_function = IrFunction {
+            id: *ir_function_id,
+            arg_count: arg_locations.len() + function_captures.len(),
+            arg_locations: arg_locations,
+            info: FunctionInfo::LocalFunction(local_function_info),
+        };
+        ir_program.functions.add_item(*ir_function_id, ir_function);
+    }
+
+    let ir_body = process_expr(
+        body,
+        program,
+        module,
+        &mut let_environment,
+        ir_program,
+        errors,
+        lambda_helper,
+        type_arg_resolver,
+        loop_helper,
+    );
+    if ir_items.is_empty() {
+        ir_body
+    } else {
+        ir_items.push(ir_body);
+        let ir_expr = IrExpr::Do(ir_items);
+        add_expr(ir_expr, id, ir_program, program)
+    }
+}
+
 pub fn process_expr(
     id: ExprId,
     program: &Program,
@@ -573,6 +1106,11 @@ pub fn process_expr(
                         let ir_expr = IrExpr::StaticFunctionCall(n, ir_args);
                         return add_expr(ir_expr, id, ir_program, program);
                     }
+                    PathResolveResult::LocalFunctionRef(n, mut captured_args) => {
+                        captured_args.extend(ir_args);
+                        let ir_expr = IrExpr::StaticFunctionCall(n, captured_args);
+                        return add_expr(ir_expr, id, ir_program, program);
+                    }
                     PathResolveResult::VariableRef(ir_id_expr_id) => {
                         let ir_expr = IrExpr::DynamicFunctionCall(ir_id_expr_id, ir_args);
                         return add_expr(ir_expr, id, ir_program, program);
@@ -720,7 +1258,7 @@ pub fn process_expr(
             return add_expr(ir_expr, id, ir_program, program);
         }
         Expr::Path(path) => {
-            match resolve_item_path(
+            return process_path(
                 path,
                 module,
                 environment,
@@ -730,19 +1268,40 @@ pub fn process_expr(
                 id,
                 errors,
                 location_id,
-            ) {
-                PathResolveResult::FunctionRef(n) => {
-                    let ir_expr = IrExpr::StaticFunctionCall(n, vec![]);
-                    return add_expr(ir_expr, id, ir_program, program);
-                }
-                PathResolveResult::VariableRef(ir_expr_id) => {
-                    return ir_expr_id;
-                }
-                PathResolveResult::ClassMemberRef(n) => {
-                    let ir_expr = IrExpr::ClassFunctionCall(n, vec![]);
-                    return add_expr(ir_expr, id, ir_program, program);
-                }
-            }
+            );
+        }
+        Expr::Hole(name) => {
+            let mut names = Vec::new();
+            environment.collect_names(&mut names);
+            // temporaries introduced by the parser start with $
+            let names: BTreeSet<_> = names
+                .into_iter()
+                .filter(|name| !name.starts_with('$'))
+                .collect();
+            let bindings = names
+                .into_iter()
+                .map(|name| {
+                    let binding = process_path(
+                        &name,
+                        module,
+                        environment,
+                        lambda_helper.clone(),
+                        program,
+                        ir_program,
+                        id,
+                        errors,
+                        location_id,
+                    );
+                    (name, binding)
+                })
+                .collect();
+            let hole_info = HoleInfo {
+                name: name.clone(),
+                bindings: bindings,
+                candidates: collect_hole_candidates(module, ir_program),
+            };
+            let ir_expr = IrExpr::Hole(hole_info);
+            return add_expr(ir_expr, id, ir_program, program);
         }
         Expr::IntegerLiteral(v) => {
             let ir_expr = IrExpr::IntegerLiteral(v.clone());
@@ -1090,6 +1649,10 @@ pub fn process_expr(
                     let ir_expr = IrExpr::StaticFunctionCall(n, vec![]);
                     add_expr(ir_expr, id, ir_program, program)
                 }
+                PathResolveResult::LocalFunctionRef(n, captured_args) => {
+                    let ir_expr = IrExpr::StaticFunctionCall(n, captured_args);
+                    add_expr(ir_expr, id, ir_program, program)
+                }
                 PathResolveResult::VariableRef(ir_expr_id) => ir_expr_id,
                 PathResolveResult::ClassMemberRef(n) => {
                     let ir_expr = IrExpr::ClassFunctionCall(n, vec![]);
@@ -1255,6 +1818,21 @@ pub fn process_expr(
             }
             return add_expr(ir_expr, id, ir_program, program);
         }
+        Expr::Let(functions, body) => {
+            return process_let(
+                id,
+                functions,
+                *body,
+                program,
+                module,
+                environment,
+                ir_program,
+                errors,
+                lambda_helper,
+                type_arg_resolver,
+                loop_helper,
+            );
+        }
         Expr::Break(inner_expr_id) => {
             let ir_inner_expr_id = process_expr(
                 *inner_expr_id,
@@ -0,0 +1,180 @@
+use siko_syntax::expr::Expr;
+use siko_syntax::expr::ExprId;
+use siko_syntax::function::FunctionBody;
+use siko_syntax::pattern::Pattern;
+use siko_syntax::pattern::PatternId;
+use siko_syntax::program::Program;
+use std::collections::BTreeSet;
+
+fn collect_pattern_bindings(
+    pattern_id: PatternId,
+    program: &Program,
+    bindings: &mut BTreeSet<String>,
+    guards: &mut Vec<ExprId>,
+) {
+    let pattern = &program.patterns.get(&pattern_id).item;
+    match pattern {
+        Pattern::Binding(name) => {
+            bindings.insert(name.clone());
+        }
+        Pattern::Tuple(items)
+        | Pattern::Constructor(_, items)
+        | Pattern::Or(items)
+        | Pattern::List(items) => {
+            for item in items {
+                collect_pattern_bindings(*item, program, bindings, guards);
+            }
+        }
+        Pattern::Guarded(pattern_id, guard) => {
+            collect_pattern_bindings(*pattern_id, program, bindings, guards);
+            guards.push(*guard);
+        }
+        Pattern::Typed(pattern_id, _) => {
+            collect_pattern_bindings(*pattern_id, program, bindings, guards);
+        }
+        Pattern::Cons(head, tail) => {
+            collect_pattern_bindings(*head, program, bindings, guards);
+            collect_pattern_bindings(*tail, program, bindings, guards);
+        }
+        Pattern::Record(_, items) => {
+            for item in items {
+                collect_pattern_bindings(item.value, program, bindings, guards);
+            }
+        }
+        Pattern::Wildcard
+        | Pattern::IntegerLiteral(_)
+        | Pattern::StringLiteral(_)
+        | Pattern::CharLiteral(_)
+        | Pattern::CharRange(..)
+        | Pattern::IntegerRange(..) => {}
+    }
+}
+
+fn bind_pattern(
+    pattern_id: PatternId,
+    program: &Program,
+    bound: &mut BTreeSet<String>,
+    free: &mut BTreeSet<String>,
+) {
+    let mut guards = Vec::new();
+    collect_pattern_bindings(pattern_id, program, bound, &mut guards);
+    for guard in guards {
+        collect_free_vars(guard, program, bound, free);
+    }
+}
+
+fn collect_block(
+    items: &[ExprId],
+    program: &Program,
+    bound: &BTreeSet<String>,
+    free: &mut BTreeSet<String>,
+) {
+    let mut bound = bound.clone();
+    for item in items {
+        if let Expr::Bind(pattern_id, expr_id) = &program.exprs.get(item).item {
+            collect_free_vars(*expr_id, program, &bound, free);
+            bind_pattern(*pattern_id, program, &mut bound, free);
+        } else {
+            collect_free_vars(*item, program, &bound, free);
+        }
+    }
+}
+
+// Collects the names used in an expression that are not bound inside of it.
+pub fn collect_free_vars(
+    expr_id: ExprId,
+    program: &Program,
+    bound: &BTreeSet<String>,
+    free: &mut BTreeSet<String>,
+) {
+    let expr = &program.exprs.get(&expr_id).item;
+    match expr {
+        Expr::Lambda(args, body) => {
+            let mut bound = bound.clone();
+            bound.extend(args.iter().map(|arg| arg.0.clone()));
+            collect_free_vars(*body, program, &bound, free);
+        }
+        Expr::FunctionCall(function, args) => {
+            collect_free_vars(*function, program, bound, free);
+            for arg in args {
+                collect_free_vars(*arg, program, bound, free);
+            }
+        }
+        Expr::Builtin(_) => {}
+        Expr::If(cond, true_branch, false_branch) => {
+            collect_free_vars(*cond, program, bound, free);
+            collect_free_vars(*true_branch, program, bound, free);
+            collect_free_vars(*false_branch, program, bound, free);
+        }
+        Expr::Tuple(items) | Expr::List(items) | Expr::Formatter(_, items) => {
+            for item in items {
+                collect_free_vars(*item, program, bound, free);
+            }
+        }
+        Expr::Path(name) => {
+            if !bound.contains(name) {
+                free.insert(name.clone());
+            }
+        }
+        Expr::IntegerLiteral(_)
+        | Expr::FloatLiteral(_)
+        | Expr::StringLiteral(_)
+        | Expr::CharLiteral(_)
+        | Expr::Hole(_) => {}
+        Expr::Do(items) => {
+            collect_block(items, program, bound, free);
+        }
+        Expr::Bind(_, expr_id) => {
+            collect_free_vars(*expr_id, program, bound, free);
+        }
+        Expr::FieldAccess(_, expr_id)
+        | Expr::TupleFieldAccess(_, expr_id)
+        | Expr::Return(expr_id)
+        | Expr::Continue(expr_id)
+        | Expr::Break(expr_id) => {
+            collect_free_vars(*expr_id, program, bound, free);
+        }
+        Expr::CaseOf(body, cases) => {
+            collect_free_vars(*body, program, bound, free);
+            for case in cases {
+                let mut bound = bound.clone();
+                bind_pattern(case.pattern_id, program, &mut bound, free);
+                collect_free_vars(case.body, program, &bound, free);
+            }
+        }
+        Expr::RecordInitialization(_, items) => {
+            for item in items {
+                collect_free_vars(item.body, program, bound, free);
+            }
+        }
+        Expr::RecordUpdate(name, items) => {
+            if !bound.contains(name) {
+                free.insert(name.clone());
+            }
+            for item in items {
+                collect_free_vars(item.body, program, bound, free);
+            }
+        }
+        Expr::Loop(pattern_id, initializer, items) => {
+            collect_free_vars(*initializer, program, bound, free);
+            let mut bound = bound.clone();
+            bind_pattern(*pattern_id, program, &mut bound, free);
+            collect_block(items, program, &bound, free);
+        }
+        Expr::Let(functions, body) => {
+            let mut bound = bound.clone();
+            for function_id in functions {
+                bound.insert(program.functions.get(function_id).name.clone());
+            }
+            for function_id in functions {
+                let function = program.functions.get(function_id);
+                if let FunctionBody::Expr(function_body) = function.body {
+                    let mut bound = bound.clone();
+                    bound.extend(function.args.iter().map(|arg| arg.0.clone()));
+                    collect_free_vars(function_body, program, &bound, free);
+                }
+            }
+            collect_free_vars(*body, program, &bound, free);
+        }
+    }
+}
@@ -25,6 +25,7 @@ impl LambdaHelperInner {
             match r {
                 NamedRef::ExprValue(expr_ref, pattern_id) => Expr::ExprValue(expr_ref, pattern_id),
                 NamedRef::FunctionArg(arg_ref) => Expr::ArgRef(arg_ref),
+                NamedRef::LocalFunction(..) => panic!("Local function captured"),
             }
         };
         if level < self.level {
@@ -3,6 +3,7 @@ pub mod error;
 pub mod export_import_pattern;
 pub mod export_processor;
 pub mod expr_processor;
+pub mod free_var_collector;
 pub mod import;
 pub mod import_processor;
 pub mod item;
@@ -11,5 +12,6 @@ pub mod lambda_helper;
 pub mod loop_helper;
 pub mod module;
 pub mod resolver;
+pub mod suggestion;
 pub mod type_arg_resolver;
 pub mod type_processor;
@@ -7,7 +7,7 @@ use siko_syntax::module::ModuleId;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Module {
     pub id: ModuleId,
     pub name: String,
@@ -6,6 +6,7 @@ use crate::expr_processor::process_expr;
 use crate::import::ImportedItemInfo;
 use crate::import::Namespace;
 use crate::import_processor::process_imports;
+use crate::import_processor::process_module_imports;
 use crate::item::DataMember;
 use crate::item::Item;
 use crate::item::RecordField;
@@ -13,18 +14,33 @@ use crate::item::Variant;
 use crate::lambda_arg_shifter::LambdaArgShifter;
 use crate::lambda_helper::LambdaHelper;
 use crate::module::Module;
+use crate::suggestion::get_item_suggestions;
 use crate::type_arg_resolver::TypeArgResolver;
 use crate::type_processor::collect_type_args;
+use crate::type_processor::format_type_signature;
+use crate::type_processor::get_type_arg_arity;
 use crate::type_processor::process_class_type_signature;
+use crate::type_processor::process_instance_type_signature;
+use crate::type_processor::process_multi_param_constraint;
 use crate::type_processor::process_type_signature;
 use crate::type_processor::subtitute_type_signature;
+use crate::type_processor::type_signatures_match;
 use siko_constants::get_auto_derivable_classes;
+use siko_constants::ACTOR_CLASS_NAME;
+use siko_constants::PROTOCOL_CLASS_NAME;
+use siko_constants::STD_ACTOR_MODULE_NAME;
 use siko_constants::STD_OPS_MODULE_NAME;
+use siko_ir::actor::Actor as IrActor;
+use siko_ir::actor::Protocol as IrProtocol;
+use siko_ir::actor::ProtocolHandler as IrProtocolHandler;
+use siko_ir::actor::ProtocolId as IrProtocolId;
 use siko_ir::class::Class as IrClass;
 use siko_ir::class::ClassId as IrClassId;
 use siko_ir::class::ClassMember as IrClassMember;
+use siko_ir::class::FunctionalDependency as IrFunctionalDependency;
 use siko_ir::class::Instance as IrInstance;
 use siko_ir::class::InstanceMember as IrInstanceMember;
+use siko_ir::class::MultiParamConstraint as IrMultiParamConstraint;
 use siko_ir::data::Adt;
 use siko_ir::data::DerivedClass as IrDerivedClass;
 use siko_ir::data::Record;
@@ -45,8 +61,11 @@ use siko_ir::type_signature::TypeSignature;
 use siko_ir::type_signature::TypeSignatureId;
 use siko_ir::type_var_generator::TypeVarGenerator;
 use siko_ir::walker::walk_expr;
+use siko_location_info::item::ItemInfo;
 use siko_location_info::location_id::LocationId;
+use siko_syntax::actor::ProtocolHandler as AstProtocolHandler;
 use siko_syntax::class::ClassId as AstClassId;
+use siko_syntax::class::Constraint as AstConstraint;
 use siko_syntax::class::Instance as AstInstance;
 use siko_syntax::data::AdtId;
 use siko_syntax::data::DerivedClass as AstDerivedClass;
@@ -57,7 +76,9 @@ use siko_syntax::function::FunctionId as AstFunctionId;
 use siko_syntax::function::FunctionType as AstFunctionType;
 use siko_syntax::function::FunctionTypeId as AstFunctionTypeId;
 use siko_syntax::module::Module as AstModule;
+use siko_syntax::module::ModuleId as AstModuleId;
 use siko_syntax::program::Program;
+use siko_syntax::types::TypeSignature as AstTypeSignature;
 use siko_util::RcCounter;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
@@ -275,6 +296,28 @@ impl Resolver {
                         members.insert(class_member.name.clone(), ir_class_member_id);
                     }
                 }
+                // the kind of the class arguments is inferred from their first use in the members
+                let arg_arities: Vec<_> = class
+                    .args
+                    .iter()
+                    .map(|arg| match &program.type_signatures.get(arg).item {
+                        AstTypeSignature::TypeArg(class_arg) => class
+                            .member_function_types
+                            .values()
+                            .flatten()
+                            .filter_map(|id| {
+                                let function_type = program.function_types.get(id);
+                                get_type_arg_arity(
+                                    &function_type.type_signature_id,
+                                    program,
+                                    class_arg,
+                                )
+                            })
+                            .next()
+                            .unwrap_or(0),
+                        _ => 0,
+                    })
+                    .collect();
                 let auto_derivable_classes: Vec<_> = get_auto_derivable_classes();
                 let auto_derivable = module.name == STD_OPS_MODULE_NAME
                     && auto_derivable_classes.contains(&class.name.as_ref());
@@ -283,6 +326,8 @@ impl Resolver {
                     name: class.name.clone(),
                     module: module.name.clone(),
                     type_signature: None,
+                    arg_arities: arg_arities,
+                    functional_dependencies: Vec::new(),
                     constraints: Vec::new(),
                     members: members,
                     location_id: class.location_id,
@@ -386,6 +431,8 @@ impl Resolver {
             type_arg_resolver.add_explicit(type_arg.clone(), Vec::new(), *location_id);
         }
 
+        let mut multi_param_constraints = Vec::new();
+
         for constraint in &function_type.constraints {
             if let Some(ir_class_id) = self.lookup_class(
                 &constraint.class_name,
@@ -393,7 +440,27 @@ impl Resolver {
                 module,
                 errors,
             ) {
-                type_arg_resolver.add_constraint(&constraint.arg, ir_class_id);
+                if !self.check_class_arg_count(constraint, ir_class_id, ir_program, errors) {
+                    continue;
+                }
+                if ir_program.classes.get(&ir_class_id).is_multi_param() {
+                    multi_param_constraints.push((constraint, ir_class_id));
+                } else {
+                    type_arg_resolver.add_constraint(&constraint.args[0], ir_class_id);
+                }
+            }
+        }
+
+        for (constraint, ir_class_id) in multi_param_constraints {
+            if let Some(multi_param_constraint) = process_multi_param_constraint(
+                &constraint.args,
+                ir_class_id,
+                constraint.location_id,
+                ir_program,
+                &mut type_arg_resolver,
+                errors,
+            ) {
+                type_arg_resolver.add_multi_param_constraint(multi_param_constraint);
             }
         }
 
@@ -475,6 +542,7 @@ impl Resolver {
             name: function.name.clone(),
             module: module.name.clone(),
             type_signature: type_signature_id,
+            multi_param_constraints: type_arg_resolver.get_multi_param_constraints(),
             location_id: function.location_id,
             kind: kind,
         };
@@ -490,6 +558,40 @@ impl Resolver {
         ir_program.functions.add_item(ir_function_id, ir_function);
     }
 
+    fn process_free_function(
+        &self,
+        program: &Program,
+        ir_program: &mut IrProgram,
+        ast_module: &AstModule,
+        ast_function_id: &AstFunctionId,
+        ir_function_id: IrFunctionId,
+        module: &Module,
+        errors: &mut Vec<ResolverError>,
+    ) {
+        let function = program.functions.get(ast_function_id);
+        let (type_signature_id, mut type_arg_resolver) =
+            if let Some(function_types) = ast_module.function_types.get(&function.name) {
+                if function_types.len() != 1 {
+                    return;
+                }
+                let function_type = program.function_types.get(&function_types[0]);
+                self.process_function_type(function_type, module, program, ir_program, errors)
+            } else {
+                (None, TypeArgResolver::new(self.type_var_generator.clone()))
+            };
+        self.process_function(
+            program,
+            ir_program,
+            &function,
+            ir_function_id,
+            module,
+            errors,
+            type_signature_id,
+            &mut type_arg_resolver,
+            NamedFunctionKind::Free,
+        );
+    }
+
     fn process_adt(
         &self,
         program: &Program,
@@ -732,20 +834,72 @@ impl Resolver {
 
         let mut type_arg_resolver = TypeArgResolver::new(self.type_var_generator.clone());
 
-        let (class_type_signature_id, class_arg) = if let Some(class_type_signature_id) =
-            process_class_type_signature(
-                &class.arg,
+        let arg_arities = ir_program.classes.get(ir_class_id).arg_arities.clone();
+        let is_multi_param = arg_arities.len() > 1;
+
+        // the args of a multi parameter class are not constrained individually,
+        // the class is represented by the tuple of its args
+        let arg_constraints = if is_multi_param {
+            Vec::new()
+        } else {
+            vec![*ir_class_id]
+        };
+
+        let mut arg_type_signature_ids = Vec::new();
+        let mut class_args = Vec::new();
+        for (arg, arg_arity) in class.args.iter().zip(arg_arities.iter()) {
+            if let Some((arg_type_signature_id, class_arg)) = process_class_type_signature(
+                arg,
                 program,
                 ir_program,
                 &mut type_arg_resolver,
                 errors,
-                *ir_class_id,
+                arg_constraints.clone(),
             ) {
-            class_type_signature_id
+                type_arg_resolver
+                    .check_arity(&class_arg, *arg_arity)
+                    .expect("Class argument kind already set");
+                arg_type_signature_ids.push(arg_type_signature_id);
+                class_args.push(class_arg);
+            } else {
+                return;
+            }
+        }
+
+        let class_type_signature_id = if is_multi_param {
+            let id = ir_program.type_signatures.get_id();
+            let type_info = ItemInfo::new(
+                TypeSignature::Tuple(arg_type_signature_ids),
+                class.location_id,
+            );
+            ir_program.type_signatures.add_item(id, type_info);
+            id
         } else {
-            return;
+            arg_type_signature_ids[0]
         };
 
+        let mut functional_dependencies = Vec::new();
+
+        for dependency in &class.functional_dependencies {
+            let mut get_indices = |args: &Vec<String>| -> Vec<usize> {
+                let mut indices = Vec::new();
+                for arg in args {
+                    match class_args.iter().position(|class_arg| class_arg == arg) {
+                        Some(index) => indices.push(index),
+                        None => {
+                            let err =
+                                ResolverError::UnknownTypeArg(arg.clone(), dependency.location_id);
+                            errors.push(err);
+                        }
+                    }
+                }
+                indices
+            };
+            let from = get_indices(&dependency.from);
+            let to = get_indices(&dependency.to);
+            functional_dependencies.push(IrFunctionalDependency { from: from, to: to });
+        }
+
         let mut ir_constraints = Vec::new();
 
         for constraint in &class.constraints {
@@ -755,10 +909,21 @@ impl Resolver {
                 module,
                 errors,
             ) {
+                if !self.check_class_arg_count(constraint, ir_class_id, ir_program, errors) {
+                    continue;
+                }
+                if is_multi_param || ir_program.classes.get(&ir_class_id).is_multi_param() {
+                    let err = ResolverError::MultiParamConstraintNotAllowed(
+                        constraint.class_name.clone(),
+                        constraint.location_id,
+                    );
+                    errors.push(err);
+                    continue;
+                }
                 ir_constraints.push(ir_class_id);
-                if !type_arg_resolver.add_constraint(&constraint.arg, ir_class_id) {
+                if !type_arg_resolver.add_constraint(&constraint.args[0], ir_class_id) {
                     let err = ResolverError::InvalidArgumentInTypeClassConstraint(
-                        constraint.arg.clone(),
+                        constraint.args[0].clone(),
                         constraint.location_id,
                     );
                     errors.push(err);
@@ -768,6 +933,7 @@ impl Resolver {
 
         let ir_class = ir_program.classes.get_mut(ir_class_id);
         ir_class.constraints = ir_constraints;
+        ir_class.functional_dependencies = functional_dependencies;
         ir_class.type_signature = Some(class_type_signature_id);
 
         let (functions_without_types, _, conflicting_functions, _) =
@@ -807,20 +973,26 @@ impl Resolver {
             let ir_class_member_id = *ir_class.members.get(name).expect("Class member not found");
             let signature_type_args: BTreeSet<_> =
                 class_member.type_args.iter().map(|i| i.0.clone()).collect();
-            if !signature_type_args.contains(&class_arg) {
-                let err = ResolverError::ClassMemberTypeArgMissing(
-                    class_member.name.clone(),
-                    class_arg.clone(),
-                    class_member.location_id,
-                );
-                errors.push(err);
+            let missing_class_args: Vec<_> = class_args
+                .iter()
+                .filter(|class_arg| !signature_type_args.contains(*class_arg))
+                .collect();
+            if !missing_class_args.is_empty() {
+                for class_arg in missing_class_args {
+                    let err = ResolverError::ClassMemberTypeArgMissing(
+                        class_member.name.clone(),
+                        class_arg.clone(),
+                        class_member.location_id,
+                    );
+                    errors.push(err);
+                }
                 continue;
             }
 
             let mut type_arg_resolver = type_arg_resolver.clone();
 
             for (type_arg, location) in &class_member.type_args {
-                if *type_arg == class_arg {
+                if class_args.contains(type_arg) {
                     continue;
                 }
                 type_arg_resolver.add_explicit(type_arg.clone(), vec![], *location);
@@ -833,10 +1005,30 @@ impl Resolver {
                     module,
                     errors,
                 ) {
-                    type_arg_resolver.add_constraint(&constraint.arg, ir_class_id);
+                    if !self.check_class_arg_count(constraint, ir_class_id, ir_program, errors) {
+                        continue;
+                    }
+                    if ir_program.classes.get(&ir_class_id).is_multi_param() {
+                        let err = ResolverError::MultiParamConstraintNotAllowed(
+                            constraint.class_name.clone(),
+                            constraint.location_id,
+                        );
+                        errors.push(err);
+                        continue;
+                    }
+                    type_arg_resolver.add_constraint(&constraint.args[0], ir_class_id);
                 }
             }
 
+            // default implementations of multi parameter classes may rely on the class itself
+            if is_multi_param {
+                type_arg_resolver.add_multi_param_constraint(IrMultiParamConstraint {
+                    class_id: *ir_class_id,
+                    type_signature: class_type_signature_id,
+                    location_id: class.location_id,
+                });
+            }
+
             let result = process_type_signature(
                 &class_member.type_signature_id,
                 program,
@@ -901,10 +1093,133 @@ impl Resolver {
                 &instance_type_signature,
                 ir_program,
             ),
+            TypeSignature::Tuple(class_args) => {
+                let class_args = class_args.clone();
+                let instance_args = match &ir_program
+                    .type_signatures
+                    .get(&instance_type_signature)
+                    .item
+                {
+                    TypeSignature::Tuple(instance_args) => instance_args.clone(),
+                    _ => panic!("Invalid instance type signature"),
+                };
+                let mut type_signature = ir_class_member.type_signature;
+                for (class_arg, instance_arg) in class_args.iter().zip(instance_args.iter()) {
+                    let index = match &ir_program.type_signatures.get(class_arg).item {
+                        TypeSignature::TypeArgument(index, _, _) => *index,
+                        _ => panic!("Invalid class type signature"),
+                    };
+                    type_signature =
+                        subtitute_type_signature(&type_signature, index, instance_arg, ir_program);
+                }
+                type_signature
+            }
             _ => panic!("Invalid class type signature"),
         }
     }
 
+    fn process_instance_member_type(
+        &self,
+        function_type: &AstFunctionType,
+        expected_type_signature_id: TypeSignatureId,
+        instance_type_args: &BTreeMap<String, LocationId>,
+        module: &Module,
+        program: &Program,
+        ir_program: &mut IrProgram,
+        type_arg_resolver: &mut TypeArgResolver,
+        errors: &mut Vec<ResolverError>,
+    ) -> Option<TypeSignatureId> {
+        for (type_arg, location) in &function_type.type_args {
+            if instance_type_args.contains_key(type_arg) {
+                continue;
+            }
+            type_arg_resolver.add_explicit(type_arg.clone(), vec![], *location);
+        }
+
+        for constraint in &function_type.constraints {
+            if let Some(ir_class_id) = self.lookup_class(
+                &constraint.class_name,
+                constraint.location_id,
+                module,
+                errors,
+            ) {
+                if !self.check_class_arg_count(constraint, ir_class_id, ir_program, errors) {
+                    continue;
+                }
+                if ir_program.classes.get(&ir_class_id).is_multi_param() {
+                    let err = ResolverError::MultiParamConstraintNotAllowed(
+                        constraint.class_name.clone(),
+                        constraint.location_id,
+                    );
+                    errors.push(err);
+                    continue;
+                }
+                if !type_arg_resolver.add_constraint(&constraint.args[0], ir_class_id) {
+                    let err = ResolverError::InvalidArgumentInTypeClassConstraint(
+                        constraint.args[0].clone(),
+                        constraint.location_id,
+                    );
+                    errors.push(err);
+                }
+            }
+        }
+
+        let type_signature_id = process_type_signature(
+            &function_type.type_signature_id,
+            program,
+            ir_program,
+            module,
+            type_arg_resolver,
+            errors,
+        )?;
+
+        // the type args of the instance are fixed, only the member's own type args can be renamed
+        let mut arg_map = BTreeMap::new();
+        for type_arg in instance_type_args.keys() {
+            if let Some(info) = type_arg_resolver.get_arg(type_arg) {
+                arg_map.insert(info.index, info.index);
+            }
+        }
+        if !type_signatures_match(
+            &expected_type_signature_id,
+            &type_signature_id,
+            ir_program,
+            &mut arg_map,
+        ) {
+            let err = ResolverError::InstanceMemberTypeMismatch(
+                function_type.name.clone(),
+                format_type_signature(&expected_type_signature_id, ir_program),
+                format_type_signature(&type_signature_id, ir_program),
+                function_type.location_id,
+            );
+            errors.push(err);
+            return None;
+        }
+        Some(type_signature_id)
+    }
+
+    fn check_class_arg_count(
+        &self,
+        constraint: &AstConstraint,
+        ir_class_id: IrClassId,
+        ir_program: &IrProgram,
+        errors: &mut Vec<ResolverError>,
+    ) -> bool {
+        let expected = ir_program.classes.get(&ir_class_id).arg_arities.len();
+        if constraint.args.len() != expected {
+            let err = ResolverError::IncorrectClassArgumentCount(
+                constraint.class_name.clone(),
+                expected,
+                constraint.args.len(),
+                constraint.location_id,
+            );
+            errors.push(err);
+            false
+        } else {
+            true
+        }
+    }
+
     fn process_instance(
         &self,
         instance: &AstInstance,
@@ -917,12 +1232,16 @@ impl Resolver {
 
         let mut type_args = BTreeMap::new();
 
-        collect_type_args(&instance.type_signature_id, program, &mut type_args);
+        for type_signature_id in &instance.type_signature_ids {
+            collect_type_args(type_signature_id, program, &mut type_args);
+        }
 
-        for (type_arg, location_id) in type_args {
-            type_arg_resolver.add_explicit(type_arg, Vec::new(), location_id);
+        for (type_arg, location_id) in &type_args {
+            type_arg_resolver.add_explicit(type_arg.clone(), Vec::new(), *location_id);
         }
 
+        let mut multi_param_constraints = Vec::new();
+
         for constraint in &instance.constraints {
             if let Some(ir_class_id) = self.lookup_class(
                 &constraint.class_name,
@@ -930,9 +1249,16 @@ impl Resolver {
                 module,
                 errors,
             ) {
-                if !type_arg_resolver.add_constraint(&constraint.arg, ir_class_id) {
+                if !self.check_class_arg_count(constraint, ir_class_id, ir_program, errors) {
+                    continue;
+                }
+                if ir_program.classes.get(&ir_class_id).is_multi_param() {
+                    multi_param_constraints.push((constraint, ir_class_id));
+                    continue;
+                }
+                if !type_arg_resolver.add_constraint(&constraint.args[0], ir_class_id) {
                     let err = ResolverError::InvalidTypeArgInInstanceConstraint(
-                        constraint.arg.clone(),
+                        constraint.args[0].clone(),
                         constraint.location_id,
                     );
                     errors.push(err);
@@ -940,6 +1266,19 @@ impl Resolver {
             }
         }
 
+        for (constraint, ir_class_id) in multi_param_constraints {
+            if let Some(multi_param_constraint) = process_multi_param_constraint(
+                &constraint.args,
+                ir_class_id,
+                constraint.location_id,
+                ir_program,
+                &mut type_arg_resolver,
+                errors,
+            ) {
+                type_arg_resolver.add_multi_param_constraint(multi_param_constraint);
+            }
+        }
+
         let ir_class_id =
             match self.lookup_class(&instance.class_name, instance.location_id, module, errors) {
                 Some(ir_class_id) => ir_class_id,
@@ -947,15 +1286,47 @@ impl Resolver {
                     return;
                 }
             };
-        let result = process_type_signature(
-            &instance.type_signature_id,
-            program,
-            ir_program,
-            module,
-            &mut type_arg_resolver,
-            errors,
-        );
         let ir_class = ir_program.classes.get(&ir_class_id).clone();
+        if ir_class.arg_arities.len() != instance.type_signature_ids.len() {
+            let err = ResolverError::IncorrectClassArgumentCount(
+                instance.class_name.clone(),
+                ir_class.arg_arities.len(),
+                instance.type_signature_ids.len(),
+                instance.location_id,
+            );
+            errors.push(err);
+            return;
+        }
+        let mut instance_args = Vec::new();
+        for (type_signature_id, arg_arity) in instance
+            .type_signature_ids
+            .iter()
+            .zip(ir_class.arg_arities.iter())
+        {
+            if let Some(instance_arg) = process_instance_type_signature(
+                type_signature_id,
+                &instance.class_name,
+                *arg_arity,
+                program,
+                ir_program,
+                module,
+                &mut type_arg_resolver,
+                errors,
+            ) {
+                instance_args.push(instance_arg);
+            }
+        }
+        let result = if instance_args.len() != ir_class.arg_arities.len() {
+            None
+        } else if ir_class.is_multi_param() {
+            let id = ir_program.type_signatures.get_id();
+            let type_info =
+                ItemInfo::new(TypeSignature::Tuple(instance_args), instance.location_id);
+            ir_program.type_signatures.add_item(id, type_info);
+            Some(id)
+        } else {
+            Some(instance_args[0])
+        };
 
         let (
             _,
@@ -1012,17 +1383,34 @@ impl Resolver {
                 if let Some(class_member_id) = ir_class.members.get(member_name) {
                     let ir_function_id = ir_program.functions.get_id();
                     let ir_class_member = ir_program.class_members.get(class_member_id).clone();
-                    let member_function_type_signature_id = if let Some(function_type) =
+                    let expected_type_signature_id = self.get_instance_member_type_signature(
+                        ir_program,
+                        &ir_class_member,
+                        instance_type_signature,
+                    );
+                    let mut member_type_arg_resolver = type_arg_resolver.clone();
+                    let member_function_type_signature_id = if let Some(function_type_ids) =
                         instance.member_function_types.get(member_name)
                     {
-                        // TODO: the instance impl has its own type signature, compare it with the class member
-                        unimplemented!()
-                    } else {
-                        self.get_instance_member_type_signature(
+                        let function_type = program.function_types.get(&function_type_ids[0]);
+                        match self.process_instance_member_type(
+                            function_type,
+                            expected_type_signature_id,
+                            &type_args,
+                            module,
+                            program,
                             ir_program,
-                            &ir_class_member,
-                            instance_type_signature,
-                        )
+                            &mut member_type_arg_resolver,
+                            errors,
+                        ) {
+                            Some(id) => id,
+                            None => {
+                                implemented_members.insert(member_name.clone());
+                                continue;
+                            }
+                        }
+                    } else {
+                        expected_type_signature_id
                     };
                     let ir_instance_member = IrInstanceMember {
                         type_signature: member_function_type_signature_id,
@@ -1037,7 +1425,7 @@ impl Resolver {
                         module,
                         errors,
                         Some(member_function_type_signature_id),
-                        &mut type_arg_resolver,
+                        &mut member_type_arg_resolver,
                         NamedFunctionKind::InstanceMember(instance.name.clone()),
                     );
                 } else {
@@ -1070,6 +1458,7 @@ impl Resolver {
                 name: instance.name.clone(),
                 class_id: ir_class_id,
                 type_signature: instance_type_signature,
+                multi_param_constraints: type_arg_resolver.get_multi_param_constraints(),
                 members: members,
                 location_id: instance.location_id,
             };
@@ -1078,6 +1467,351 @@ impl Resolver {
         }
     }
 
+    fn get_record_typedef_id(
+        &self,
+        module: &Module,
+        name: &String,
+        record_id: RecordId,
+    ) -> TypeDefId {
+        let items = module.items.get(name).expect("Record not found");
+        for item in items {
+            if let Item::Record(ast_record_id, ir_typedef_id) = item {
+                if *ast_record_id == record_id {
+                    return *ir_typedef_id;
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    fn find_std_actor_class(&self, ir_program: &IrProgram, name: &str) -> Option<IrClassId> {
+        ir_program
+            .classes
+            .items
+            .values()
+            .find(|class| class.module == STD_ACTOR_MODULE_NAME && class.name == name)
+            .map(|class| class.id)
+    }
+
+    fn add_std_actor_instance(
+        &self,
+        ir_program: &mut IrProgram,
+        class_id: Option<IrClassId>,
+        name: &String,
+        typedef_id: TypeDefId,
+        location_id: LocationId,
+    ) {
+        if let Some(class_id) = class_id {
+            let type_signature = TypeSignature::Named(name.clone(), typedef_id, Vec::new());
+            let type_signature_id = ir_program.type_signatures.get_id();
+            ir_program.type_signatures.add_item(
+                type_signature_id,
+                ItemInfo::new(type_signature, location_id),
+            );
+            let id = ir_program.instances.get_id();
+            let ir_instance = IrInstance {
+                id: id,
+                name: None,
+                class_id: class_id,
+                type_signature: type_signature_id,
+                multi_param_constraints: Vec::new(),
+                members: BTreeMap::new(),
+                location_id: location_id,
+            };
+            ir_program.instances.add_item(id, ir_instance);
+        }
+    }
+
+    fn lookup_protocol(
+        &self,
+        handler: &AstProtocolHandler,
+        module: &Module,
+        protocols: &BTreeMap<RecordId, IrProtocolId>,
+        errors: &mut Vec<ResolverError>,
+    ) -> Option<IrProtocolId> {
+        if let Some(items) = module.imported_items.get(&handler.protocol) {
+            match ImportedItemInfo::resolve_ambiguity(items, Namespace::Type) {
+                None => {
+                    let err = ResolverError::AmbiguousName(
+                        handler.protocol.clone(),
+                        handler.protocol_location_id,
+                    );
+                    errors.push(err);
+                    return None;
+                }
+                Some(item) => {
+                    if let Item::Record(ast_record_id, _) = item.item {
+                        if let Some(protocol_id) = protocols.get(&ast_record_id) {
+                            return Some(*protocol_id);
+                        }
+                    }
+                }
+            }
+        }
+        let err =
+            ResolverError::NotAProtocolName(handler.protocol.clone(), handler.protocol_location_id);
+        errors.push(err);
+        None
+    }
+
+    fn lookup_handler_function(
+        &self,
+        handler: &AstProtocolHandler,
+        module: &Module,
+        errors: &mut Vec<ResolverError>,
+    ) -> Option<IrFunctionId> {
+        if let Some(items) = module.imported_items.get(&handler.handler_func) {
+            match ImportedItemInfo::resolve_ambiguity(items, Namespace::Value) {
+                None => {
+                    let err = ResolverError::AmbiguousName(
+                        handler.handler_func.clone(),
+                        handler.handler_func_location_id,
+                    );
+                    errors.push(err);
+                    return None;
+                }
+                Some(item) => {
+                    if let Item::Function(_, ir_function_id) = item.item {
+                        return Some(ir_function_id);
+                    }
+                }
+            }
+        }
+        let suggestions = get_item_suggestions(&handler.handler_func, module, |item| match item {
+            Item::Function(..) => true,
+            _ => false,
+        });
+        let err = ResolverError::UnknownFunction(
+            handler.handler_func.clone(),
+            suggestions,
+            handler.handler_func_location_id,
+        );
+        errors.push(err);
+        None
+    }
+
+    fn process_actors_and_protocols(
+        &self,
+        program: &Program,
+        ir_program: &mut IrProgram,
+        errors: &mut Vec<ResolverError>,
+    ) {
+        let actor_class_id = self.find_std_actor_class(ir_program, ACTOR_CLASS_NAME);
+        let protocol_class_id = self.find_std_actor_class(ir_program, PROTOCOL_CLASS_NAME);
+        let mut protocols = BTreeMap::new();
+        for (_, module) in &self.modules {
+            let ast_module = program.modules.get(&module.id);
+            for protocol_id in &ast_module.protocols {
+                let protocol = program.protocols.get(protocol_id);
+                let typedef_id =
+                    self.get_record_typedef_id(module, &protocol.name, protocol.record_id);
+                let id = ir_program.protocols.get_id();
+                let ir_protocol = IrProtocol {
+                    id: id,
+                    name: protocol.name.clone(),
+                    module: module.name.clone(),
+                    typedef_id: typedef_id,
+                    location_id: protocol.location_id,
+                };
+                ir_program.protocols.add_item(id, ir_protocol);
+                protocols.insert(protocol.record_id, id);
+                self.add_std_actor_instance(
+                    ir_program,
+                    protocol_class_id,
+                    &protocol.name,
+                    typedef_id,
+                    protocol.location_id,
+                );
+            }
+        }
+        for (_, module) in &self.modules {
+            let ast_module = program.modules.get(&module.id);
+            for actor_id in &ast_module.actors {
+                let actor = program.actors.get(actor_id);
+                let typedef_id = self.get_record_typedef_id(module, &actor.name, actor.record_id);
+                let mut handlers: Vec<IrProtocolHandler> = Vec::new();
+                for handler in &actor.handlers {
+                    let protocol_id = self.lookup_protocol(handler, module, &protocols, errors);
+                    let function_id = self.lookup_handler_function(handler, module, errors);
+                    if let (Some(protocol_id), Some(function_id)) = (protocol_id, function_id) {
+                        if handlers.iter().any(|h| h.protocol_id == protocol_id) {
+                            let err = ResolverError::ConflictingProtocolHandler(
+                                actor.name.clone(),
+                                handler.protocol.clone(),
+                                handler.protocol_location_id,
+                            );
+                            errors.push(err);
+                            continue;
+                        }
+                        handlers.push(IrProtocolHandler {
+                            protocol_id: protocol_id,
+                            function_id: function_id,
+                            location_id: handler.protocol_location_id,
+                        });
+                    }
+                }
+                let id = ir_program.actors.get_id();
+                let ir_actor = IrActor {
+                    id: id,
+                    name: actor.name.clone(),
+                    module: module.name.clone(),
+                    typedef_id: typedef_id,
+                    handlers: handlers,
+                    location_id: actor.location_id,
+                };
+                ir_program.actors.add_item(id, ir_actor);
+                self.add_std_actor_instance(
+                    ir_program,
+                    actor_class_id,
+                    &actor.name,
+                    typedef_id,
+                    actor.location_id,
+                );
+            }
+        }
+    }
+
+    // implicit imports are left out, every module has the same ones
+    pub fn get_module_imports(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut imports = BTreeMap::new();
+        for (name, module) in &self.modules {
+            let sources: BTreeSet<_> = module
+                .imported_items
+                .values()
+                .flatten()
+                .filter(|info| !info.implicit && info.source_module != *name)
+                .map(|info| info.source_module.clone())
+                .collect();
+            imports.insert(name.clone(), sources);
+        }
+        imports
+    }
+
+    pub fn save_module(&self, module_name: &str) -> Module {
+        self.modules
+            .get(module_name)
+            .expect("Module not found")
+            .clone()
+    }
+
+    pub fn restore_module(&mut self, module: Module) {
+        self.modules.insert(module.name.clone(), module);
+    }
+
+    // Recomputes the imports of an already resolved module, used after new
+    // imports were added to its source.
+    pub fn resolve_module_imports(
+        &mut self,
+        program: &Program,
+        module_name: &str,
+    ) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        process_module_imports(
+            &module_name.to_string(),
+            &mut self.modules,
+            program,
+            &mut errors,
+        );
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::resolve_err(errors))
+        }
+    }
+
+    // Resolves the functions of a newly parsed source module as if they were
+    // defined in an already resolved module, replacing its functions with the same
+    // name. Nothing is changed if resolution fails. Returns the ids of the added
+    // named functions.
+    pub fn resolve_additional_functions(
+        &mut self,
+        program: &Program,
+        source_module_id: AstModuleId,
+        module_name: &str,
+        ir_program: &mut IrProgram,
+    ) -> Result<BTreeMap<String, IrFunctionId>, Error> {
+        let mut errors = Vec::new();
+        let module_name = module_name.to_string();
+        let ast_module = program.modules.get(&source_module_id);
+
+        check_function_types(&module_name, ast_module, program, &mut errors);
+
+        for (name, ids) in &ast_module.functions {
+            if ids.len() > 1 {
+                let locations: Vec<_> = ids
+                    .iter()
+                    .map(|id| program.functions.get(id).location_id)
+                    .collect();
+                let err = ResolverError::InternalModuleConflicts(
+                    module_name.clone(),
+                    name.clone(),
+                    locations,
+                );
+                errors.push(err);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::resolve_err(errors));
+        }
+
+        let saved_module = self.save_module(&module_name);
+        let existing_function_ids: BTreeSet<_> =
+            ir_program.functions.items.keys().cloned().collect();
+
+        let mut functions = BTreeMap::new();
+        let module = self
+            .modules
+            .get_mut(&module_name)
+            .expect("Module not found");
+        for (name, ids) in &ast_module.functions {
+            if let Some(items) = module.items.get_mut(name) {
+                items.retain(|item| !matches!(item, Item::Function(..)));
+            }
+            let ir_function_id = ir_program.functions.get_id();
+            module.add_item(name.clone(), Item::Function(ids[0], ir_function_id));
+            functions.insert(name.clone(), (ids[0], ir_function_id));
+        }
+
+        process_module_imports(&module_name, &mut self.modules, program, &mut errors);
+
+        let module = self.modules.get(&module_name).expect("Module not found");
+        for (ast_function_id, ir_function_id) in functions.values() {
+            self.process_free_function(
+                program,
+                ir_program,
+                ast_module,
+                ast_function_id,
+                *ir_function_id,
+                module,
+                &mut errors,
+            );
+        }
+
+        let new_function_ids: Vec<_> = ir_program
+            .functions
+            .items
+            .keys()
+            .filter(|id| !existing_function_ids.contains(id))
+            .cloned()
+            .collect();
+
+        if !errors.is_empty() {
+            self.restore_module(saved_module);
+            for function_id in new_function_ids {
+                ir_program.functions.items.remove(&function_id);
+            }
+            return Err(Error::resolve_err(errors));
+        }
+
+        shift_lambda_args(ir_program, &new_function_ids);
+
+        Ok(functions
+            .into_iter()
+            .map(|(name, (_, ir_function_id))| (name, ir_function_id))
+            .collect())
+    }
+
     pub fn resolve(&mut self, program: &Program) -> Result<IrProgram, Error> {
         let mut errors = Vec::new();
 
@@ -1172,75 +1906,25 @@ impl Resolver {
 
         for (_, module) in &self.modules {
             let ast_module = program.modules.get(&module.id);
-            let (_, function_types_without_functions, _, conflicting_function_types) =
-                check_function_and_function_type_consistency(
-                    &ast_module.functions,
-                    &ast_module.function_types,
-                );
-
-            for id in function_types_without_functions {
-                let function_type = program.function_types.get(&id);
-                let err = ResolverError::FunctionTypeWithoutImplementationInModule(
-                    module.name.clone(),
-                    function_type.name.clone(),
-                    function_type.location_id,
-                );
-                errors.push(err);
-            }
-
-            for (name, ids) in conflicting_function_types {
-                let locations: Vec<_> = ids
-                    .iter()
-                    .map(|id| program.function_types.get(id).location_id)
-                    .collect();
-                let err = ResolverError::ConflictingFunctionTypesInModule(
-                    module.name.clone(),
-                    name.clone(),
-                    locations,
-                );
-                errors.push(err);
-            }
-        }
-
-        if !errors.is_empty() {
-            return Err(Error::resolve_err(errors));
+            check_function_types(&module.name, ast_module, program, &mut errors);
         }
 
+        // Function type errors above are local to their own functions, so the
+        // remaining functions are still resolved to report all errors at once.
         for (_, module) in &self.modules {
             for (_, items) in &module.items {
                 for item in items {
                     match item {
                         Item::Function(ast_function_id, ir_function_id) => {
-                            let function = program.functions.get(ast_function_id);
                             let ast_module = program.modules.get(&module.id);
-                            let (type_signature_id, mut type_arg_resolver) =
-                                if let Some(function_types) =
-                                    ast_module.function_types.get(&function.name)
-                                {
-                                    assert_eq!(function_types.len(), 1);
-                                    let function_type_id = function_types[0];
-                                    let function_type =
-                                        program.function_types.get(&function_type_id);
-                                    self.process_function_type(
-                                        function_type,
-                                        module,
-                                        program,
-                                        &mut ir_program,
-                                        &mut errors,
-                                    )
-                                } else {
-                                    (None, TypeArgResolver::new(self.type_var_generator.clone()))
-                                };
-                            self.process_function(
+                            self.process_free_function(
                                 program,
                                 &mut ir_program,
-                                &function,
+                                ast_module,
+                                ast_function_id,
                                 *ir_function_id,
                                 module,
                                 &mut errors,
-                                type_signature_id,
-                                &mut type_arg_resolver,
-                                NamedFunctionKind::Free,
                             );
                         }
                         _ => {}
@@ -1249,6 +1933,8 @@ impl Resolver {
             }
         }
 
+        self.process_actors_and_protocols(program, &mut ir_program, &mut errors);
+
         if !errors.is_empty() {
             return Err(Error::resolve_err(errors));
         } else {
@@ -1277,23 +1963,69 @@ impl Resolver {
             ir_program.named_types = named_types;
         }
 
-        let mut lambdas = Vec::new();
+        let function_ids: Vec<_> = ir_program.functions.items.keys().cloned().collect();
+        shift_lambda_args(&mut ir_program, &function_ids);
 
-        for (_, function) in ir_program.functions.items.iter() {
-            match &function.info {
-                FunctionInfo::Lambda(info) => {
-                    let capture_count = function.arg_count - function.arg_locations.len();
-                    lambdas.push((info.body, capture_count));
-                }
-                _ => {}
-            }
-        }
+        Ok(ir_program)
+    }
+}
+
+fn check_function_types(
+    module_name: &str,
+    ast_module: &AstModule,
+    program: &Program,
+    errors: &mut Vec<ResolverError>,
+) {
+    let (_, function_types_without_functions, _, conflicting_function_types) =
+        check_function_and_function_type_consistency(
+            &ast_module.functions,
+            &ast_module.function_types,
+        );
+
+    for id in function_types_without_functions {
+        let function_type = program.function_types.get(&id);
+        let err = ResolverError::FunctionTypeWithoutImplementationInModule(
+            module_name.to_string(),
+            function_type.name.clone(),
+            function_type.location_id,
+        );
+        errors.push(err);
+    }
 
-        for (body, capture_count) in lambdas {
-            let mut shifter = LambdaArgShifter::new(&mut ir_program, capture_count);
-            walk_expr(&body, &mut shifter);
+    for (name, ids) in conflicting_function_types {
+        let locations: Vec<_> = ids
+            .iter()
+            .map(|id| program.function_types.get(id).location_id)
+            .collect();
+        let err = ResolverError::ConflictingFunctionTypesInModule(
+            module_name.to_string(),
+            name.clone(),
+            locations,
+        );
+        errors.push(err);
+    }
+}
+
+fn shift_lambda_args(ir_program: &mut IrProgram, function_ids: &[IrFunctionId]) {
+    let mut lambdas = Vec::new();
+
+    for function_id in function_ids {
+        let function = ir_program.functions.get(function_id);
+        match &function.info {
+            FunctionInfo::Lambda(info) => {
+                let capture_count = function.arg_count - function.arg_locations.len();
+                lambdas.push((info.body, capture_count));
+            }
+            FunctionInfo::LocalFunction(info) => {
+                let capture_count = function.arg_count - function.arg_locations.len();
+                lambdas.push((info.body, capture_count));
+            }
+            _ => {}
         }
+    }
 
-        Ok(ir_program)
+    for (body, capture_count) in lambdas {
+        let mut shifter = LambdaArgShifter::new(ir_program, capture_count);
+        walk_expr(&body, &mut shifter);
     }
 }
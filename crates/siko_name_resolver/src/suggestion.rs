@@ -0,0 +1,74 @@
+use crate::item::DataMember;
+use crate::item::Item;
+use crate::module::Module;
+
+const MAX_SUGGESTIONS: usize = 3;
+
+// Optimal string alignment distance, swapping two adjacent chars counts as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len() {
+        distances[i][0] = i;
+    }
+    for j in 0..=b.len() {
+        distances[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+// Returns the candidates close enough to the unknown name, the closest ones first.
+pub fn get_suggestions<'a>(
+    name: &str,
+    candidates: impl Iterator<Item = &'a String>,
+) -> Vec<String> {
+    let limit = std::cmp::max(1, name.chars().count() / 3);
+    let mut suggestions: Vec<_> = candidates
+        .filter(|candidate| candidate.as_str() != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate.clone()))
+        .filter(|(distance, _)| *distance <= limit)
+        .collect();
+    suggestions.sort();
+    suggestions.dedup();
+    suggestions
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+pub fn get_item_suggestions(name: &str, module: &Module, filter: fn(&Item) -> bool) -> Vec<String> {
+    let candidates = module
+        .imported_items
+        .iter()
+        .filter(|(_, items)| items.iter().any(|item| filter(&item.item)))
+        .map(|(name, _)| name);
+    get_suggestions(name, candidates)
+}
+
+pub fn get_field_suggestions(name: &str, module: &Module) -> Vec<String> {
+    let candidates = module
+        .imported_members
+        .iter()
+        .filter(|(_, members)| {
+            members.iter().any(|member| match member.member {
+                DataMember::RecordField(..) => true,
+                DataMember::Variant(..) => false,
+            })
+        })
+        .map(|(name, _)| name);
+    get_suggestions(name, candidates)
+}
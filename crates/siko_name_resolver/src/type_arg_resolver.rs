@@ -1,4 +1,5 @@
 use siko_ir::class::ClassId;
+use siko_ir::class::MultiParamConstraint;
 use siko_ir::type_var_generator::TypeVarGenerator;
 use siko_location_info::location_id::LocationId;
 use std::collections::BTreeMap;
@@ -7,13 +8,16 @@ use std::collections::BTreeMap;
 pub struct TypeArgInfo {
     pub index: usize,
     pub constraints: Vec<ClassId>,
+    pub arity: Option<usize>,
     pub used: bool,
+    pub kind_mismatch_reported: bool,
     pub location_id: LocationId,
 }
 
 #[derive(Clone)]
 pub struct TypeArgResolver {
     args: BTreeMap<String, TypeArgInfo>,
+    multi_param_constraints: Vec<MultiParamConstraint>,
     type_var_generator: TypeVarGenerator,
 }
 
@@ -21,6 +25,7 @@ impl TypeArgResolver {
     pub fn new(type_var_generator: TypeVarGenerator) -> TypeArgResolver {
         TypeArgResolver {
             args: BTreeMap::new(),
+            multi_param_constraints: Vec::new(),
             type_var_generator: type_var_generator,
         }
     }
@@ -35,7 +40,9 @@ impl TypeArgResolver {
         let info = TypeArgInfo {
             index: index,
             constraints: constraints,
+            arity: None,
             used: false,
+            kind_mismatch_reported: false,
             location_id: location_id,
         };
         self.args.insert(arg.clone(), info);
@@ -53,6 +60,18 @@ impl TypeArgResolver {
         }
     }
 
+    pub fn add_multi_param_constraint(&mut self, constraint: MultiParamConstraint) {
+        self.multi_param_constraints.push(constraint);
+    }
+
+    pub fn get_multi_param_constraints(&self) -> Vec<MultiParamConstraint> {
+        self.multi_param_constraints.clone()
+    }
+
+    pub fn get_arg(&self, arg: &String) -> Option<TypeArgInfo> {
+        self.args.get(arg).cloned()
+    }
+
     pub fn resolve_arg(&mut self, arg: &String) -> Option<TypeArgInfo> {
         if let Some(info) = self.args.get_mut(arg) {
             info.used = true;
@@ -62,6 +81,26 @@ impl TypeArgResolver {
         }
     }
 
+    pub fn check_arity(&mut self, arg: &String, arity: usize) -> Result<(), usize> {
+        if let Some(info) = self.args.get_mut(arg) {
+            match info.arity {
+                Some(expected) if expected != arity => return Err(expected),
+                _ => {
+                    info.arity = Some(arity);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // returns false if a kind mismatch was already reported for the argument
+    pub fn mark_kind_mismatch(&mut self, arg: &String) -> bool {
+        match self.args.get_mut(arg) {
+            Some(info) => !std::mem::replace(&mut info.kind_mismatch_reported, true),
+            None => true,
+        }
+    }
+
     pub fn contains(&self, arg: &str) -> bool {
         self.args.contains_key(arg)
     }
@@ -3,8 +3,12 @@ use crate::import::ImportedItemInfo;
 use crate::import::Namespace;
 use crate::item::Item;
 use crate::module::Module;
+use crate::suggestion::get_item_suggestions;
 use crate::type_arg_resolver::TypeArgResolver;
+use siko_constants::LIST_MODULE_NAME;
+use siko_constants::LIST_TYPE_NAME;
 use siko_ir::class::ClassId;
+use siko_ir::class::MultiParamConstraint;
 use siko_ir::program::Program as IrProgram;
 use siko_ir::type_signature::TypeSignature as IrTypeSignature;
 use siko_ir::type_signature::TypeSignatureId as IrTypeSignatureId;
@@ -18,6 +22,7 @@ use std::collections::BTreeMap;
 fn process_named_type(
     name: &str,
     named_args: &Vec<TypeSignatureId>,
+    missing_arg_count: usize,
     location_id: LocationId,
     program: &Program,
     ir_program: &mut IrProgram,
@@ -54,7 +59,7 @@ fn process_named_type(
                 match item.item {
                     Item::Adt(_, ir_typedef_id) => {
                         let ir_adt = ir_program.typedefs.get(&ir_typedef_id).get_adt();
-                        if ir_adt.type_args.len() != named_arg_ids.len() {
+                        if ir_adt.type_args.len() != named_arg_ids.len() + missing_arg_count {
                             let err = ResolverError::IncorrectTypeArgumentCount(
                                 name.to_string(),
                                 ir_adt.type_args.len(),
@@ -69,7 +74,7 @@ fn process_named_type(
                     Item::Record(_, ir_typedef_id) => {
                         let ir_record = ir_program.typedefs.get(&ir_typedef_id).get_record();
 
-                        if ir_record.type_args.len() != named_arg_ids.len() {
+                        if ir_record.type_args.len() != named_arg_ids.len() + missing_arg_count {
                             let err = ResolverError::IncorrectTypeArgumentCount(
                                 name.to_string(),
                                 ir_record.type_args.len(),
@@ -93,7 +98,8 @@ fn process_named_type(
             }
         },
         None => {
-            let error = ResolverError::UnknownTypeName(name.to_string(), location_id);
+            let suggestions = get_item_suggestions(name, module, Item::is_type_level);
+            let error = ResolverError::UnknownTypeName(name.to_string(), suggestions, location_id);
             errors.push(error);
             return None;
         }
@@ -137,6 +143,14 @@ pub fn subtitute_type_signature(
                 return *source;
             }
         }
+        IrTypeSignature::Applied(head, items) => {
+            let new_head = subtitute_type_signature(head, from, to, ir_program);
+            let new_items: Vec<_> = items
+                .iter()
+                .map(|item| subtitute_type_signature(item, from, to, ir_program))
+                .collect();
+            IrTypeSignature::Applied(new_head, new_items)
+        }
         IrTypeSignature::Tuple(items) => {
             let new_items: Vec<_> = items
                 .iter()
@@ -155,6 +169,178 @@ pub fn subtitute_type_signature(
     id
 }
 
+// type applications of named types are flattened, so that f a with f = Option matches Option a
+fn get_normalized_type_signature(
+    id: &IrTypeSignatureId,
+    ir_program: &IrProgram,
+) -> IrTypeSignature {
+    let signature = ir_program.type_signatures.get(id).item.clone();
+    match signature {
+        IrTypeSignature::Applied(head, items) => {
+            match get_normalized_type_signature(&head, ir_program) {
+                IrTypeSignature::Named(name, type_id, mut head_items) => {
+                    head_items.extend(items);
+                    IrTypeSignature::Named(name, type_id, head_items)
+                }
+                _ => IrTypeSignature::Applied(head, items),
+            }
+        }
+        signature => signature,
+    }
+}
+
+fn type_signature_lists_match(
+    first: &Vec<IrTypeSignatureId>,
+    second: &Vec<IrTypeSignatureId>,
+    ir_program: &IrProgram,
+    arg_map: &mut BTreeMap<usize, usize>,
+) -> bool {
+    first.len() == second.len()
+        && first
+            .iter()
+            .zip(second.iter())
+            .all(|(first, second)| type_signatures_match(first, second, ir_program, arg_map))
+}
+
+// Checks whether the two type signatures are the same, up to the renaming of their type arguments.
+// The arg map records the already paired type arguments.
+pub fn type_signatures_match(
+    first: &IrTypeSignatureId,
+    second: &IrTypeSignatureId,
+    ir_program: &IrProgram,
+    arg_map: &mut BTreeMap<usize, usize>,
+) -> bool {
+    let first = get_normalized_type_signature(first, ir_program);
+    let second = get_normalized_type_signature(second, ir_program);
+    match (&first, &second) {
+        (IrTypeSignature::Tuple(items1), IrTypeSignature::Tuple(items2)) => {
+            type_signature_lists_match(items1, items2, ir_program, arg_map)
+        }
+        (IrTypeSignature::Function(from1, to1), IrTypeSignature::Function(from2, to2)) => {
+            type_signatures_match(from1, from2, ir_program, arg_map)
+                && type_signatures_match(to1, to2, ir_program, arg_map)
+        }
+        (
+            IrTypeSignature::TypeArgument(index1, _, constraints1),
+            IrTypeSignature::TypeArgument(index2, _, constraints2),
+        ) => {
+            let mut constraints1 = constraints1.clone();
+            let mut constraints2 = constraints2.clone();
+            constraints1.sort();
+            constraints1.dedup();
+            constraints2.sort();
+            constraints2.dedup();
+            if constraints1 != constraints2 {
+                return false;
+            }
+            match arg_map.get(index1) {
+                Some(index) => index == index2,
+                None => {
+                    if arg_map.values().any(|index| index == index2) {
+                        return false;
+                    }
+                    arg_map.insert(*index1, *index2);
+                    true
+                }
+            }
+        }
+        (IrTypeSignature::Applied(head1, items1), IrTypeSignature::Applied(head2, items2)) => {
+            type_signatures_match(head1, head2, ir_program, arg_map)
+                && type_signature_lists_match(items1, items2, ir_program, arg_map)
+        }
+        (
+            IrTypeSignature::Named(_, type_id1, items1),
+            IrTypeSignature::Named(_, type_id2, items2),
+        ) => {
+            type_id1 == type_id2 && type_signature_lists_match(items1, items2, ir_program, arg_map)
+        }
+        (IrTypeSignature::Ref(item1), IrTypeSignature::Ref(item2)) => {
+            type_signatures_match(item1, item2, ir_program, arg_map)
+        }
+        (IrTypeSignature::Wildcard, IrTypeSignature::Wildcard) => true,
+        (IrTypeSignature::Never, IrTypeSignature::Never) => true,
+        _ => false,
+    }
+}
+
+fn format_type_signature_inner(
+    id: &IrTypeSignatureId,
+    need_parens: bool,
+    ir_program: &IrProgram,
+    constraints: &mut Vec<String>,
+) -> String {
+    let format_items =
+        |items: &Vec<IrTypeSignatureId>, constraints: &mut Vec<String>| -> Vec<String> {
+            items
+                .iter()
+                .map(|item| format_type_signature_inner(item, true, ir_program, constraints))
+                .collect()
+        };
+    let (s, compound) = match get_normalized_type_signature(id, ir_program) {
+        IrTypeSignature::Tuple(items) => {
+            let items: Vec<_> = items
+                .iter()
+                .map(|item| format_type_signature_inner(item, false, ir_program, constraints))
+                .collect();
+            (format!("({})", items.join(", ")), false)
+        }
+        IrTypeSignature::Function(from, to) => {
+            let from = format_type_signature_inner(&from, true, ir_program, constraints);
+            let to = format_type_signature_inner(&to, false, ir_program, constraints);
+            (format!("{} -> {}", from, to), true)
+        }
+        IrTypeSignature::TypeArgument(_, name, classes) => {
+            for class_id in classes {
+                let class = ir_program.classes.get(&class_id);
+                let constraint = format!("{} {}", class.name, name);
+                if !constraints.contains(&constraint) {
+                    constraints.push(constraint);
+                }
+            }
+            (name, false)
+        }
+        IrTypeSignature::Applied(head, items) => {
+            let head = format_type_signature_inner(&head, true, ir_program, constraints);
+            let items = format_items(&items, constraints);
+            (format!("{} {}", head, items.join(" ")), true)
+        }
+        IrTypeSignature::Named(name, type_id, items) => {
+            let (module, _) = ir_program.get_module_and_name(type_id);
+            if module == LIST_MODULE_NAME && name == LIST_TYPE_NAME && items.len() == 1 {
+                let item = format_type_signature_inner(&items[0], false, ir_program, constraints);
+                (format!("[{}]", item), false)
+            } else if items.is_empty() {
+                (name, false)
+            } else {
+                let items = format_items(&items, constraints);
+                (format!("{} {}", name, items.join(" ")), true)
+            }
+        }
+        IrTypeSignature::Variant(name, _) => (name, false),
+        IrTypeSignature::Ref(item) => {
+            let item = format_type_signature_inner(&item, true, ir_program, constraints);
+            (format!("&{}", item), false)
+        }
+        IrTypeSignature::Wildcard => (format!("_"), false),
+        IrTypeSignature::Never => (format!("!"), false),
+    };
+    if need_parens && compound {
+        format!("({})", s)
+    } else {
+        s
+    }
+}
+
+pub fn format_type_signature(id: &IrTypeSignatureId, ir_program: &IrProgram) -> String {
+    let mut constraints = Vec::new();
+    let s = format_type_signature_inner(id, false, ir_program, &mut constraints);
+    if constraints.is_empty() {
+        s
+    } else {
+        format!("({}) => {}", constraints.join(", "), s)
+    }
+}
+
 pub fn collect_type_args(
     type_signature_id: &TypeSignatureId,
     program: &Program,
@@ -179,6 +365,12 @@ pub fn collect_type_args(
         AstTypeSignature::TypeArg(name) => {
             type_args.insert(name.clone(), info.location_id);
         }
+        AstTypeSignature::AppliedTypeArg(name, items) => {
+            type_args.insert(name.clone(), info.location_id);
+            for item in items {
+                collect_type_args(item, program, type_args);
+            }
+        }
         AstTypeSignature::Variant(_, items) => {
             for item in items {
                 collect_type_args(item, program, type_args);
@@ -192,18 +384,181 @@ pub fn collect_type_args(
     }
 }
 
+pub fn get_type_arg_arity(
+    type_signature_id: &TypeSignatureId,
+    program: &Program,
+    type_arg: &str,
+) -> Option<usize> {
+    let info = program.type_signatures.get(type_signature_id);
+    let items = match &info.item {
+        AstTypeSignature::Function(from, to) => vec![*from, *to],
+        AstTypeSignature::Named(_, items) => items.clone(),
+        AstTypeSignature::Tuple(items) => items.clone(),
+        AstTypeSignature::TypeArg(name) => {
+            if name == type_arg {
+                return Some(0);
+            }
+            Vec::new()
+        }
+        AstTypeSignature::AppliedTypeArg(name, items) => {
+            if name == type_arg {
+                return Some(items.len());
+            }
+            items.clone()
+        }
+        AstTypeSignature::Variant(_, items) => items.clone(),
+        AstTypeSignature::Wildcard => Vec::new(),
+        AstTypeSignature::Never => Vec::new(),
+        AstTypeSignature::Ref(item) => vec![*item],
+    };
+    items
+        .iter()
+        .filter_map(|item| get_type_arg_arity(item, program, type_arg))
+        .next()
+}
+
+fn get_type_arg_count(name: &str, module: &Module, ir_program: &IrProgram) -> Option<usize> {
+    let items = module.imported_items.get(name)?;
+    let item = ImportedItemInfo::resolve_ambiguity(items, Namespace::Type)?;
+    match item.item {
+        Item::Adt(_, ir_typedef_id) => Some(
+            ir_program
+                .typedefs
+                .get(&ir_typedef_id)
+                .get_adt()
+                .type_args
+                .len(),
+        ),
+        Item::Record(_, ir_typedef_id) => Some(
+            ir_program
+                .typedefs
+                .get(&ir_typedef_id)
+                .get_record()
+                .type_args
+                .len(),
+        ),
+        _ => None,
+    }
+}
+
+pub fn process_instance_type_signature(
+    type_signature_id: &TypeSignatureId,
+    class_name: &String,
+    class_arity: usize,
+    program: &Program,
+    ir_program: &mut IrProgram,
+    module: &Module,
+    type_arg_resolver: &mut TypeArgResolver,
+    errors: &mut Vec<ResolverError>,
+) -> Option<IrTypeSignatureId> {
+    if class_arity == 0 {
+        return process_type_signature(
+            type_signature_id,
+            program,
+            ir_program,
+            module,
+            type_arg_resolver,
+            errors,
+        );
+    }
+    // instances of higher kinded classes are given type constructors
+    // missing their last class_arity args, e.g. instance Functor Option
+    let info = program.type_signatures.get(type_signature_id);
+    if let AstTypeSignature::Named(name, named_args) = &info.item {
+        match get_type_arg_count(name, module, ir_program) {
+            Some(count) if count != named_args.len() + class_arity => {}
+            _ => {
+                return process_named_type(
+                    name,
+                    named_args,
+                    class_arity,
+                    info.location_id,
+                    program,
+                    ir_program,
+                    module,
+                    type_arg_resolver,
+                    errors,
+                );
+            }
+        }
+    }
+    let err = ResolverError::InvalidInstanceKind(class_name.clone(), class_arity, info.location_id);
+    errors.push(err);
+    None
+}
+
+// a type argument used several times in a signature is reported only once
+fn add_kind_mismatch(
+    name: &String,
+    expected: usize,
+    found: usize,
+    location_id: LocationId,
+    type_arg_resolver: &mut TypeArgResolver,
+    errors: &mut Vec<ResolverError>,
+) {
+    if type_arg_resolver.mark_kind_mismatch(name) {
+        let err = ResolverError::KindMismatch(name.clone(), expected, found, location_id);
+        errors.push(err);
+    }
+}
+
+fn resolve_type_arg(
+    name: &String,
+    arity: usize,
+    location_id: LocationId,
+    ir_program: &IrProgram,
+    type_arg_resolver: &mut TypeArgResolver,
+    errors: &mut Vec<ResolverError>,
+) -> Option<IrTypeSignature> {
+    if let Some(info) = type_arg_resolver.resolve_arg(name) {
+        if let Err(expected) = type_arg_resolver.check_arity(name, arity) {
+            add_kind_mismatch(
+                name,
+                expected,
+                arity,
+                location_id,
+                type_arg_resolver,
+                errors,
+            );
+            return None;
+        }
+        for class_id in &info.constraints {
+            let class = ir_program.classes.get(class_id);
+            if class.arg_arities[0] != arity {
+                add_kind_mismatch(
+                    name,
+                    class.arg_arities[0],
+                    arity,
+                    location_id,
+                    type_arg_resolver,
+                    errors,
+                );
+                return None;
+            }
+        }
+        Some(IrTypeSignature::TypeArgument(
+            info.index,
+            name.clone(),
+            info.constraints,
+        ))
+    } else {
+        let error = ResolverError::UnknownTypeArg(name.clone(), location_id);
+        errors.push(error);
+        None
+    }
+}
+
 pub fn process_class_type_signature(
     type_signature_id: &TypeSignatureId,
     program: &Program,
     ir_program: &mut IrProgram,
     type_arg_resolver: &mut TypeArgResolver,
     errors: &mut Vec<ResolverError>,
-    class_id: ClassId,
+    constraints: Vec<ClassId>,
 ) -> Option<(IrTypeSignatureId, String)> {
     let info = program.type_signatures.get(type_signature_id);
     match &info.item {
         AstTypeSignature::TypeArg(name) => {
-            let constraints = vec![class_id];
             let index =
                 type_arg_resolver.add_explicit(name.clone(), constraints.clone(), info.location_id);
             let ir_type_signature = IrTypeSignature::TypeArgument(index, name.clone(), constraints);
@@ -220,6 +575,54 @@ pub fn process_class_type_signature(
     }
 }
 
+pub fn process_multi_param_constraint(
+    args: &Vec<String>,
+    class_id: ClassId,
+    location_id: LocationId,
+    ir_program: &mut IrProgram,
+    type_arg_resolver: &mut TypeArgResolver,
+    errors: &mut Vec<ResolverError>,
+) -> Option<MultiParamConstraint> {
+    let class = ir_program.classes.get(&class_id).clone();
+    let mut items = Vec::new();
+    for (arg, arity) in args.iter().zip(class.arg_arities.iter()) {
+        let info = match type_arg_resolver.get_arg(arg) {
+            Some(info) => info,
+            None => {
+                let err =
+                    ResolverError::InvalidArgumentInTypeClassConstraint(arg.clone(), location_id);
+                errors.push(err);
+                return None;
+            }
+        };
+        if let Err(expected) = type_arg_resolver.check_arity(arg, *arity) {
+            add_kind_mismatch(
+                arg,
+                expected,
+                *arity,
+                location_id,
+                type_arg_resolver,
+                errors,
+            );
+            return None;
+        }
+        let ir_type_signature =
+            IrTypeSignature::TypeArgument(info.index, arg.clone(), info.constraints);
+        let id = ir_program.type_signatures.get_id();
+        let type_info = ItemInfo::new(ir_type_signature, location_id);
+        ir_program.type_signatures.add_item(id, type_info);
+        items.push(id);
+    }
+    let id = ir_program.type_signatures.get_id();
+    let type_info = ItemInfo::new(IrTypeSignature::Tuple(items), location_id);
+    ir_program.type_signatures.add_item(id, type_info);
+    Some(MultiParamConstraint {
+        class_id: class_id,
+        type_signature: id,
+        location_id: location_id,
+    })
+}
+
 pub fn process_type_signature(
     type_signature_id: &TypeSignatureId,
     program: &Program,
@@ -233,13 +636,49 @@ pub fn process_type_signature(
     let location_id = info.location_id;
     let ir_type_signature = match type_signature {
         AstTypeSignature::TypeArg(name) => {
-            if let Some(info) = type_arg_resolver.resolve_arg(name) {
-                IrTypeSignature::TypeArgument(info.index, name.clone(), info.constraints)
-            } else {
-                let error = ResolverError::UnknownTypeArg(name.clone(), location_id);
-                errors.push(error);
-                return None;
+            match resolve_type_arg(name, 0, location_id, ir_program, type_arg_resolver, errors) {
+                Some(ir_type_signature) => ir_type_signature,
+                None => {
+                    return None;
+                }
+            }
+        }
+        AstTypeSignature::AppliedTypeArg(name, items) => {
+            let head = match resolve_type_arg(
+                name,
+                items.len(),
+                location_id,
+                ir_program,
+                type_arg_resolver,
+                errors,
+            ) {
+                Some(head) => head,
+                None => {
+                    return None;
+                }
+            };
+            let head_id = ir_program.type_signatures.get_id();
+            let head_info = ItemInfo::new(head, location_id);
+            ir_program.type_signatures.add_item(head_id, head_info);
+            let mut item_ids = Vec::new();
+            for item in items {
+                match process_type_signature(
+                    item,
+                    program,
+                    ir_program,
+                    module,
+                    type_arg_resolver,
+                    errors,
+                ) {
+                    Some(id) => {
+                        item_ids.push(id);
+                    }
+                    None => {
+                        return None;
+                    }
+                }
             }
+            IrTypeSignature::Applied(head_id, item_ids)
         }
         AstTypeSignature::Variant(name, items) => {
             let mut item_ids = Vec::new();
@@ -266,6 +705,7 @@ pub fn process_type_signature(
             return process_named_type(
                 name,
                 named_args,
+                0,
                 location_id,
                 program,
                 ir_program,
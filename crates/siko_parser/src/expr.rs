@@ -124,7 +124,7 @@ fn parse_do(parser: &mut Parser) -> Result<ExprId, ParseError> {
 
 fn parse_tuple_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
     let start_index = parser.get_index();
-    let res = parse_parens(parser, |p| parse_pattern(p), "<pattern>")?;
+    let res = parse_parens(parser, |p| parse_cons_pattern(p), "<pattern>")?;
     match res {
         ParenParseResult::Single(t) => {
             return Ok(t);
@@ -137,6 +137,28 @@ fn parse_tuple_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
     }
 }
 
+fn parse_list_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
+    let start_index = parser.get_index();
+    parser.expect(TokenKind::LBracket)?;
+    let mut items = Vec::new();
+    loop {
+        if parser.current(TokenKind::RBracket) {
+            break;
+        }
+        let item = parse_cons_pattern(parser)?;
+        items.push(item);
+        if parser.current(TokenKind::Comma) {
+            parser.expect(TokenKind::Comma)?;
+        } else {
+            break;
+        }
+    }
+    parser.expect(TokenKind::RBracket)?;
+    let pattern = Pattern::List(items);
+    let id = parser.add_pattern(pattern, start_index);
+    Ok(id)
+}
+
 fn parse_record_field_pattern(parser: &mut Parser) -> Result<RecordFieldPattern, ParseError> {
     let start_index = parser.get_index();
     let field_name = parser.var_identifier("field name")?;
@@ -156,21 +178,53 @@ fn parse_record_field_pattern(parser: &mut Parser) -> Result<RecordFieldPattern,
     }
 }
 
+fn parse_integer_pattern_bound(parser: &mut Parser) -> Result<i64, ParseError> {
+    let negative = parser.consume_op(&[BuiltinOperator::Sub]).is_some();
+    if parser.current_kind() == TokenKind::IntegerLiteral {
+        let literal = parser.advance()?;
+        if let Token::IntegerLiteral(i) = literal.token {
+            if negative {
+                Ok(-i)
+            } else {
+                Ok(i)
+            }
+        } else {
+            unreachable!()
+        }
+    } else {
+        report_unexpected_token(parser, format!("integer literal"))
+    }
+}
+
 fn parse_sub_pattern(parser: &mut Parser, inner: bool) -> Result<Option<PatternId>, ParseError> {
     let id = match parser.current_kind() {
         TokenKind::LParen => {
             let id = parse_tuple_pattern(parser)?;
             id
         }
-        TokenKind::IntegerLiteral => {
+        TokenKind::LBracket => {
+            let id = parse_list_pattern(parser)?;
+            id
+        }
+        TokenKind::IntegerLiteral | TokenKind::Op(BuiltinOperator::Sub) => {
             let start_index = parser.get_index();
-            let literal = parser.advance()?;
-            if let Token::IntegerLiteral(i) = literal.token {
-                let pattern = Pattern::IntegerLiteral(i);
+            let i = parse_integer_pattern_bound(parser)?;
+            if parser.current(TokenKind::DoubleDot) {
+                parser.expect(TokenKind::DoubleDot)?;
+                let i2 = parse_integer_pattern_bound(parser)?;
+                let pattern = Pattern::IntegerRange(i, i2, RangeKind::Exclusive);
+                let id = parser.add_pattern(pattern, start_index);
+                id
+            } else if parser.current(TokenKind::InclusiveRange) {
+                parser.expect(TokenKind::InclusiveRange)?;
+                let i2 = parse_integer_pattern_bound(parser)?;
+                let pattern = Pattern::IntegerRange(i, i2, RangeKind::Inclusive);
                 let id = parser.add_pattern(pattern, start_index);
                 id
             } else {
-                unreachable!()
+                let pattern = Pattern::IntegerLiteral(i);
+                let id = parser.add_pattern(pattern, start_index);
+                id
             }
         }
         TokenKind::CharLiteral => {
@@ -285,6 +339,22 @@ pub fn parse_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
     }
 }
 
+// head :: tail, the type annotation of a bind cannot follow a cons pattern
+// so it is only parsed in case branches and inside of parens and brackets
+fn parse_cons_pattern(parser: &mut Parser) -> Result<PatternId, ParseError> {
+    let start_index = parser.get_index();
+    let head = parse_pattern(parser)?;
+    if parser.current(TokenKind::KeywordDoubleColon) {
+        parser.expect(TokenKind::KeywordDoubleColon)?;
+        let tail = parse_cons_pattern(parser)?;
+        let pattern = Pattern::Cons(head, tail);
+        let id = parser.add_pattern(pattern, start_index);
+        Ok(id)
+    } else {
+        Ok(head)
+    }
+}
+
 fn parse_case(parser: &mut Parser) -> Result<ExprId, ParseError> {
     let start_index = parser.get_index();
     parser.expect(TokenKind::KeywordCase)?;
@@ -295,7 +365,7 @@ fn parse_case(parser: &mut Parser) -> Result<ExprId, ParseError> {
         let start_index = parser.get_index();
         let mut sub_patterns = Vec::new();
         loop {
-            let pattern_id = parse_pattern(parser)?;
+            let pattern_id = parse_cons_pattern(parser)?;
             sub_patterns.push(pattern_id);
             if parser.current(TokenKind::Pipe) {
                 parser.expect(TokenKind::Pipe)?;
@@ -346,6 +416,17 @@ fn parse_if(parser: &mut Parser) -> Result<ExprId, ParseError> {
     Ok(id)
 }
 
+fn parse_let(parser: &mut Parser) -> Result<ExprId, ParseError> {
+    let start_index = parser.get_index();
+    parser.expect(TokenKind::KeywordLet)?;
+    let functions = parser.parse_local_functions()?;
+    parser.expect(TokenKind::KeywordIn)?;
+    let body = parser.parse_expr(false)?;
+    let expr = Expr::Let(functions, body);
+    let id = parser.add_expr(expr, start_index);
+    Ok(id)
+}
+
 fn parse_loop(parser: &mut Parser) -> Result<ExprId, ParseError> {
     let start_index = parser.get_index();
     parser.expect(TokenKind::KeywordLoop)?;
@@ -433,6 +514,12 @@ fn parse_arg(parser: &mut Parser) -> Result<ExprId, ParseError> {
             let id = parser.add_expr(expr, start_index);
             id
         }
+        Token::Hole(name) => {
+            parser.advance()?;
+            let expr = Expr::Hole(name);
+            let id = parser.add_expr(expr, start_index);
+            id
+        }
         Token::StringLiteral(s) => {
             parser.advance()?;
             if parser.current(TokenKind::Formatter) {
@@ -477,6 +564,9 @@ fn parse_arg(parser: &mut Parser) -> Result<ExprId, ParseError> {
             let id = parser.add_expr(expr, start_index);
             id
         }
+        Token::KeywordLet => {
+            return parse_let(parser);
+        }
         Token::KeywordLoop => {
             return parse_loop(parser);
         }
@@ -514,6 +604,7 @@ fn parse_primary(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, P
             | TokenKind::FloatLiteral
             | TokenKind::StringLiteral
             | TokenKind::CharLiteral
+            | TokenKind::Hole
             | TokenKind::LParen
             | TokenKind::KeywordIf
             | TokenKind::LBracket
@@ -625,6 +716,42 @@ fn parse_ord_ops(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, P
             BuiltinOperator::GreaterOrEqualThan,
         ],
         for_initializer,
+        parse_bitor,
+    );
+}
+
+fn parse_bitor(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, ParseError> {
+    return parse_binary_op(
+        parser,
+        &[BuiltinOperator::BitOr],
+        for_initializer,
+        parse_bitxor,
+    );
+}
+
+fn parse_bitxor(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, ParseError> {
+    return parse_binary_op(
+        parser,
+        &[BuiltinOperator::BitXor],
+        for_initializer,
+        parse_bitand,
+    );
+}
+
+fn parse_bitand(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, ParseError> {
+    return parse_binary_op(
+        parser,
+        &[BuiltinOperator::BitAnd],
+        for_initializer,
+        parse_shift,
+    );
+}
+
+fn parse_shift(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, ParseError> {
+    return parse_binary_op(
+        parser,
+        &[BuiltinOperator::ShiftLeft, BuiltinOperator::ShiftRight],
+        for_initializer,
         parse_addsub,
     );
 }
@@ -641,7 +768,12 @@ fn parse_addsub(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, Pa
 fn parse_muldiv(parser: &mut Parser, for_initializer: bool) -> Result<ExprId, ParseError> {
     return parse_binary_op(
         parser,
-        &[BuiltinOperator::Mul, BuiltinOperator::Div],
+        &[
+            BuiltinOperator::Mul,
+            BuiltinOperator::Div,
+            BuiltinOperator::Mod,
+            BuiltinOperator::Rem,
+        ],
         for_initializer,
         parse_pipe_forward,
     );
@@ -0,0 +1,556 @@
+use crate::token::Comment;
+use crate::token::TokenInfo;
+use crate::token::TokenKind;
+use siko_constants::BuiltinOperator;
+
+const INDENT: usize = 4;
+
+// The layout of a file as the lexer sees it, every where/do/of/let is followed by a
+// block and every item of a block ends with an EndOfItem.
+enum Node {
+    Token(usize),
+    Block(Vec<Item>),
+}
+
+struct Item {
+    nodes: Vec<Node>,
+}
+
+fn is_block_opener(kind: TokenKind) -> bool {
+    match kind {
+        TokenKind::KeywordWhere
+        | TokenKind::KeywordDo
+        | TokenKind::KeywordOf
+        | TokenKind::KeywordLet => true,
+        _ => false,
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_operator_char(c: char) -> bool {
+    "|><&*+-/=!\\:.^".contains(c)
+}
+
+fn build_item(tokens: &[TokenInfo], index: &mut usize) -> Item {
+    let mut nodes = Vec::new();
+    while *index < tokens.len() {
+        let kind = tokens[*index].token.kind();
+        match kind {
+            TokenKind::EndOfItem => {
+                *index += 1;
+                break;
+            }
+            TokenKind::EndOfBlock | TokenKind::EndOfModule => {
+                break;
+            }
+            _ => {
+                nodes.push(Node::Token(*index));
+                *index += 1;
+                if is_block_opener(kind) {
+                    nodes.push(Node::Block(build_block(tokens, index)));
+                }
+            }
+        }
+    }
+    Item { nodes: nodes }
+}
+
+fn build_block(tokens: &[TokenInfo], index: &mut usize) -> Vec<Item> {
+    let mut items = Vec::new();
+    while *index < tokens.len() {
+        match tokens[*index].token.kind() {
+            TokenKind::EndOfBlock => {
+                *index += 1;
+                break;
+            }
+            TokenKind::EndOfModule => {
+                break;
+            }
+            _ => {
+                items.push(build_item(tokens, index));
+            }
+        }
+    }
+    items
+}
+
+fn first_token(nodes: &[Node]) -> Option<usize> {
+    for node in nodes {
+        match node {
+            Node::Token(index) => return Some(*index),
+            Node::Block(items) => {
+                for item in items {
+                    if let Some(index) = first_token(&item.nodes) {
+                        return Some(index);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn last_token(nodes: &[Node]) -> Option<usize> {
+    for node in nodes.iter().rev() {
+        match node {
+            Node::Token(index) => return Some(*index),
+            Node::Block(items) => {
+                for item in items.iter().rev() {
+                    if let Some(index) = last_token(&item.nodes) {
+                        return Some(index);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn collect_tokens(nodes: &[Node], result: &mut Vec<usize>) {
+    for node in nodes {
+        match node {
+            Node::Token(index) => result.push(*index),
+            Node::Block(items) => {
+                for item in items {
+                    collect_tokens(&item.nodes, result);
+                }
+            }
+        }
+    }
+}
+
+struct Printer<'a> {
+    source: Vec<Vec<char>>,
+    tokens: &'a [TokenInfo],
+    comments: &'a [Comment],
+    next_comment: usize,
+    output: Vec<String>,
+    current: String,
+    last_token: Option<usize>,
+    last_source_line: Option<usize>,
+    after_comment: bool,
+    force_break: bool,
+}
+
+impl<'a> Printer<'a> {
+    fn new(source: &str, tokens: &'a [TokenInfo], comments: &'a [Comment]) -> Printer<'a> {
+        Printer {
+            source: source
+                .split('\n')
+                .map(|line| line.chars().collect())
+                .collect(),
+            tokens: tokens,
+            comments: comments,
+            next_comment: 0,
+            output: Vec::new(),
+            current: String::new(),
+            last_token: None,
+            last_source_line: None,
+            after_comment: false,
+            force_break: false,
+        }
+    }
+
+    fn text(&self, index: usize) -> String {
+        let location = &self.tokens[index].location;
+        let line = &self.source[location.line];
+        line[location.span.start..location.span.end]
+            .iter()
+            .collect()
+    }
+
+    fn line_of(&self, index: usize) -> usize {
+        self.tokens[index].location.line
+    }
+
+    fn column(&self) -> usize {
+        self.current.chars().count()
+    }
+
+    fn current_indent(&self) -> usize {
+        self.current.chars().take_while(|c| *c == ' ').count()
+    }
+
+    fn is_line_empty(&self) -> bool {
+        self.current.trim().is_empty()
+    }
+
+    // returns the next comment if it comes before the given token
+    fn pending_comment(&self, index: Option<usize>) -> Option<&'a Comment> {
+        let comment = self.comments.get(self.next_comment)?;
+        match index {
+            Some(index) => {
+                let location = &self.tokens[index].location;
+                let comment_position = (comment.location.line, comment.location.span.start);
+                if comment_position < (location.line, location.span.start) {
+                    Some(comment)
+                } else {
+                    None
+                }
+            }
+            None => Some(comment),
+        }
+    }
+
+    fn has_comment_between(&self, start: usize, end: usize) -> bool {
+        let start = &self.tokens[start].location;
+        let end = &self.tokens[end].location;
+        self.comments.iter().any(|comment| {
+            let position = (comment.location.line, comment.location.span.start);
+            (start.line, start.span.start) < position && position < (end.line, end.span.start)
+        })
+    }
+
+    fn finish_line(&mut self) {
+        if !self.is_line_empty() {
+            let line = self.current.trim_end().to_string();
+            self.output.push(line);
+        }
+        self.current.clear();
+        self.after_comment = false;
+    }
+
+    // at most one of the empty lines between two lines of the source is kept
+    fn new_line(&mut self, indent: usize, source_line: usize) {
+        self.finish_line();
+        if let Some(last) = self.last_source_line {
+            if source_line > last + 1 && !self.output.is_empty() {
+                self.output.push(String::new());
+            }
+        }
+        self.current = " ".repeat(indent);
+    }
+
+    fn add_comment(&mut self, comment: &Comment) {
+        let mut lines = comment.text.lines();
+        if let Some(first) = lines.next() {
+            self.current.push_str(first.trim_end());
+        }
+        for line in lines {
+            let prev = std::mem::replace(&mut self.current, line.trim_end().to_string());
+            self.output.push(prev.trim_end().to_string());
+        }
+        let line_count = comment.text.lines().count().max(1);
+        self.last_source_line = Some(comment.location.line + line_count - 1);
+        self.after_comment = true;
+        if line_count > 1 || !comment.text.starts_with("/*") {
+            self.force_break = true;
+        }
+        self.next_comment += 1;
+    }
+
+    fn add_comments(&mut self, index: Option<usize>, indent: usize) {
+        // comments on the line of the previous token stay at the end of that line
+        while let Some(comment) = self.pending_comment(index) {
+            let trailing = match self.last_token {
+                Some(last) => !self.is_line_empty() && self.line_of(last) == comment.location.line,
+                None => false,
+            };
+            if !trailing {
+                break;
+            }
+            self.current.push(' ');
+            self.add_comment(comment);
+        }
+        while let Some(comment) = self.pending_comment(index) {
+            self.new_line(indent, comment.location.line);
+            self.add_comment(comment);
+        }
+    }
+
+    fn needs_space(&self, prev: usize, next: usize) -> bool {
+        let prev_kind = self.tokens[prev].token.kind();
+        let next_kind = self.tokens[next].token.kind();
+        let space = match (prev_kind, next_kind) {
+            (_, TokenKind::RParen) | (_, TokenKind::RBracket) | (_, TokenKind::Comma) => false,
+            (TokenKind::LParen, _) | (TokenKind::LBracket, _) => false,
+            (TokenKind::Dot, _) | (_, TokenKind::Dot) => false,
+            (TokenKind::DoubleDot, _) | (_, TokenKind::DoubleDot) => false,
+            (TokenKind::InclusiveRange, _) | (_, TokenKind::InclusiveRange) => false,
+            (TokenKind::Lambda, _) => false,
+            (TokenKind::LCurly, TokenKind::RCurly) => false,
+            (TokenKind::Op(BuiltinOperator::Not), _) => false,
+            (TokenKind::Op(BuiltinOperator::Sub), _) | (TokenKind::Ampersand, _) => {
+                !self.is_prefix(prev)
+            }
+            _ => true,
+        };
+        if space {
+            return true;
+        }
+        // tokens that would be lexed as one token are kept apart
+        let prev_text = self.text(prev);
+        let next_text = self.text(next);
+        match (prev_text.chars().last(), next_text.chars().next()) {
+            (Some(a), Some(b)) => {
+                (is_identifier_char(a) && is_identifier_char(b))
+                    || (is_operator_char(a) && is_operator_char(b))
+            }
+            _ => false,
+        }
+    }
+
+    fn is_prefix(&self, index: usize) -> bool {
+        if index == 0 {
+            return true;
+        }
+        match self.tokens[index - 1].token.kind() {
+            TokenKind::VarIdentifier
+            | TokenKind::TypeIdentifier
+            | TokenKind::StringLiteral
+            | TokenKind::IntegerLiteral
+            | TokenKind::FloatLiteral
+            | TokenKind::CharLiteral
+            | TokenKind::Hole
+            | TokenKind::Wildcard
+            | TokenKind::RParen
+            | TokenKind::RBracket
+            | TokenKind::RCurly => false,
+            _ => true,
+        }
+    }
+
+    fn add_token(&mut self, index: usize, new_line: bool, indent: usize) {
+        self.add_comments(Some(index), indent);
+        if new_line || self.force_break {
+            self.new_line(indent, self.line_of(index));
+            self.force_break = false;
+        } else if self.after_comment {
+            self.current.push(' ');
+        } else if let Some(last) = self.last_token {
+            if self.needs_space(last, index) {
+                self.current.push(' ');
+            }
+        }
+        let text = self.text(index);
+        self.current.push_str(&text);
+        self.last_token = Some(index);
+        self.last_source_line = Some(self.line_of(index));
+        self.after_comment = false;
+    }
+
+    // a record spanning multiple lines is printed with one field per line
+    fn is_multiline_group(&self, nodes: &[Node]) -> bool {
+        let start_line = match nodes.first() {
+            Some(Node::Token(index)) => self.line_of(*index),
+            _ => return false,
+        };
+        let mut multiline = false;
+        let mut depth = 0;
+        for node in nodes {
+            match node {
+                Node::Token(index) => {
+                    if self.line_of(*index) != start_line {
+                        multiline = true;
+                    }
+                    match self.tokens[*index].token.kind() {
+                        TokenKind::LCurly => depth += 1,
+                        TokenKind::RCurly => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return multiline;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Node::Block(_) => return false,
+            }
+        }
+        false
+    }
+
+    fn can_inline(&self, opener: usize, items: &[Item], continuation: usize) -> bool {
+        if items.len() != 1 {
+            return false;
+        }
+        let mut tokens = Vec::new();
+        collect_tokens(&items[0].nodes, &mut tokens);
+        let line = self.line_of(opener);
+        if tokens.iter().any(|index| self.line_of(*index) != line) {
+            return false;
+        }
+        match tokens.last() {
+            Some(last) => {
+                !self.has_comment_between(opener, *last)
+                    && !self.force_break
+                    && self.column() + 1 > continuation
+            }
+            None => false,
+        }
+    }
+
+    fn has_continuation(&self, block_end: Option<usize>, rest: &[Node]) -> bool {
+        for node in rest {
+            match node {
+                Node::Token(index) => {
+                    if self.tokens[*index].token.kind() != TokenKind::RParen {
+                        return true;
+                    }
+                    if let Some(block_end) = block_end {
+                        if self.has_comment_between(block_end, *index) {
+                            return true;
+                        }
+                    }
+                }
+                Node::Block(_) => return true,
+            }
+        }
+        false
+    }
+
+    fn print_inline(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::Token(index) => self.add_token(*index, false, 0),
+                Node::Block(items) => {
+                    for item in items {
+                        self.print_inline(&item.nodes);
+                    }
+                }
+            }
+        }
+    }
+
+    fn print_item(&mut self, item: &Item, indent: usize, module_header: bool) {
+        let mut continuation = indent + INDENT;
+        let mut prev: Option<usize> = None;
+        let mut after_block = false;
+        let mut field_break = None;
+        let mut groups: Vec<Option<usize>> = Vec::new();
+        for (pos, node) in item.nodes.iter().enumerate() {
+            match node {
+                Node::Token(index) => {
+                    let index = *index;
+                    let kind = self.tokens[index].token.kind();
+                    let aligned = groups.iter().any(|group| group.is_some());
+                    let (new_line, line_indent) = match prev {
+                        None => (true, indent),
+                        Some(prev) => {
+                            if after_block {
+                                (kind != TokenKind::RParen, continuation)
+                            } else if let Some(column) = field_break {
+                                (true, column)
+                            } else if aligned {
+                                let column = groups.iter().rev().find_map(|group| *group);
+                                (false, column.unwrap_or(continuation))
+                            } else {
+                                (self.line_of(prev) != self.line_of(index), continuation)
+                            }
+                        }
+                    };
+                    self.add_token(index, new_line, line_indent);
+                    if module_header && pos == 0 {
+                        continuation = self.column() + 1 + INDENT;
+                    }
+                    field_break = None;
+                    match kind {
+                        TokenKind::LParen | TokenKind::LBracket => groups.push(None),
+                        TokenKind::LCurly => {
+                            if self.is_multiline_group(&item.nodes[pos..]) {
+                                groups.push(Some(self.column() + 1));
+                            } else {
+                                groups.push(None);
+                            }
+                        }
+                        TokenKind::RParen | TokenKind::RBracket | TokenKind::RCurly => {
+                            groups.pop();
+                        }
+                        TokenKind::Comma => {
+                            if let Some(Some(column)) = groups.last() {
+                                field_break = Some(*column);
+                            }
+                        }
+                        _ => {}
+                    }
+                    prev = Some(index);
+                    after_block = false;
+                }
+                Node::Block(items) => {
+                    let opener = prev.expect("block without opener");
+                    if module_header {
+                        for item in items {
+                            self.print_item(item, 0, false);
+                        }
+                        after_block = true;
+                    } else if self.can_inline(opener, items, continuation) {
+                        self.print_inline(&items[0].nodes);
+                        prev = last_token(&items[0].nodes);
+                    } else {
+                        let line_indent = self.current_indent();
+                        let block_end = items.last().and_then(|item| last_token(&item.nodes));
+                        let block_indent =
+                            if self.has_continuation(block_end, &item.nodes[pos + 1..]) {
+                                line_indent.max(continuation) + INDENT
+                            } else {
+                                line_indent + INDENT
+                            };
+                        for item in items {
+                            self.print_item(item, block_indent, false);
+                        }
+                        after_block = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.add_comments(None, 0);
+        self.finish_line();
+        let mut result = self.output.join("\n");
+        result.push('\n');
+        result
+    }
+}
+
+pub fn format(source: &str, tokens: &[TokenInfo], comments: &[Comment]) -> String {
+    let mut modules = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let start = index;
+        let module = build_item(tokens, &mut index);
+        if index < tokens.len() {
+            match tokens[index].token.kind() {
+                TokenKind::EndOfBlock | TokenKind::EndOfModule => index += 1,
+                _ => {}
+            }
+        }
+        if first_token(&module.nodes).is_some() {
+            modules.push(module);
+        }
+        if index == start {
+            index += 1;
+        }
+    }
+    let mut printer = Printer::new(source, tokens, comments);
+    for module in &modules {
+        printer.print_item(module, 0, true);
+    }
+    printer.finish()
+}
+
+// Two sources have the same meaning if their tokens and layout markers match.
+pub fn get_token_texts(source: &str, tokens: &[TokenInfo]) -> Vec<String> {
+    let lines: Vec<Vec<char>> = source
+        .split('\n')
+        .map(|line| line.chars().collect())
+        .collect();
+    tokens
+        .iter()
+        .map(|token| match token.token.kind() {
+            TokenKind::EndOfItem | TokenKind::EndOfBlock | TokenKind::EndOfModule => {
+                format!("{:?}", token.token.kind())
+            }
+            _ => {
+                let line = &lines[token.location.line];
+                line[token.location.span.start..token.location.span.end]
+                    .iter()
+                    .collect()
+            }
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 use crate::error::LexerError;
 use crate::error::LocationInfo;
+use crate::token::Comment;
 use crate::token::Token;
 use crate::token::TokenInfo;
 use crate::token::TokenKind;
@@ -16,6 +17,7 @@ pub struct Lexer {
     input: Vec<char>,
     line_offset: usize,
     tokens: Vec<TokenInfo>,
+    comments: Vec<Comment>,
 }
 
 impl Lexer {
@@ -27,6 +29,7 @@ impl Lexer {
             input: input.chars().collect(),
             line_offset: 0,
             tokens: Vec::new(),
+            comments: Vec::new(),
         }
     }
 
@@ -85,7 +88,9 @@ impl Lexer {
 
     fn is_operator(c: char) -> bool {
         match c {
-            '|' | '>' | '<' | '&' | '*' | '+' | '-' | '/' | '=' | '!' | '\\' | ':' | '.' => true,
+            '|' | '>' | '<' | '&' | '*' | '+' | '-' | '/' | '=' | '!' | '\\' | ':' | '.' | '^' => {
+                true
+            }
             _ => false,
         }
     }
@@ -143,6 +148,10 @@ impl Lexer {
             "loop" => Token::KeywordLoop,
             "continue" => Token::KeywordContinue,
             "break" => Token::KeywordBreak,
+            "let" => Token::KeywordLet,
+            "in" => Token::KeywordIn,
+            "mod" => Token::Op(BuiltinOperator::Mod),
+            "rem" => Token::Op(BuiltinOperator::Rem),
             _ => {
                 let first = identifier.chars().next().expect("empty identifer");
                 if first.is_uppercase() {
@@ -156,8 +165,39 @@ impl Lexer {
         Ok(())
     }
 
+    fn collect_hole(&mut self) -> Result<(), LexerError> {
+        let start = self.line_offset;
+        self.advance()?;
+        let (name, span) = self.collect(|c| Lexer::is_identifier(c))?;
+        let span = Span {
+            start: start,
+            end: span.end,
+        };
+        self.add_token(Token::Hole(name), span);
+        Ok(())
+    }
+
     fn collect_operator(&mut self) -> Result<(), LexerError> {
         let (operator, span) = self.collect(Lexer::is_operator)?;
+        if operator == "..-" || operator == "..=-" {
+            // a range followed by a negative bound
+            let range_span = Span {
+                start: span.start,
+                end: span.end - 1,
+            };
+            let range = if operator == "..-" {
+                Token::DoubleDot
+            } else {
+                Token::InclusiveRange
+            };
+            self.add_token(range, range_span);
+            let minus_span = Span {
+                start: span.end - 1,
+                end: span.end,
+            };
+            self.add_token(Token::Op(BuiltinOperator::Sub), minus_span);
+            return Ok(());
+        }
         let t = match operator.as_ref() {
             "+" => Token::Op(BuiltinOperator::Add),
             "-" => Token::Op(BuiltinOperator::Sub),
@@ -185,6 +225,9 @@ impl Lexer {
             ":" => Token::Colon,
             "=>" => Token::KeywordConstraint,
             "&" => Token::Ampersand,
+            "^" => Token::Op(BuiltinOperator::BitXor),
+            "<<" => Token::Op(BuiltinOperator::ShiftLeft),
+            ">>" => Token::Op(BuiltinOperator::ShiftRight),
             _ => {
                 return Err(LexerError::General(
                     format!("Unsupported operator {}", operator),
@@ -333,6 +376,22 @@ impl Lexer {
         Ok(())
     }
 
+    fn add_comment(&mut self, start_index: usize, start_line: usize, start_offset: usize) {
+        let text: String = self.input[start_index..self.index].iter().collect();
+        let first_line_len = text.lines().next().map(|l| l.chars().count()).unwrap_or(0);
+        self.comments.push(Comment {
+            text: text,
+            location: Location::new(
+                start_line,
+                Span::new(start_offset, start_offset + first_line_len),
+            ),
+        });
+    }
+
+    pub fn get_comments(&self) -> &[Comment] {
+        &self.comments[..]
+    }
+
     fn process_line_comment(&mut self) -> Result<(), LexerError> {
         while !self.is_done() {
             let c = self.peek()?;
@@ -392,22 +451,27 @@ impl Lexer {
             if Lexer::is_identifier(c) {
                 self.collect_identifier()?;
             } else if Lexer::is_operator(c) {
+                let (start_index, start_line, start_offset) =
+                    (self.index, self.line_index, self.line_offset);
                 match self.peek_next() {
                     Some(next_char) => match (c, next_char) {
                         ('/', '/') | ('-', '-') => {
                             self.process_line_comment()?;
+                            self.add_comment(start_index, start_line, start_offset);
                             continue;
                         }
                         ('/', '*') => {
                             self.advance()?;
                             self.advance()?;
                             self.process_block_comment(('*', '/'))?;
+                            self.add_comment(start_index, start_line, start_offset);
                             continue;
                         }
                         ('{', '-') => {
                             self.advance()?;
                             self.advance()?;
                             self.process_block_comment(('-', '}'))?;
+                            self.add_comment(start_index, start_line, start_offset);
                             continue;
                         }
                         _ => {}
@@ -419,6 +483,8 @@ impl Lexer {
                 self.collect_string_literal()?;
             } else if c == '\'' {
                 self.collect_char_literal()?;
+            } else if c == '?' {
+                self.collect_hole()?;
             } else {
                 let span = Span::single(self.line_offset);
                 let t = match c {
@@ -629,6 +695,11 @@ fn process_item(
         if info.token.kind() == TokenKind::KeywordModule {
             return Ok(true);
         }
+        // an in closes every block up to its let, even on the same line
+        if info.token.kind() == TokenKind::KeywordIn && paren_level == 0 && !module {
+            iterator.add_end(Token::EndOfItem);
+            return Ok(true);
+        }
         if info.token.kind() == TokenKind::LParen {
             paren_level += 1;
         }
@@ -645,6 +716,14 @@ fn process_item(
         {
             iterator.advance();
             process_block(iterator, info, false, file_path)?;
+        } else if info.token.kind() == TokenKind::KeywordLet {
+            iterator.advance();
+            process_block(iterator, info, false, file_path)?;
+            // the in belongs to the let regardless of its indentation
+            if !iterator.is_done() && iterator.peek().token.kind() == TokenKind::KeywordIn {
+                let in_token = iterator.advance();
+                iterator.result.push(in_token);
+            }
         } else {
             iterator.advance();
         }
@@ -1,5 +1,6 @@
 pub mod error;
 mod expr;
+pub mod formatter;
 pub mod lexer;
 pub mod parser;
 pub mod token;
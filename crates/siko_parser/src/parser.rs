@@ -25,12 +25,14 @@ use siko_syntax::actor::Protocol;
 use siko_syntax::actor::ProtocolHandler;
 use siko_syntax::class::Class;
 use siko_syntax::class::Constraint;
+use siko_syntax::class::FunctionalDependency;
 use siko_syntax::class::Instance;
 use siko_syntax::data::Adt;
 use siko_syntax::data::Data;
 use siko_syntax::data::DerivedClass;
 use siko_syntax::data::Record;
 use siko_syntax::data::RecordField;
+use siko_syntax::data::RecordId;
 use siko_syntax::data::Variant;
 use siko_syntax::data::VariantId;
 use siko_syntax::export_import::EIGroup;
@@ -68,17 +70,40 @@ enum FunctionOrFunctionType {
 fn parse_class_constraint(parser: &mut Parser) -> Result<Constraint, ParseError> {
     let start_index = parser.get_index();
     let name = parser.parse_qualified_type_name()?;
-    let arg = parser.var_identifier("type arg")?;
+    let args = parse_type_arg_names(parser)?;
     let end_index = parser.get_index();
     let location_id = parser.get_location_id(start_index, end_index);
     let constraint = Constraint {
         class_name: name,
-        arg: arg,
+        args: args,
         location_id: location_id,
     };
     Ok(constraint)
 }
 
+fn parse_type_arg_names(parser: &mut Parser) -> Result<Vec<String>, ParseError> {
+    let mut args = vec![parser.var_identifier("type arg")?];
+    while parser.current_kind() == TokenKind::VarIdentifier {
+        args.push(parser.var_identifier("type arg")?);
+    }
+    Ok(args)
+}
+
+fn parse_functional_dependency(parser: &mut Parser) -> Result<FunctionalDependency, ParseError> {
+    let start_index = parser.get_index();
+    let from = parse_type_arg_names(parser)?;
+    parser.expect(TokenKind::Op(BuiltinOperator::Arrow))?;
+    let to = parse_type_arg_names(parser)?;
+    let end_index = parser.get_index();
+    let location_id = parser.get_location_id(start_index, end_index);
+    let dependency = FunctionalDependency {
+        from: from,
+        to: to,
+        location_id: location_id,
+    };
+    Ok(dependency)
+}
+
 pub struct Parser<'a> {
     file_path: FilePath,
     tokens: &'a [TokenInfo],
@@ -86,6 +111,7 @@ pub struct Parser<'a> {
     program: &'a mut Program,
     location_info: &'a mut LocationInfo,
     temp_var_counter: Counter,
+    errors: Vec<ParseError>,
 }
 
 impl<'a> Parser<'a> {
@@ -102,6 +128,7 @@ impl<'a> Parser<'a> {
             program: program,
             location_info: location_info,
             temp_var_counter: Counter::new(),
+            errors: Vec::new(),
         }
     }
 
@@ -178,6 +205,9 @@ impl<'a> Parser<'a> {
             if self.tokens[index].token.kind() == TokenKind::KeywordDo {
                 return false;
             }
+            if self.tokens[index].token.kind() == TokenKind::KeywordLet {
+                return false;
+            }
             if self.tokens[index].token.kind() == TokenKind::Op(BuiltinOperator::Bind) {
                 return true;
             }
@@ -462,6 +492,47 @@ impl<'a> Parser<'a> {
         Ok(from)
     }
 
+    fn parse_type_args(
+        &mut self,
+        allow_wildcard: bool,
+        start_index: usize,
+    ) -> Result<Vec<TypeSignatureId>, ParseError> {
+        let mut args = Vec::new();
+        loop {
+            match self.current_kind() {
+                TokenKind::TypeIdentifier => {
+                    let arg_start_index = self.get_index();
+                    let arg = self.parse_qualified_type_name()?;
+                    let arg = self
+                        .add_type_signature(TypeSignature::Named(arg, Vec::new()), arg_start_index);
+                    args.push(arg);
+                }
+                TokenKind::VarIdentifier => {
+                    let arg_start_index = self.get_index();
+                    let arg = self.var_identifier("type arg")?;
+                    let arg = self.add_type_signature(TypeSignature::TypeArg(arg), arg_start_index);
+                    args.push(arg);
+                }
+                TokenKind::LParen => {
+                    let arg = self.parse_tuple_type(allow_wildcard)?;
+                    args.push(arg);
+                }
+                TokenKind::LBracket => {
+                    self.expect(TokenKind::LBracket)?;
+                    let arg = self.parse_function_type(false, allow_wildcard)?;
+                    self.expect(TokenKind::RBracket)?;
+                    let ty = TypeSignature::Named(get_qualified_list_type_name(), vec![arg]);
+                    let id = self.add_type_signature(ty, start_index);
+                    args.push(id);
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+        Ok(args)
+    }
+
     fn parse_type_part(
         &mut self,
         parsing_variant: bool,
@@ -483,45 +554,7 @@ impl<'a> Parser<'a> {
                 }
                 Token::TypeIdentifier(_) => {
                     let name = self.parse_qualified_type_name()?;
-                    let mut args = Vec::new();
-                    loop {
-                        match self.current_kind() {
-                            TokenKind::TypeIdentifier => {
-                                let arg_start_index = self.get_index();
-                                let arg = self.parse_qualified_type_name()?;
-                                let arg = self.add_type_signature(
-                                    TypeSignature::Named(arg, Vec::new()),
-                                    arg_start_index,
-                                );
-                                args.push(arg);
-                            }
-                            TokenKind::VarIdentifier => {
-                                let arg_start_index = self.get_index();
-                                let arg = self.var_identifier("type arg")?;
-                                let arg = self.add_type_signature(
-                                    TypeSignature::TypeArg(arg),
-                                    arg_start_index,
-                                );
-                                args.push(arg);
-                            }
-                            TokenKind::LParen => {
-                                let arg = self.parse_tuple_type(allow_wildcard)?;
-                                args.push(arg);
-                            }
-                            TokenKind::LBracket => {
-                                self.expect(TokenKind::LBracket)?;
-                                let arg = self.parse_function_type(false, allow_wildcard)?;
-                                self.expect(TokenKind::RBracket)?;
-                                let ty =
-                                    TypeSignature::Named(get_qualified_list_type_name(), vec![arg]);
-                                let id = self.add_type_signature(ty, start_index);
-                                args.push(id);
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
-                    }
+                    let args = self.parse_type_args(allow_wildcard, start_index)?;
                     let ty = if parsing_variant {
                         TypeSignature::Variant(name, args)
                     } else {
@@ -532,7 +565,12 @@ impl<'a> Parser<'a> {
                 }
                 Token::VarIdentifier(_) => {
                     let name = self.var_identifier("type arg")?;
-                    let ty = TypeSignature::TypeArg(name);
+                    let args = self.parse_type_args(allow_wildcard, start_index)?;
+                    let ty = if args.is_empty() {
+                        TypeSignature::TypeArg(name)
+                    } else {
+                        TypeSignature::AppliedTypeArg(name, args)
+                    };
                     let id = self.add_type_signature(ty, start_index);
                     return Ok(id);
                 }
@@ -662,7 +700,13 @@ impl<'a> Parser<'a> {
                     }
                     FunctionBody::Extern
                 } else {
-                    let body_expr_id = self.parse_expr(false)?;
+                    let mut body_expr_id = self.parse_expr(false)?;
+                    if self.current(TokenKind::KeywordWhere) {
+                        self.expect(TokenKind::KeywordWhere)?;
+                        let functions = self.parse_local_functions()?;
+                        let let_expr = Expr::Let(functions, body_expr_id);
+                        body_expr_id = self.add_expr(let_expr, start_index);
+                    }
                     if args.is_empty() {
                         FunctionBody::Expr(body_expr_id)
                     } else {
@@ -703,6 +747,38 @@ impl<'a> Parser<'a> {
         }
     }
 
+    pub fn parse_local_functions(&mut self) -> Result<Vec<FunctionId>, ParseError> {
+        let mut functions = Vec::new();
+        while self.current_kind() != TokenKind::EndOfBlock {
+            let start_index = self.get_index();
+            match self.parse_function_or_function_type()? {
+                FunctionOrFunctionType::Function(_, function_id) => {
+                    if let FunctionBody::Extern = self.program.functions.get(&function_id).body {
+                        self.restore(start_index);
+                        return report_parser_error(
+                            self,
+                            ParserErrorReason::Custom {
+                                msg: format!("local functions cannot be extern"),
+                            },
+                        );
+                    }
+                    functions.push(function_id);
+                }
+                FunctionOrFunctionType::FunctionType(..) => {
+                    self.restore(start_index);
+                    return report_parser_error(
+                        self,
+                        ParserErrorReason::Custom {
+                            msg: format!("local functions cannot have type signatures"),
+                        },
+                    );
+                }
+            }
+        }
+        self.expect(TokenKind::EndOfBlock)?;
+        Ok(functions)
+    }
+
     fn parse_export_import_data_member(parser: &mut Parser) -> Result<EIMemberInfo, ParseError> {
         let start_index = parser.get_index();
         let member = if parser.current(TokenKind::DoubleDot) {
@@ -1069,7 +1145,23 @@ impl<'a> Parser<'a> {
         let name = self.type_identifier("class name")?;
         let end_index = self.get_index();
         let class_location_id = self.get_location_id(start_index, end_index);
-        let arg = self.parse_function_type(false, false)?;
+        let args_start_index = self.get_index();
+        let args = self.parse_type_args(false, args_start_index)?;
+        if args.is_empty() {
+            return report_unexpected_token(self, format!("class argument"));
+        }
+        let mut functional_dependencies = Vec::new();
+        if self.current_kind() == TokenKind::Pipe {
+            self.expect(TokenKind::Pipe)?;
+            loop {
+                functional_dependencies.push(parse_functional_dependency(self)?);
+                if self.current(TokenKind::Comma) {
+                    self.expect(TokenKind::Comma)?;
+                } else {
+                    break;
+                }
+            }
+        }
         let mut member_functions = BTreeMap::new();
         let mut member_function_types = BTreeMap::new();
         if self.current_kind() == TokenKind::KeywordWhere {
@@ -1097,7 +1189,8 @@ impl<'a> Parser<'a> {
         let class = Class {
             id: id,
             name: name,
-            arg: arg,
+            args: args,
+            functional_dependencies: functional_dependencies,
             constraints: constraints,
             member_functions: member_functions,
             member_function_types: member_function_types,
@@ -1124,7 +1217,10 @@ impl<'a> Parser<'a> {
         };
         let class_name = self.type_identifier("class name")?;
         let start_index = self.get_index();
-        let type_signature_id = self.parse_function_type(false, false)?;
+        let type_signature_ids = self.parse_type_args(false, start_index)?;
+        if type_signature_ids.is_empty() {
+            return report_unexpected_token(self, format!("instance type"));
+        }
         let end_index = self.get_index();
         let instance_location_id = self.get_location_id(start_index, end_index);
         let mut member_functions = BTreeMap::new();
@@ -1155,7 +1251,7 @@ impl<'a> Parser<'a> {
             id: id,
             name: name,
             class_name: class_name,
-            type_signature_id: type_signature_id,
+            type_signature_ids: type_signature_ids,
             constraints: constraints,
             member_functions: member_functions,
             member_function_types: member_function_types,
@@ -1173,12 +1269,20 @@ impl<'a> Parser<'a> {
         self.expect(TokenKind::Equal)?;
         let type_signature_id = self.parse_function_type(false, false)?;
         self.expect(TokenKind::EndOfItem)?;
+        let record_id = self.add_message_record(
+            module,
+            name.clone(),
+            "value".to_string(),
+            type_signature_id,
+            location_id,
+        );
         let id = self.program.protocols.get_id();
         module.protocols.push(id);
         let protocol = Protocol {
             id: id,
             name: name,
             type_signature: type_signature_id,
+            record_id: record_id,
             location_id: location_id,
         };
         Ok(protocol)
@@ -1217,6 +1321,13 @@ impl<'a> Parser<'a> {
         }
         self.expect(TokenKind::EndOfBlock)?;
         self.expect(TokenKind::EndOfItem)?;
+        let record_id = self.add_message_record(
+            module,
+            name.clone(),
+            "state".to_string(),
+            type_signature_id,
+            location_id,
+        );
         let id = self.program.actors.get_id();
         module.actors.push(id);
         let actor = Actor {
@@ -1224,11 +1335,142 @@ impl<'a> Parser<'a> {
             name: name,
             type_signature: type_signature_id,
             handlers: handlers,
+            record_id: record_id,
             location_id: location_id,
         };
         Ok(actor)
     }
 
+    // Protocols and actors are backed by a single field record, this gives them
+    // a type and a constructor function without any special handling.
+    fn add_message_record(
+        &mut self,
+        module: &mut Module,
+        name: String,
+        field_name: String,
+        type_signature_id: TypeSignatureId,
+        location_id: LocationId,
+    ) -> RecordId {
+        let field = RecordField {
+            name: field_name,
+            id: self.program.record_fields.get_id(),
+            type_signature_id: type_signature_id,
+            location_id: location_id,
+        };
+        let field_id = field.id;
+        self.program.record_fields.add_item(field.id, field);
+        let record = Record {
+            name: name,
+            id: self.program.records.get_id(),
+            type_args: Vec::new(),
+            fields: vec![field_id],
+            location_id: location_id,
+            external: false,
+            derived_classes: Vec::new(),
+        };
+        let record_id = record.id;
+        module.records.push(record_id);
+        self.program.records.add_item(record_id, record);
+        record_id
+    }
+
+    fn parse_module_item(&mut self, module: &mut Module) -> Result<(), ParseError> {
+        let token = self.peek().expect("Ran out of tokens");
+        match token.token.kind() {
+            TokenKind::KeywordImport => {
+                let import_id = self.program.imports.get_id();
+                let import = self.parse_import(import_id)?;
+                self.program.imports.add_item(import_id, import);
+                module.imports.push(import_id);
+            }
+            TokenKind::KeywordData => {
+                let data = self.parse_data()?;
+                self.expect(TokenKind::EndOfItem)?;
+                match data {
+                    Data::Record(record) => {
+                        module.records.push(record.id);
+                        self.program.records.add_item(record.id, record);
+                    }
+                    Data::Adt(adt) => {
+                        module.adts.push(adt.id);
+                        self.program.adts.add_item(adt.id, adt);
+                    }
+                }
+            }
+            TokenKind::KeywordClass => {
+                let class = self.parse_class(module)?;
+                self.program.classes.add_item(class.id, class);
+            }
+            TokenKind::KeywordInstance => {
+                let instance = self.parse_instance(module)?;
+                self.program.instances.add_item(instance.id, instance);
+            }
+            TokenKind::KeywordProtocol => {
+                let protocol = self.parse_protocol(module)?;
+                self.program.protocols.add_item(protocol.id, protocol);
+            }
+            TokenKind::KeywordActor => {
+                let actor = self.parse_actor(module)?;
+                self.program.actors.add_item(actor.id, actor);
+            }
+            _ => match self.parse_function_or_function_type()? {
+                FunctionOrFunctionType::Function(name, function_id) => {
+                    let fs = module.functions.entry(name).or_insert_with(|| Vec::new());
+                    fs.push(function_id);
+                }
+                FunctionOrFunctionType::FunctionType(name, function_type_id) => {
+                    let fs = module
+                        .function_types
+                        .entry(name)
+                        .or_insert_with(|| Vec::new());
+                    fs.push(function_type_id);
+                }
+            },
+        }
+        Ok(())
+    }
+
+    // Every where/do/of/let opens a block that the lexer closes with an EndOfBlock,
+    // so the end of the failed item is the next EndOfItem outside of those blocks.
+    fn skip_item(&mut self, start_index: usize) {
+        self.restore(start_index);
+        let mut depth = 0;
+        while !self.is_done() {
+            match self.current_kind() {
+                TokenKind::KeywordWhere
+                | TokenKind::KeywordDo
+                | TokenKind::KeywordOf
+                | TokenKind::KeywordLet => {
+                    depth += 1;
+                }
+                TokenKind::EndOfBlock => {
+                    if depth == 0 {
+                        return;
+                    }
+                    depth -= 1;
+                }
+                TokenKind::EndOfItem => {
+                    if depth == 0 {
+                        self.index += 1;
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.index += 1;
+        }
+    }
+
+    fn skip_module(&mut self) {
+        while !self.is_done() {
+            let kind = self.current_kind();
+            self.index += 1;
+            if kind == TokenKind::EndOfModule {
+                return;
+            }
+        }
+    }
+
     fn parse_module(&mut self, id: ModuleId) -> Result<Module, ParseError> {
         self.expect(TokenKind::KeywordModule)?;
         let start_index = self.get_index();
@@ -1240,59 +1482,13 @@ impl<'a> Parser<'a> {
         self.expect(TokenKind::KeywordWhere)?;
         loop {
             if let Some(token) = self.peek() {
-                match token.token.kind() {
-                    TokenKind::KeywordImport => {
-                        let import_id = self.program.imports.get_id();
-                        let import = self.parse_import(import_id)?;
-                        self.program.imports.add_item(import_id, import);
-                        module.imports.push(import_id);
-                    }
-                    TokenKind::KeywordData => {
-                        let data = self.parse_data()?;
-                        self.expect(TokenKind::EndOfItem)?;
-                        match data {
-                            Data::Record(record) => {
-                                module.records.push(record.id);
-                                self.program.records.add_item(record.id, record);
-                            }
-                            Data::Adt(adt) => {
-                                module.adts.push(adt.id);
-                                self.program.adts.add_item(adt.id, adt);
-                            }
-                        }
-                    }
-                    TokenKind::KeywordClass => {
-                        let class = self.parse_class(&mut module)?;
-                        self.program.classes.add_item(class.id, class);
-                    }
-                    TokenKind::KeywordInstance => {
-                        let instance = self.parse_instance(&mut module)?;
-                        self.program.instances.add_item(instance.id, instance);
-                    }
-                    TokenKind::KeywordProtocol => {
-                        let protocol = self.parse_protocol(&mut module)?;
-                        self.program.protocols.add_item(protocol.id, protocol);
-                    }
-                    TokenKind::KeywordActor => {
-                        let actor = self.parse_actor(&mut module)?;
-                        self.program.actors.add_item(actor.id, actor);
-                    }
-                    TokenKind::EndOfBlock => {
-                        break;
-                    }
-                    _ => match self.parse_function_or_function_type()? {
-                        FunctionOrFunctionType::Function(name, function_id) => {
-                            let fs = module.functions.entry(name).or_insert_with(|| Vec::new());
-                            fs.push(function_id);
-                        }
-                        FunctionOrFunctionType::FunctionType(name, function_type_id) => {
-                            let fs = module
-                                .function_types
-                                .entry(name)
-                                .or_insert_with(|| Vec::new());
-                            fs.push(function_type_id);
-                        }
-                    },
+                if token.token.kind() == TokenKind::EndOfBlock {
+                    break;
+                }
+                let start_index = self.get_index();
+                if let Err(err) = self.parse_module_item(&mut module) {
+                    self.errors.push(err);
+                    self.skip_item(start_index);
                 }
             } else {
                 break;
@@ -1303,11 +1499,22 @@ impl<'a> Parser<'a> {
         Ok(module)
     }
 
-    pub fn parse(&mut self) -> Result<(), ParseError> {
+    pub fn parse(&mut self) -> Result<(), Vec<ParseError>> {
         while !self.is_done() {
             let m_id = self.program.modules.get_id();
-            let module = self.parse_module(m_id)?;
-            self.program.modules.add_item(m_id, module);
+            match self.parse_module(m_id) {
+                Ok(module) => {
+                    self.program.modules.add_item(m_id, module);
+                }
+                Err(err) => {
+                    self.errors.push(err);
+                    self.skip_module();
+                }
+            }
+        }
+
+        if !self.errors.is_empty() {
+            return Err(std::mem::replace(&mut self.errors, Vec::new()));
         }
 
         let implicit_modules = get_implicit_module_list();
@@ -9,6 +9,7 @@ pub enum Token {
     IntegerLiteral(i64),
     FloatLiteral(f64),
     CharLiteral(char),
+    Hole(String),
     KeywordWhere,
     KeywordData,
     KeywordModule,
@@ -33,6 +34,8 @@ pub enum Token {
     KeywordLoop,
     KeywordContinue,
     KeywordBreak,
+    KeywordLet,
+    KeywordIn,
     Op(BuiltinOperator),
     Equal,
     Comma,
@@ -59,11 +62,13 @@ pub enum Token {
 }
 
 impl Token {
+    // | and & are also used in patterns and types, in expressions they are bitwise operators
     pub fn get_op(&self) -> Option<BuiltinOperator> {
-        if let Token::Op(o) = self {
-            Some(o.clone())
-        } else {
-            None
+        match self {
+            Token::Op(o) => Some(o.clone()),
+            Token::Pipe => Some(BuiltinOperator::BitOr),
+            Token::Ampersand => Some(BuiltinOperator::BitAnd),
+            _ => None,
         }
     }
 
@@ -75,6 +80,7 @@ impl Token {
             Token::IntegerLiteral(..) => TokenKind::IntegerLiteral,
             Token::FloatLiteral(..) => TokenKind::FloatLiteral,
             Token::CharLiteral(..) => TokenKind::CharLiteral,
+            Token::Hole(..) => TokenKind::Hole,
             Token::KeywordWhere => TokenKind::KeywordWhere,
             Token::KeywordData => TokenKind::KeywordData,
             Token::KeywordModule => TokenKind::KeywordModule,
@@ -99,6 +105,8 @@ impl Token {
             Token::KeywordLoop => TokenKind::KeywordLoop,
             Token::KeywordContinue => TokenKind::KeywordContinue,
             Token::KeywordBreak => TokenKind::KeywordBreak,
+            Token::KeywordLet => TokenKind::KeywordLet,
+            Token::KeywordIn => TokenKind::KeywordIn,
             Token::Op(op) => TokenKind::Op(*op),
             Token::Equal => TokenKind::Equal,
             Token::Comma => TokenKind::Comma,
@@ -134,6 +142,7 @@ pub enum TokenKind {
     IntegerLiteral,
     FloatLiteral,
     CharLiteral,
+    Hole,
     KeywordWhere,
     KeywordData,
     KeywordModule,
@@ -158,6 +167,8 @@ pub enum TokenKind {
     KeywordLoop,
     KeywordContinue,
     KeywordBreak,
+    KeywordLet,
+    KeywordIn,
     Op(BuiltinOperator),
     Equal,
     Comma,
@@ -207,6 +218,7 @@ impl TokenKind {
             TokenKind::FloatLiteral => format!("float literal"),
             TokenKind::IntegerLiteral => format!("integer literal"),
             TokenKind::CharLiteral => format!("char literal"),
+            TokenKind::Hole => format!("hole"),
             _ => {
                 let name = format!("{:?}", self);
                 let name = name.to_lowercase();
@@ -222,3 +234,9 @@ pub struct TokenInfo {
     pub token: Token,
     pub location: Location,
 }
+
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub text: String,
+    pub location: Location,
+}
@@ -1,3 +1,4 @@
+use crate::data::RecordId;
 use crate::types::TypeSignatureId;
 use siko_location_info::location_id::LocationId;
 
@@ -7,6 +8,7 @@ pub struct Actor {
     pub name: String,
     pub type_signature: TypeSignatureId,
     pub handlers: Vec<ProtocolHandler>,
+    pub record_id: RecordId,
     pub location_id: LocationId,
 }
 
@@ -34,6 +36,7 @@ pub struct Protocol {
     pub id: ProtocolId,
     pub name: String,
     pub type_signature: TypeSignatureId,
+    pub record_id: RecordId,
     pub location_id: LocationId,
 }
 
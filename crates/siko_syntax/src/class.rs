@@ -8,7 +8,8 @@ use std::collections::BTreeMap;
 pub struct Class {
     pub id: ClassId,
     pub name: String,
-    pub arg: TypeSignatureId,
+    pub args: Vec<TypeSignatureId>,
+    pub functional_dependencies: Vec<FunctionalDependency>,
     pub constraints: Vec<Constraint>,
     pub member_functions: BTreeMap<String, Vec<FunctionId>>,
     pub member_function_types: BTreeMap<String, Vec<FunctionTypeId>>,
@@ -20,7 +21,7 @@ pub struct Instance {
     pub id: InstanceId,
     pub name: Option<String>,
     pub class_name: String,
-    pub type_signature_id: TypeSignatureId,
+    pub type_signature_ids: Vec<TypeSignatureId>,
     pub constraints: Vec<Constraint>,
     pub member_functions: BTreeMap<String, Vec<FunctionId>>,
     pub member_function_types: BTreeMap<String, Vec<FunctionTypeId>>,
@@ -30,7 +31,14 @@ pub struct Instance {
 #[derive(Debug, Clone)]
 pub struct Constraint {
     pub class_name: String,
-    pub arg: String,
+    pub args: Vec<String>,
+    pub location_id: LocationId,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionalDependency {
+    pub from: Vec<String>,
+    pub to: Vec<String>,
     pub location_id: LocationId,
 }
 
@@ -1,3 +1,4 @@
+use crate::function::FunctionId;
 use crate::pattern::PatternId;
 use siko_constants::BuiltinOperator;
 use siko_location_info::location_id::LocationId;
@@ -59,6 +60,7 @@ pub enum Expr {
     FloatLiteral(f64),
     StringLiteral(String),
     CharLiteral(char),
+    Hole(String),
     Do(Vec<ExprId>),
     Bind(PatternId, ExprId),
     FieldAccess(String, ExprId),
@@ -71,6 +73,7 @@ pub enum Expr {
     Loop(PatternId, ExprId, Vec<ExprId>),
     Continue(ExprId),
     Break(ExprId),
+    Let(Vec<FunctionId>, ExprId),
 }
 
 impl fmt::Display for Expr {
@@ -95,6 +98,7 @@ impl fmt::Display for Expr {
             Expr::FloatLiteral(v) => write!(f, "Float({})", v),
             Expr::StringLiteral(v) => write!(f, "String({})", v),
             Expr::CharLiteral(v) => write!(f, "Char({})", v),
+            Expr::Hole(name) => write!(f, "Hole({})", name),
             Expr::Do(items) => write!(f, "Do({})", format_list(items)),
             Expr::Bind(t, expr) => write!(f, "Bind({}, {})", t, expr),
             Expr::FieldAccess(name, expr) => write!(f, "FieldAccess({}, {})", name, expr),
@@ -115,6 +119,7 @@ impl fmt::Display for Expr {
             }
             Expr::Continue(expr) => write!(f, "Continue({})", expr),
             Expr::Break(expr) => write!(f, "Break({})", expr),
+            Expr::Let(functions, body) => write!(f, "Let({:?}, {})", functions, body),
         }
     }
 }
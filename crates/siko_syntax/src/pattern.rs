@@ -46,5 +46,8 @@ pub enum Pattern {
     Typed(PatternId, TypeSignatureId),
     Record(String, Vec<RecordFieldPattern>),
     CharRange(char, char, RangeKind),
+    IntegerRange(i64, i64, RangeKind),
     Or(Vec<PatternId>),
+    List(Vec<PatternId>),
+    Cons(PatternId, PatternId),
 }
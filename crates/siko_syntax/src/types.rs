@@ -12,6 +12,7 @@ impl From<usize> for TypeSignatureId {
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum TypeSignature {
     TypeArg(String),
+    AppliedTypeArg(String, Vec<TypeSignatureId>),
     Named(String, Vec<TypeSignatureId>),
     Variant(String, Vec<TypeSignatureId>),
     Tuple(Vec<TypeSignatureId>),
@@ -1,10 +1,19 @@
+use crate::internal_module::get_message_variant_name;
 use crate::types::ir_type_to_rust_type;
+use crate::util::get_module_name;
 use crate::util::get_ord_type_from_optional_ord;
 use crate::util::Indent;
+use siko_constants::MATH_MODULE_NAME;
+use siko_constants::MIR_INTERNAL_MODULE_NAME;
+use siko_constants::STD_ACTOR_MODULE_NAME;
+use siko_constants::STD_ENV_MODULE_NAME;
+use siko_constants::STD_IO_MODULE_NAME;
+use siko_mir::data::RecordKind;
+use siko_mir::data::TypeDefId;
 use siko_mir::function::Function;
 use siko_mir::program::Program;
+use siko_mir::types::Modifier;
 use siko_mir::types::Type;
-use siko_util::to_first_uppercase;
 use std::io::Result;
 use std::io::Write;
 
@@ -189,6 +198,21 @@ fn generate_opadd_builtin_body(
     Ok(())
 }
 
+fn generate_binary_op_builtin_body(
+    output_file: &mut dyn Write,
+    indent: &mut Indent,
+    result_ty_str: &str,
+    value: &str,
+) -> Result<()> {
+    write!(output_file, "{}let value = {};\n", indent, value)?;
+    write!(
+        output_file,
+        "{}{} {{ value : value }}",
+        indent, result_ty_str
+    )?;
+    Ok(())
+}
+
 fn generate_num_builtins(
     module: &str,
     output_file: &mut dyn Write,
@@ -212,6 +236,359 @@ fn generate_num_builtins(
         "opDiv" => {
             generate_opdiv_builtin_body(output_file, indent, result_ty_str)?;
         }
+        "opMod" => {
+            let value = "{ let m = arg0.value % arg1.value; if m != 0 && ((m < 0) != (arg1.value < 0)) { m + arg1.value } else { m } }";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opRem" => {
+            let value = "arg0.value % arg1.value";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opBitAnd" => {
+            let value = "arg0.value & arg1.value";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opBitOr" => {
+            let value = "arg0.value | arg1.value";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opBitXor" => {
+            let value = "arg0.value ^ arg1.value";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opShiftLeft" => {
+            let value = "arg0.value.wrapping_shl(arg1.value as u32)";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opShiftRight" => {
+            let value = "arg0.value.wrapping_shr(arg1.value as u32)";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "opEq" => {
+            generate_opeq_builtin_body(output_file, indent, result_ty_str)?;
+        }
+        "show" => {
+            generate_show_builtin_body(output_file, indent, result_ty_str)?;
+        }
+        "partialCmp" => {
+            generate_partial_cmp_builtin_body(
+                output_file,
+                program,
+                indent,
+                result_ty,
+                result_ty_str,
+            )?;
+        }
+        "cmp" => {
+            generate_cmp_builtin_body(output_file, program, indent, result_ty_str)?;
+        }
+        "toFloat" => {
+            let value = "arg0.value as f64";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "abs" => {
+            let value = "arg0.value.abs()";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "negate" => {
+            let value = "-arg0.value";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "pow" => {
+            let value = "{ if arg1.value < 0 { panic!(\"Negative exponent {}\", arg1.value); } arg0.value.pow(arg1.value as u32) }";
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        "round" | "floor" | "ceil" | "truncate" => {
+            let method = if original_name == "truncate" {
+                "trunc"
+            } else {
+                original_name
+            };
+            let value = format!("arg0.value.{}() as i64", method);
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, &value)?;
+        }
+        "isNaN" | "isInfinite" => {
+            let method = if original_name == "isNaN" {
+                "is_nan"
+            } else {
+                "is_infinite"
+            };
+            write!(
+                output_file,
+                "{}if arg0.value.{}() {{ {}::True }} else {{ {}::False }}",
+                indent, method, result_ty_str, result_ty_str
+            )?;
+        }
+        "infinity" | "nan" | "epsilon" | "maxValue" | "minValue" => {
+            let value = match original_name {
+                "infinity" => "std::f64::INFINITY",
+                "nan" => "std::f64::NAN",
+                "epsilon" => "std::f64::EPSILON",
+                "maxValue" => "std::f64::MAX",
+                _ => "std::f64::MIN",
+            };
+            generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+        }
+        _ => panic!("{}/{} not implemented", module, original_name),
+    }
+    indent.dec();
+    Ok(())
+}
+
+fn generate_math_builtins(
+    output_file: &mut dyn Write,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    let value = match original_name {
+        "pi" => "std::f64::consts::PI",
+        "e" => "std::f64::consts::E",
+        "sqrt" => "arg0.value.sqrt()",
+        "pow" => "arg0.value.powf(arg1.value)",
+        "exp" => "arg0.value.exp()",
+        "ln" => "arg0.value.ln()",
+        "log2" => "arg0.value.log2()",
+        "log10" => "arg0.value.log10()",
+        "logBase" => "arg1.value.log(arg0.value)",
+        "sin" => "arg0.value.sin()",
+        "cos" => "arg0.value.cos()",
+        "tan" => "arg0.value.tan()",
+        "asin" => "arg0.value.asin()",
+        "acos" => "arg0.value.acos()",
+        "atan" => "arg0.value.atan()",
+        "atan2" => "arg0.value.atan2(arg1.value)",
+        "min" => "arg0.value.min(arg1.value)",
+        "max" => "arg0.value.max(arg1.value)",
+        _ => panic!("Math/{} not implemented", original_name),
+    };
+    generate_binary_op_builtin_body(output_file, indent, result_ty_str, value)?;
+    indent.dec();
+    Ok(())
+}
+
+fn generate_string_builtins(
+    module: &str,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty: &Type,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    match original_name {
+        "opAdd" => {
+            write!(
+                output_file,
+                "{}let value = format!(\"{{}}{{}}\", arg0.value, arg1.value);\n",
+                indent
+            )?;
+            write!(
+                output_file,
+                "{}{} {{ value : value }}",
+                indent, result_ty_str
+            )?;
+        }
+        "opEq" => {
+            generate_opeq_builtin_body(output_file, indent, result_ty_str)?;
+        }
+        "partialCmp" => {
+            generate_partial_cmp_builtin_body(
+                output_file,
+                program,
+                indent,
+                result_ty,
+                result_ty_str,
+            )?;
+        }
+        "cmp" => {
+            generate_cmp_builtin_body(output_file, program, indent, result_ty_str)?;
+        }
+        "chars" => {
+            let char_ty = get_external_type_arg(result_ty, program);
+            let char_ty_str = ir_type_to_rust_type(&char_ty, program);
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.chars().map(|c| {} {{ value: c }}).collect() }}",
+                indent, result_ty_str, char_ty_str
+            )?;
+        }
+        "iter" => {
+            let char_ty = get_external_type_arg(result_ty, program);
+            let char_ty_str = ir_type_to_rust_type(&char_ty, program);
+            write!(output_file, "{}let value = arg0.value;\n", indent)?;
+            write!(output_file, "{}let mut offset = 0;\n", indent)?;
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                &format!(
+                    "std::iter::from_fn(move || {{ let c = value[offset..].chars().next()?; offset += c.len_utf8(); Some({} {{ value: c }}) }})",
+                    char_ty_str
+                ),
+            )?;
+        }
+        "fromChars" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.into_iter().map(|c| c.value).collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "getLength" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.chars().count() as i64 }}",
+                indent, result_ty_str
+            )?;
+        }
+        "split" | "lines" | "words" => {
+            let string_ty = get_external_type_arg(result_ty, program);
+            let string_ty_str = ir_type_to_rust_type(&string_ty, program);
+            let parts = match original_name {
+                "split" => "arg1.value.split(arg0.value.as_str())",
+                "lines" => "arg0.value.lines()",
+                _ => "arg0.value.split_whitespace()",
+            };
+            write!(
+                output_file,
+                "{}{} {{ value: {}.map(|s| {} {{ value: s.to_string() }}).collect() }}",
+                indent, result_ty_str, parts, string_ty_str
+            )?;
+        }
+        "join" => {
+            write!(
+                output_file,
+                "{}let parts: Vec<_> = arg1.value.into_iter().map(|s| s.value).collect();\n",
+                indent
+            )?;
+            write!(
+                output_file,
+                "{}{} {{ value: parts.join(arg0.value.as_str()) }}",
+                indent, result_ty_str
+            )?;
+        }
+        "trim" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.trim().to_string() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "replace" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg2.value.replace(arg0.value.as_str(), arg1.value.as_str()) }}",
+                indent, result_ty_str
+            )?;
+        }
+        "contains" | "startsWith" | "endsWith" => {
+            let method = match original_name {
+                "contains" => "contains",
+                "startsWith" => "starts_with",
+                _ => "ends_with",
+            };
+            write!(
+                output_file,
+                "{}if arg1.value.{}(arg0.value.as_str()) {{ {}::True }} else {{ {}::False }}",
+                indent, method, result_ty_str, result_ty_str
+            )?;
+        }
+        "indexOf" => {
+            let int_ty = get_option_item_type(result_ty, program);
+            let int_ty_str = ir_type_to_rust_type(&int_ty, program);
+            write!(
+                output_file,
+                "{}match arg1.value.find(arg0.value.as_str()) {{\n",
+                indent
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some(index) => {}::Some({} {{ value: arg1.value[..index].chars().count() as i64 }}),\n",
+                indent, result_ty_str, int_ty_str
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, result_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
+        }
+        "substring" => {
+            write!(
+                output_file,
+                "{}let start = arg0.value.max(0) as usize;\n",
+                indent
+            )?;
+            write!(
+                output_file,
+                "{}let end = arg1.value.max(0) as usize;\n",
+                indent
+            )?;
+            write!(
+                output_file,
+                "{}{} {{ value: arg2.value.chars().skip(start).take(end.saturating_sub(start)).collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "toUpper" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.to_uppercase() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "toLower" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.to_lowercase() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "parseInt" | "parseFloat" => {
+            let value_ty = get_option_item_type(result_ty, program);
+            let value_ty_str = ir_type_to_rust_type(&value_ty, program);
+            let rust_ty = if original_name == "parseInt" {
+                "i64"
+            } else {
+                "f64"
+            };
+            write!(
+                output_file,
+                "{}match arg0.value.parse::<{}>() {{\n",
+                indent, rust_ty
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Ok(v) => {}::Some({} {{ value: v }}),\n",
+                indent, result_ty_str, value_ty_str
+            )?;
+            write!(
+                output_file,
+                "{} Err(_) => {}::None,\n",
+                indent, result_ty_str
+            )?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
+        }
+        _ => panic!("{}/{} not implemented", module, original_name),
+    }
+    indent.dec();
+    Ok(())
+}
+
+fn generate_char_builtins(
+    module: &str,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty: &Type,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    match original_name {
         "opEq" => {
             generate_opeq_builtin_body(output_file, indent, result_ty_str)?;
         }
@@ -230,14 +607,731 @@ fn generate_num_builtins(
         "cmp" => {
             generate_cmp_builtin_body(output_file, program, indent, result_ty_str)?;
         }
+        "isUppercase" => {
+            write!(
+                output_file,
+                "{}if arg0.value.is_uppercase() {{ {}::True }} else {{ {}::False }}",
+                indent, result_ty_str, result_ty_str
+            )?;
+        }
         _ => panic!("{}/{} not implemented", module, original_name),
     }
     indent.dec();
-    Ok(())
+    Ok(())
+}
+
+fn generate_map_builtins(
+    function: &Function,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty: &Type,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    match original_name {
+        "empty" => {
+            write!(
+                output_file,
+                "{}let value = std::collections::BTreeMap::new();\n",
+                indent
+            )?;
+            write!(
+                output_file,
+                "{}{} {{ value : value }}",
+                indent, result_ty_str
+            )?;
+        }
+        "insert" => {
+            let result_id = result_ty.get_typedef_id();
+            let tuple_record = program.typedefs.get(&result_id).get_record();
+            let option_ty = ir_type_to_rust_type(&tuple_record.fields[1].ty, program);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}let value = match arg0.value.insert(arg1, arg2) {{\n",
+                indent
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some(v) => {}::Some(v),\n",
+                indent, option_ty
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, option_ty)?;
+            indent.dec();
+            write!(output_file, "{}}};\n", indent)?;
+            write!(
+                output_file,
+                "{}{} {{ field_0 : arg0, field_1: value }}",
+                indent, result_ty_str
+            )?;
+        }
+        "remove" => {
+            let result_id = result_ty.get_typedef_id();
+            let tuple_record = program.typedefs.get(&result_id).get_record();
+            let option_ty = ir_type_to_rust_type(&tuple_record.fields[1].ty, program);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}let value = match arg0.value.remove(&arg1) {{\n",
+                indent
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some(v) => {}::Some(v),\n",
+                indent, option_ty
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, option_ty)?;
+            indent.dec();
+            write!(output_file, "{}}};\n", indent)?;
+            write!(
+                output_file,
+                "{}{} {{ field_0 : arg0, field_1: value }}",
+                indent, result_ty_str
+            )?;
+        }
+        "get" => {
+            write!(output_file, "{}match arg0.value.get(&arg1) {{\n", indent)?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some(v) => {}::Some(v.clone()),\n",
+                indent, result_ty_str
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, result_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
+        }
+        "iter" => {
+            let item_ty = get_external_type_arg(result_ty, program);
+            let item_ty_str = ir_type_to_rust_type(&item_ty, program);
+            write!(
+                output_file,
+                "{}let items: Vec<_> = arg0.value.into_iter().map(|(k, v)| {} {{ field_0: k, field_1: v }}).collect();\n",
+                indent, item_ty_str
+            )?;
+            write_iterator_value(output_file, indent, result_ty_str, "items.into_iter()")?;
+        }
+        "size" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.len() as i64 }}",
+                indent, result_ty_str
+            )?;
+        }
+        "contains" => {
+            write!(
+                output_file,
+                "{}if arg0.value.contains_key(&arg1) {{ {}::True }} else {{ {}::False }}",
+                indent, result_ty_str, result_ty_str
+            )?;
+        }
+        "keys" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.into_iter().map(|(k, _)| k).collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "values" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.into_iter().map(|(_, v)| v).collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "fold" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}arg2.value.into_iter().fold(arg1, |acc, (k, v)| arg0.call(acc).call(k).call(v))",
+                indent
+            )?;
+        }
+        "union" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}for (k, v) in arg1.value {{\n", indent)?;
+            indent.inc();
+            write!(output_file, "{}arg0.value.entry(k).or_insert(v);\n", indent)?;
+            indent.dec();
+            write!(output_file, "{}}}\n", indent)?;
+            write!(output_file, "{}arg0", indent)?;
+        }
+        "filter" => {
+            let predicate = get_predicate_call(function, program, &["k.clone()", "v.clone()"]);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}{} {{ value: arg1.value.into_iter().filter(|(k, v)| {}).collect() }}",
+                indent, result_ty_str, predicate
+            )?;
+        }
+        "fromList" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.into_iter().map(|item| (item.field_0, item.field_1)).collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "alter" => {
+            let mut fn_args = Vec::new();
+            function.function_type.get_args(&mut fn_args);
+            let closure = program.get_closure_type(&fn_args[0]);
+            let from_ty_str = ir_type_to_rust_type(&closure.from_ty, program);
+            let to_ty_str = ir_type_to_rust_type(&closure.to_ty, program);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}let mut arg2 = arg2;\n", indent)?;
+            write!(
+                output_file,
+                "{}let current = match arg2.value.remove(&arg1) {{\n",
+                indent
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some(v) => {}::Some(v),\n",
+                indent, from_ty_str
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, from_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}};\n", indent)?;
+            write!(
+                output_file,
+                "{}if let {}::Some(v) = arg0.call(current) {{\n",
+                indent, to_ty_str
+            )?;
+            indent.inc();
+            write!(output_file, "{}arg2.value.insert(arg1, v);\n", indent)?;
+            indent.dec();
+            write!(output_file, "{}}}\n", indent)?;
+            write!(output_file, "{}arg2", indent)?;
+        }
+        "range" => {
+            let item_ty = get_external_type_arg(result_ty, program);
+            let item_ty_str = ir_type_to_rust_type(&item_ty, program);
+            write!(
+                output_file,
+                "{}let items: Vec<_> = if arg1 < arg2 {{ arg0.value.range(arg1..arg2).map(|(k, v)| {} {{ field_0: k.clone(), field_1: v.clone() }}).collect() }} else {{ Vec::new() }};\n",
+                indent, item_ty_str
+            )?;
+            write_iterator_value(output_file, indent, result_ty_str, "items.into_iter()")?;
+        }
+        "first" | "last" => {
+            let item_ty = get_option_item_type(result_ty, program);
+            let item_ty_str = ir_type_to_rust_type(&item_ty, program);
+            let method = if original_name == "first" {
+                "next"
+            } else {
+                "next_back"
+            };
+            write!(
+                output_file,
+                "{}match arg0.value.iter().{}() {{\n",
+                indent, method
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some((k, v)) => {}::Some({} {{ field_0: k.clone(), field_1: v.clone() }}),\n",
+                indent, result_ty_str, item_ty_str
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, result_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
+        }
+        _ => panic!("Map/{} not implemented", original_name),
+    }
+    indent.dec();
+    Ok(())
+}
+
+fn generate_set_builtins(
+    function: &Function,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    match original_name {
+        "empty" => {
+            write!(
+                output_file,
+                "{}{} {{ value: std::collections::BTreeSet::new() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "insert" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}arg0.value.insert(arg1);\n", indent)?;
+            write!(output_file, "{}arg0", indent)?;
+        }
+        "remove" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}arg0.value.remove(&arg1);\n", indent)?;
+            write!(output_file, "{}arg0", indent)?;
+        }
+        "contains" => {
+            write!(
+                output_file,
+                "{}if arg0.value.contains(&arg1) {{ {}::True }} else {{ {}::False }}",
+                indent, result_ty_str, result_ty_str
+            )?;
+        }
+        "size" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.len() as i64 }}",
+                indent, result_ty_str
+            )?;
+        }
+        "iter" => {
+            write!(
+                output_file,
+                "{}let items: Vec<_> = arg0.value.into_iter().collect();\n",
+                indent
+            )?;
+            write_iterator_value(output_file, indent, result_ty_str, "items.into_iter()")?;
+        }
+        "fromList" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.into_iter().collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "union" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}arg0.value.extend(arg1.value);\n", indent)?;
+            write!(output_file, "{}arg0", indent)?;
+        }
+        "intersection" | "difference" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.value.{}(&arg1.value).cloned().collect() }}",
+                indent, result_ty_str, original_name
+            )?;
+        }
+        "filter" => {
+            let predicate = get_predicate_call(function, program, &["v.clone()"]);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}{} {{ value: arg1.value.into_iter().filter(|v| {}).collect() }}",
+                indent, result_ty_str, predicate
+            )?;
+        }
+        "fold" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}arg2.value.into_iter().fold(arg1, |acc, v| arg0.call(acc).call(v))",
+                indent
+            )?;
+        }
+        "range" => {
+            write!(
+                output_file,
+                "{}let items: Vec<_> = if arg1 < arg2 {{ arg0.value.range(arg1..arg2).cloned().collect() }} else {{ Vec::new() }};\n",
+                indent
+            )?;
+            write_iterator_value(output_file, indent, result_ty_str, "items.into_iter()")?;
+        }
+        "first" | "last" => {
+            let method = if original_name == "first" {
+                "next"
+            } else {
+                "next_back"
+            };
+            write!(
+                output_file,
+                "{}match arg0.value.iter().{}() {{\n",
+                indent, method
+            )?;
+            indent.inc();
+            write!(
+                output_file,
+                "{} Some(v) => {}::Some(v.clone()),\n",
+                indent, result_ty_str
+            )?;
+            write!(output_file, "{} None => {}::None,\n", indent, result_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
+        }
+        _ => panic!("Set/{} not implemented", original_name),
+    }
+    indent.dec();
+    Ok(())
+}
+
+fn generate_list_builtins(
+    _function: &Function,
+    output_file: &mut dyn Write,
+    _program: &Program,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    match original_name {
+        "show" => {
+            write!(
+                output_file,
+                "{}let subs: Vec<_> = arg0.value.iter().map(|item| format!(\"{{}}\", item)).collect();\n",
+                indent
+            )?;
+            write!(
+                output_file,
+                "{}{} {{ value : format!(\"[{{}}]\", subs.join(\", \")) }}",
+                indent, result_ty_str
+            )?;
+        }
+        "toList" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.collect() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "iter" => {
+            write!(
+                output_file,
+                "{}{} {{ value: Box::new(arg0.value.into_iter()) }}",
+                indent, result_ty_str
+            )?;
+        }
+        "opEq" => {
+            write!(output_file, "{}if arg0.value.eq(&arg1.value) {{\n", indent)?;
+            indent.inc();
+            write!(output_file, "{}{}::True\n", indent, result_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}} else {{\n", indent)?;
+            indent.inc();
+            write!(output_file, "{}{}::False\n", indent, result_ty_str)?;
+            indent.dec();
+            write!(output_file, "{}}}\n", indent)?;
+        }
+        _ => panic!("List/{} not implemented", original_name),
+    }
+    indent.dec();
+    Ok(())
+}
+
+fn get_external_type_arg(iterator_ty: &Type, program: &Program) -> Type {
+    let record = program
+        .typedefs
+        .get(&iterator_ty.get_typedef_id())
+        .get_record();
+    match &record.kind {
+        RecordKind::External(_, args) => args[0].clone(),
+        _ => unreachable!(),
+    }
+}
+
+fn write_iterator_value(
+    output_file: &mut dyn Write,
+    indent: &mut Indent,
+    result_ty_str: &str,
+    iterator: &str,
+) -> Result<()> {
+    write!(
+        output_file,
+        "{}{} {{ value: Box::new({}) }}",
+        indent, result_ty_str, iterator
+    )
+}
+
+fn get_variant_item_type(adt_ty: &Type, variant_name: &str, program: &Program) -> Type {
+    let adt = program.typedefs.get(&adt_ty.get_typedef_id()).get_adt();
+    let variant = adt
+        .variants
+        .iter()
+        .find(|variant| variant.name == variant_name)
+        .expect("Variant not found");
+    variant.items[0].ty.clone()
+}
+
+fn get_option_item_type(option_ty: &Type, program: &Program) -> Type {
+    get_variant_item_type(option_ty, "Some", program)
+}
+
+// Calls the predicate closure in arg0 with the given args and converts its result into a rust bool.
+fn get_predicate_call(function: &Function, program: &Program, args: &[&str]) -> String {
+    let mut fn_args = Vec::new();
+    function.function_type.get_args(&mut fn_args);
+    let mut ty = fn_args[0].clone();
+    let mut call = format!("arg0");
+    for arg in args {
+        call = format!("{}.call({})", call, arg);
+        ty = program.get_closure_type(&ty).to_ty.clone();
+    }
+    let bool_ty_str = ir_type_to_rust_type(&ty, program);
+    format!(
+        "match {} {{ {}::True => true, {}::False => false }}",
+        call, bool_ty_str, bool_ty_str
+    )
+}
+
+fn generate_iterator_builtins(
+    function: &Function,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    original_name: &str,
+    result_ty: &Type,
+    result_ty_str: &str,
+) -> Result<()> {
+    indent.inc();
+    match original_name {
+        "map" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                "arg1.map(move |v| arg0.call(v))",
+            )?;
+        }
+        "filter" => {
+            let predicate = get_predicate_call(function, program, &["v.clone()"]);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                &format!("arg1.filter(move |v| {})", predicate),
+            )?;
+        }
+        "fold" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(
+                output_file,
+                "{}arg2.fold(arg1, |acc, v| arg0.call(acc).call(v))",
+                indent
+            )?;
+        }
+        "zip" => {
+            let item_ty = get_external_type_arg(result_ty, program);
+            let item_ty_str = ir_type_to_rust_type(&item_ty, program);
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                &format!(
+                    "arg0.zip(arg1).map(|(a, b)| {} {{ field_0: a, field_1: b }})",
+                    item_ty_str
+                ),
+            )?;
+        }
+        "enumerate" => {
+            let item_ty = get_external_type_arg(result_ty, program);
+            let item_ty_str = ir_type_to_rust_type(&item_ty, program);
+            let tuple = program.typedefs.get(&item_ty.get_typedef_id()).get_record();
+            let index_ty_str = ir_type_to_rust_type(&tuple.fields[0].ty, program);
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                &format!(
+                    "arg0.enumerate().map(|(i, v)| {} {{ field_0: {} {{ value: i as i64 }}, field_1: v }})",
+                    item_ty_str, index_ty_str
+                ),
+            )?;
+        }
+        "take" => {
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                "arg1.take(arg0.value.max(0) as usize)",
+            )?;
+        }
+        "skip" => {
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                "arg1.skip(arg0.value.max(0) as usize)",
+            )?;
+        }
+        "takeWhile" => {
+            let predicate = get_predicate_call(function, program, &["v.clone()"]);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                &format!("arg1.take_while(move |v| {})", predicate),
+            )?;
+        }
+        "flatMap" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                "arg1.flat_map(move |v| arg0.call(v))",
+            )?;
+        }
+        "chain" => {
+            write_iterator_value(output_file, indent, result_ty_str, "arg0.chain(arg1)")?;
+        }
+        "any" | "all" => {
+            let predicate = get_predicate_call(function, program, &["v"]);
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}let mut arg1 = arg1;\n", indent)?;
+            write!(
+                output_file,
+                "{}if arg1.{}(|v| {}) {{ {}::True }} else {{ {}::False }}",
+                indent, original_name, predicate, result_ty_str, result_ty_str
+            )?;
+        }
+        "count" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.count() as i64 }}",
+                indent, result_ty_str
+            )?;
+        }
+        "sum" => {
+            write!(
+                output_file,
+                "{}{} {{ value: arg0.map(|v| v.value).sum() }}",
+                indent, result_ty_str
+            )?;
+        }
+        "range" => {
+            let item_ty = get_external_type_arg(result_ty, program);
+            let item_ty_str = ir_type_to_rust_type(&item_ty, program);
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                &format!(
+                    "(arg0.value..arg1.value).map(|v| {} {{ value: v }})",
+                    item_ty_str
+                ),
+            )?;
+        }
+        "repeat" => {
+            write_iterator_value(
+                output_file,
+                indent,
+                result_ty_str,
+                "std::iter::repeat(arg0)",
+            )?;
+        }
+        "forEach" => {
+            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+            write!(output_file, "{}for v in arg1 {{\n", indent)?;
+            indent.inc();
+            write!(output_file, "{}arg0.call(v);\n", indent)?;
+            indent.dec();
+            write!(output_file, "{}}}\n", indent)?;
+            write!(output_file, "{}{} {{ }}", indent, result_ty_str)?;
+        }
+        _ => panic!("Iterator/{} not implemented", original_name),
+    }
+    indent.dec();
+    Ok(())
+}
+
+// Writes a match over the io::Result in `result`, wrapping the value produced by `ok_value` from `v`
+// into Ok and converting the io::Error into the matching IOError variant.
+fn write_io_result(
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    result: &str,
+    ok_value: &str,
+    result_ty: &Type,
+    result_ty_str: &str,
+) -> Result<()> {
+    let error_ty = get_variant_item_type(result_ty, "Err", program);
+    let error_ty_str = ir_type_to_rust_type(&error_ty, program);
+    let message_ty = get_variant_item_type(&error_ty, "Other", program);
+    let message_ty_str = ir_type_to_rust_type(&message_ty, program);
+    write!(
+        output_file,
+        "{}match {} {{
+",
+        indent, result
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}Ok(v) => {}::Ok({}),
+",
+        indent, result_ty_str, ok_value
+    )?;
+    write!(
+        output_file,
+        "{}Err(e) => {{
+",
+        indent
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}let message = {} {{ value: e.to_string() }};
+",
+        indent, message_ty_str
+    )?;
+    write!(
+        output_file,
+        "{}let error = match e.kind() {{
+",
+        indent
+    )?;
+    indent.inc();
+    for kind in &[
+        "NotFound",
+        "PermissionDenied",
+        "AlreadyExists",
+        "InvalidData",
+    ] {
+        write!(
+            output_file,
+            "{}std::io::ErrorKind::{} => {}::{}(message),
+",
+            indent, kind, error_ty_str, kind
+        )?;
+    }
+    write!(
+        output_file,
+        "{}_ => {}::Other(message),
+",
+        indent, error_ty_str
+    )?;
+    indent.dec();
+    write!(
+        output_file,
+        "{}}};
+",
+        indent
+    )?;
+    write!(
+        output_file,
+        "{}{}::Err(error)
+",
+        indent, result_ty_str
+    )?;
+    indent.dec();
+    write!(
+        output_file,
+        "{}}}
+",
+        indent
+    )?;
+    indent.dec();
+    write!(output_file, "{}}}", indent)
 }
 
-fn generate_string_builtins(
-    module: &str,
+fn generate_io_builtins(
     output_file: &mut dyn Write,
     program: &Program,
     indent: &mut Indent,
@@ -246,42 +1340,89 @@ fn generate_string_builtins(
     result_ty_str: &str,
 ) -> Result<()> {
     indent.inc();
+    let ok_ty = get_variant_item_type(result_ty, "Ok", program);
+    let ok_ty_str = ir_type_to_rust_type(&ok_ty, program);
     match original_name {
-        "opAdd" => {
+        "readLine" => {
+            let line_ty = get_option_item_type(&ok_ty, program);
+            let line_ty_str = ir_type_to_rust_type(&line_ty, program);
             write!(
                 output_file,
-                "{}let value = format!(\"{{}}{{}}\", arg0.value, arg1.value);\n",
+                "{}let mut line = std::string::String::new();\n",
                 indent
             )?;
             write!(
                 output_file,
-                "{}{} {{ value : value }}",
-                indent, result_ty_str
+                "{}let result = std::io::stdin().read_line(&mut line).map(|n| if n == 0 {{ None }} else {{ Some(line.trim_end_matches(&['\\n', '\\r'][..]).to_string()) }});\n",
+                indent
+            )?;
+            let ok_value = format!(
+                "match v {{ Some(line) => {}::Some({} {{ value: line }}), None => {}::None }}",
+                ok_ty_str, line_ty_str, ok_ty_str
+            );
+            write_io_result(
+                output_file,
+                program,
+                indent,
+                "result",
+                &ok_value,
+                result_ty,
+                result_ty_str,
             )?;
         }
-        "opEq" => {
-            generate_opeq_builtin_body(output_file, indent, result_ty_str)?;
+        "readFile" => {
+            let ok_value = format!("{} {{ value: v }}", ok_ty_str);
+            write_io_result(
+                output_file,
+                program,
+                indent,
+                "std::fs::read_to_string(&arg0.value)",
+                &ok_value,
+                result_ty,
+                result_ty_str,
+            )?;
         }
-        "partialCmp" => {
-            generate_partial_cmp_builtin_body(
+        "writeFile" => {
+            let ok_value = format!("{{ let _ = v; {} {{ }} }}", ok_ty_str);
+            write_io_result(
                 output_file,
                 program,
                 indent,
+                "std::fs::write(&arg0.value, &arg1.value)",
+                &ok_value,
                 result_ty,
                 result_ty_str,
             )?;
         }
-        "cmp" => {
-            generate_cmp_builtin_body(output_file, program, indent, result_ty_str)?;
+        "listDir" => {
+            let name_ty = get_external_type_arg(&ok_ty, program);
+            let name_ty_str = ir_type_to_rust_type(&name_ty, program);
+            write!(
+                output_file,
+                "{}let result = std::fs::read_dir(&arg0.value).map(|entries| {{ let mut names: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.file_name().to_string_lossy().to_string()).collect(); names.sort(); names }});\n",
+                indent
+            )?;
+            let ok_value = format!(
+                "{} {{ value: v.into_iter().map(|name| {} {{ value: name }}).collect() }}",
+                ok_ty_str, name_ty_str
+            );
+            write_io_result(
+                output_file,
+                program,
+                indent,
+                "result",
+                &ok_value,
+                result_ty,
+                result_ty_str,
+            )?;
         }
-        _ => panic!("{}/{} not implemented", module, original_name),
+        _ => panic!("Std.IO/{} not implemented", original_name),
     }
     indent.dec();
     Ok(())
 }
 
-fn generate_map_builtins(
-    _function: &Function,
+fn generate_env_builtins(
     output_file: &mut dyn Write,
     program: &Program,
     indent: &mut Indent,
@@ -291,223 +1432,189 @@ fn generate_map_builtins(
 ) -> Result<()> {
     indent.inc();
     match original_name {
-        "empty" => {
-            write!(
-                output_file,
-                "{}let value = std::collections::BTreeMap::new();\n",
-                indent
-            )?;
+        "args" => {
+            let arg_ty = get_external_type_arg(result_ty, program);
+            let arg_ty_str = ir_type_to_rust_type(&arg_ty, program);
             write!(
                 output_file,
-                "{}{} {{ value : value }}",
-                indent, result_ty_str
+                "{}{} {{ value: std::env::args().skip(1).map(|arg| {} {{ value: arg }}).collect() }}",
+                indent, result_ty_str, arg_ty_str
             )?;
         }
-        "insert" => {
-            let result_id = result_ty.get_typedef_id();
-            let tuple_record = program.typedefs.get(&result_id).get_record();
-            let option_ty = ir_type_to_rust_type(&tuple_record.fields[1].ty, program);
-            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+        "getEnv" => {
+            let value_ty = get_option_item_type(result_ty, program);
+            let value_ty_str = ir_type_to_rust_type(&value_ty, program);
             write!(
                 output_file,
-                "{}let value = match arg0.value.insert(arg1, arg2) {{\n",
+                "{}match std::env::var(&arg0.value) {{\n",
                 indent
             )?;
             indent.inc();
             write!(
                 output_file,
-                "{} Some(v) => {}::Some(v),\n",
-                indent, option_ty
+                "{} Ok(v) => {}::Some({} {{ value: v }}),\n",
+                indent, result_ty_str, value_ty_str
             )?;
-            write!(output_file, "{} None => {}::None,\n", indent, option_ty)?;
-            indent.dec();
-            write!(output_file, "{}}};\n", indent)?;
             write!(
                 output_file,
-                "{}{} {{ field_0 : arg0, field_1: value }}",
+                "{} Err(_) => {}::None,\n",
                 indent, result_ty_str
             )?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
         }
-        "remove" => {
-            let result_id = result_ty.get_typedef_id();
-            let tuple_record = program.typedefs.get(&result_id).get_record();
-            let option_ty = ir_type_to_rust_type(&tuple_record.fields[1].ty, program);
-            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
+        "exit" => {
             write!(
                 output_file,
-                "{}let value = match arg0.value.remove(&arg1) {{\n",
+                "{}let _ = std::io::Write::flush(&mut std::io::stdout());\n",
                 indent
             )?;
-            indent.inc();
-            write!(
-                output_file,
-                "{} Some(v) => {}::Some(v),\n",
-                indent, option_ty
-            )?;
-            write!(output_file, "{} None => {}::None,\n", indent, option_ty)?;
-            indent.dec();
-            write!(output_file, "{}}};\n", indent)?;
-            write!(
-                output_file,
-                "{}{} {{ field_0 : arg0, field_1: value }}",
-                indent, result_ty_str
-            )?;
-        }
-        "get" => {
-            write!(output_file, "{}match arg0.value.get(&arg1) {{\n", indent)?;
-            indent.inc();
             write!(
                 output_file,
-                "{} Some(v) => {}::Some(v.clone()),\n",
-                indent, result_ty_str
+                "{}std::process::exit(arg0.value as i32)",
+                indent
             )?;
-            write!(output_file, "{} None => {}::None,\n", indent, result_ty_str)?;
-            indent.dec();
-            write!(output_file, "{}}}", indent)?;
         }
-        _ => panic!("Map/{} not implemented", original_name),
+        _ => panic!("Std.Env/{} not implemented", original_name),
     }
     indent.dec();
     Ok(())
 }
 
-fn generate_list_builtins(
-    _function: &Function,
+fn get_single_field_value(record_ty: &Type, value: &str, program: &Program) -> String {
+    let record = program
+        .typedefs
+        .get(&record_ty.get_typedef_id())
+        .get_record();
+    let field = &record.fields[0];
+    if field.ty.is_boxed() {
+        format!("*{}.{}", value, field.name)
+    } else {
+        format!("{}.{}", value, field.name)
+    }
+}
+
+// the name of the type in the Siko source, without the id suffix of the MIR typedef
+fn get_source_type_name(typedef_id: TypeDefId, program: &Program) -> String {
+    let name = &program.typedefs.get(&typedef_id).get_record().name;
+    match name.rfind('_') {
+        Some(index) => name[..index].to_string(),
+        None => name.clone(),
+    }
+}
+
+fn generate_actor_builtins(
+    function: &Function,
     output_file: &mut dyn Write,
-    _program: &Program,
+    program: &Program,
     indent: &mut Indent,
     original_name: &str,
+    result_ty: &Type,
     result_ty_str: &str,
-    arg_types: Vec<String>,
 ) -> Result<()> {
+    let internal_module = get_module_name(MIR_INTERNAL_MODULE_NAME);
+    let mut fn_args = Vec::new();
+    function.function_type.get_args(&mut fn_args);
     indent.inc();
     match original_name {
-        "show" => {
+        "spawn" => {
+            let actor_ty = &fn_args[0];
+            let handlers = program
+                .actors
+                .get(&actor_ty.get_typedef_id())
+                .expect("Actor not found");
             write!(
                 output_file,
-                "{}let subs: Vec<_> = arg0.value.iter().map(|item| format!(\"{{}}\", item)).collect();\n",
+                "{}let (sender, receiver) = std::sync::mpsc::channel();\n",
                 indent
             )?;
             write!(
                 output_file,
-                "{}{} {{ value : format!(\"[{{}}]\", subs.join(\", \")) }}",
-                indent, result_ty_str
-            )?;
-        }
-        "toList" => {
-            write!(output_file, "{}let mut arg0 = arg0;\n", indent)?;
-            write!(output_file, "{}let mut value = Vec::new();\n", indent)?;
-            write!(output_file, "{}loop {{\n", indent)?;
-            indent.inc();
-            write!(
-                output_file,
-                "{}if let Some(v) = arg0.value.next() {{\n",
-                indent
+                "{}let id = crate::{}::register_actor(sender);\n",
+                indent, internal_module
             )?;
-            indent.inc();
-            write!(output_file, "{}value.push(v);\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}} else {{\n", indent)?;
-            indent.inc();
-            write!(output_file, "{}break;\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
             write!(
                 output_file,
-                "{}{} {{ value: value }}",
-                indent, result_ty_str
+                "{}let mut state = {};\n",
+                indent,
+                get_single_field_value(actor_ty, "arg0", program)
             )?;
-        }
-        "iter" => {
-            let list_type = &arg_types[0];
-            let base_list_type: Vec<_> = list_type.split("::").collect();
-            let list_iter_name = format!("{}_Iter", base_list_type[2]);
-            let iter_trait_name =
-                result_ty_str.replace("crate::Iterator::", "crate::Iterator::Trait_");
-
-            write!(output_file, "{}#[derive(Clone)]\n", indent)?;
-            write!(output_file, "{}pub struct {} {{\n", indent, list_iter_name)?;
-            indent.inc();
-            write!(output_file, "{}pub value: Vec<crate::Int::Int>,\n", indent)?;
-            write!(output_file, "{}pub index: usize,\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
-
             write!(
                 output_file,
-                "{}impl {} for {} {{\n",
-                indent, iter_trait_name, list_iter_name
+                "{}crate::{}::start_actor(move || {{\n",
+                indent, internal_module
             )?;
             indent.inc();
-            write!(
-                output_file,
-                "{}fn next(&mut self) -> Option<crate::Int::Int> {{\n",
-                indent
-            )?;
+            write!(output_file, "{}for message in receiver.iter() {{\n", indent)?;
             indent.inc();
-            write!(
-                output_file,
-                "{}if self.index >= self.value.len() {{\n",
-                indent
-            )?;
+            write!(output_file, "{}state = match message {{\n", indent)?;
             indent.inc();
-            write!(output_file, "{}None\n", indent)?;
+            for (protocol, handler) in handlers {
+                let handler = program.functions.get(handler);
+                let protocol_ty = Type::Named(Modifier::Owned, *protocol);
+                write!(
+                    output_file,
+                    "{}crate::{}::Message::{}(message) => crate::{}::{}(state, {}),\n",
+                    indent,
+                    internal_module,
+                    get_message_variant_name(*protocol),
+                    get_module_name(&handler.module),
+                    handler.name,
+                    get_single_field_value(&protocol_ty, "message", program)
+                )?;
+            }
+            let actor_name = get_source_type_name(actor_ty.get_typedef_id(), program);
+            for protocol in &program.protocols {
+                if handlers.contains_key(protocol) {
+                    continue;
+                }
+                let protocol_name = get_source_type_name(*protocol, program);
+                write!(
+                    output_file,
+                    "{}crate::{}::Message::{}(_) => panic!(\"actor {} has no handler for protocol {}\"),\n",
+                    indent,
+                    internal_module,
+                    get_message_variant_name(*protocol),
+                    actor_name,
+                    protocol_name
+                )?;
+            }
             indent.dec();
-            write!(output_file, "{}}} else {{\n", indent)?;
-            indent.inc();
+            write!(output_file, "{}}};\n", indent)?;
             write!(
                 output_file,
-                "{}let v = self.value[self.index].clone();\n",
-                indent
+                "{}crate::{}::message_done();\n",
+                indent, internal_module
             )?;
-            write!(output_file, "{}self.index += 1;\n", indent)?;
-            write!(output_file, "{}Some(v)\n", indent)?;
             indent.dec();
             write!(output_file, "{}}}\n", indent)?;
             indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
-
+            write!(output_file, "{}}});\n", indent)?;
+            let pid = program
+                .typedefs
+                .get(&result_ty.get_typedef_id())
+                .get_record();
+            let id_ty = ir_type_to_rust_type(&pid.fields[0].ty, program);
             write!(
                 output_file,
-                "{}fn box_clone(&self) -> Box<dyn {}> {{\n",
-                indent, iter_trait_name
+                "{}{} {{ {}: {} {{ value: id as i64 }} }}",
+                indent, result_ty_str, pid.fields[0].name, id_ty
             )?;
-            indent.inc();
-            write!(output_file, "{}Box::new(self.clone())\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
-
-            write!(output_file, "{}{} {{\n", indent, result_ty_str)?;
-            indent.inc();
+        }
+        "send" => {
+            let protocol_ty = &fn_args[1];
             write!(
                 output_file,
-                "{}value: Box::new({} {{\n",
-                indent, list_iter_name
+                "{}crate::{}::send_message({}.value as usize, crate::{}::Message::{}(arg1));\n",
+                indent,
+                internal_module,
+                get_single_field_value(&fn_args[0], "arg0", program),
+                internal_module,
+                get_message_variant_name(protocol_ty.get_typedef_id())
             )?;
-            indent.inc();
-            write!(output_file, "{}value: arg0.value.clone(),\n", indent)?;
-            write!(output_file, "{}index: 0,\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}),\n", indent)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
-        }
-        "opEq" => {
-            write!(output_file, "{}if arg0.value.eq(&arg1.value) {{\n", indent)?;
-            indent.inc();
-            write!(output_file, "{}{}::True\n", indent, result_ty_str)?;
-            indent.dec();
-            write!(output_file, "{}}} else {{\n", indent)?;
-            indent.inc();
-            write!(output_file, "{}{}::False\n", indent, result_ty_str)?;
-            indent.dec();
-            write!(output_file, "{}}}\n", indent)?;
+            write!(output_file, "{}{} {{ }}", indent, result_ty_str)?;
         }
-        _ => panic!("List/{} not implemented", original_name),
+        _ => panic!("Std.Actor/{} not implemented", original_name),
     }
     indent.dec();
     Ok(())
@@ -546,6 +1653,17 @@ pub fn generate_builtin(
                 result_ty_str,
             );
         }
+        "Char" => {
+            return generate_char_builtins(
+                function.module.as_ref(),
+                output_file,
+                program,
+                indent,
+                original_name,
+                result_ty,
+                result_ty_str,
+            );
+        }
         "Float" => {
             return generate_num_builtins(
                 function.module.as_ref(),
@@ -557,6 +1675,9 @@ pub fn generate_builtin(
                 result_ty_str,
             );
         }
+        MATH_MODULE_NAME => {
+            return generate_math_builtins(output_file, indent, original_name, result_ty_str);
+        }
         "Map" => {
             return generate_map_builtins(
                 function,
@@ -568,6 +1689,58 @@ pub fn generate_builtin(
                 result_ty_str,
             );
         }
+        "Set" => {
+            return generate_set_builtins(
+                function,
+                output_file,
+                program,
+                indent,
+                original_name,
+                result_ty_str,
+            );
+        }
+        STD_ACTOR_MODULE_NAME => {
+            return generate_actor_builtins(
+                function,
+                output_file,
+                program,
+                indent,
+                original_name,
+                result_ty,
+                result_ty_str,
+            );
+        }
+        STD_IO_MODULE_NAME => {
+            return generate_io_builtins(
+                output_file,
+                program,
+                indent,
+                original_name,
+                result_ty,
+                result_ty_str,
+            );
+        }
+        STD_ENV_MODULE_NAME => {
+            return generate_env_builtins(
+                output_file,
+                program,
+                indent,
+                original_name,
+                result_ty,
+                result_ty_str,
+            );
+        }
+        "Iterator" => {
+            return generate_iterator_builtins(
+                function,
+                output_file,
+                program,
+                indent,
+                original_name,
+                result_ty,
+                result_ty_str,
+            );
+        }
         "List" => {
             return generate_list_builtins(
                 function,
@@ -576,7 +1749,6 @@ pub fn generate_builtin(
                 indent,
                 original_name,
                 result_ty_str,
-                arg_types,
             );
         }
         _ => {
@@ -643,96 +1815,6 @@ pub fn generate_builtin(
                     )?;
                     write!(output_file, "{}{} {{ }}", indent, result_ty_str)?;
                 }
-                ("Iterator", "map") => {
-                    let struct_name = to_first_uppercase(function.name.clone());
-                    write!(output_file, "{}struct {} {{\n", indent, struct_name)?;
-                    indent.inc();
-                    for (index, arg_type) in arg_types.iter().enumerate() {
-                        write!(output_file, "{}pub arg{}: {},\n", indent, index, arg_type)?;
-                    }
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-
-                    write!(output_file, "{}impl Clone for {} {{\n", indent, struct_name)?;
-                    indent.inc();
-                    write!(
-                        output_file,
-                        "{}fn clone(&self) -> {} {{\n",
-                        indent, struct_name
-                    )?;
-                    indent.inc();
-                    write!(output_file, "{}{} {{\n", indent, struct_name)?;
-                    indent.inc();
-                    write!(output_file, "{}arg0: self.arg0.clone(),\n", indent,)?;
-                    write!(output_file, "{}arg1: self.arg1.clone(),\n", indent,)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-
-                    let iter_id = result_ty.get_typedef_id();
-                    let iter = program.typedefs.get(&iter_id).get_record();
-                    let iter_trait_name = format!("Trait_{}", iter.name);
-
-                    write!(
-                        output_file,
-                        "{}impl {} for {} {{\n",
-                        indent, iter_trait_name, struct_name
-                    )?;
-                    indent.inc();
-                    write!(
-                        output_file,
-                        "{}fn next(&mut self) -> Option<crate::Int::Int> {{\n",
-                        indent
-                    )?;
-                    indent.inc();
-                    write!(
-                        output_file,
-                        "{}if let Some(value) = self.arg1.value.next() {{\n",
-                        indent
-                    )?;
-                    indent.inc();
-                    write!(output_file, "{}Some(self.arg0.call(value))\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}} else {{\n", indent)?;
-                    indent.inc();
-                    write!(output_file, "{}None\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-                    write!(
-                        output_file,
-                        "{}fn box_clone(&self) -> Box<dyn {}> {{\n",
-                        indent, iter_trait_name
-                    )?;
-                    indent.inc();
-                    write!(output_file, "{}Box::new(self.clone())\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-                    indent.dec();
-                    write!(output_file, "{}}}\n", indent)?;
-
-                    write!(output_file, "{}{} {{\n", indent, result_ty_str)?;
-                    indent.inc();
-                    write!(
-                        output_file,
-                        "{}value: Box::new({} {{\n",
-                        indent, struct_name
-                    )?;
-                    indent.inc();
-                    write!(output_file, "{}arg0: arg0,\n", indent,)?;
-                    write!(output_file, "{}arg1: arg1,\n", indent,)?;
-                    indent.dec();
-                    write!(output_file, "{}}}),\n", indent,)?;
-                    indent.dec();
-                    write!(output_file, "{}}}", indent)?;
-                }
-                ("Iterator", "filter") => {
-                    write!(output_file, "{}{} }}\n", indent, result_ty_str)?;
-                }
                 _ => panic!("{}/{} not implemented", function.module, function.name),
             }
         }
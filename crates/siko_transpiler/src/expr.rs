@@ -1,3 +1,5 @@
+use crate::pattern::write_case_pattern;
+use crate::pattern::write_list_matches;
 use crate::pattern::write_pattern;
 use crate::types::ir_type_to_rust_type;
 use crate::util::arg_name;
@@ -89,7 +91,7 @@ pub fn write_expr(
         }
         Expr::Bind(pattern, rhs) => {
             write!(output_file, "let ")?;
-            write_pattern(*pattern, output_file, program, indent)?;
+            write_pattern(*pattern, output_file, program, indent, &mut Vec::new())?;
             write!(output_file, " = ")?;
             write_expr(*rhs, output_file, program, indent)?;
             write!(output_file, ";")?;
@@ -161,7 +163,7 @@ pub fn write_expr(
         Expr::FloatLiteral(f) => {
             let ty = program.get_expr_type(&expr_id);
             let ty = ir_type_to_rust_type(ty, program);
-            write!(output_file, "{} {{ value: {:.5} }}", ty, f)?;
+            write!(output_file, "{} {{ value: {:?} }}", ty, f)?;
         }
         Expr::CharLiteral(c) => {
             let ty = program.get_expr_type(&expr_id);
@@ -189,12 +191,25 @@ pub fn write_expr(
             write_expr(*body, output_file, program, indent)?;
             write!(output_file, ") {{\n")?;
             indent.inc();
+            let mut guarded = false;
             for case in cases {
                 write!(output_file, "{}", indent)?;
-                write_pattern(case.pattern_id, output_file, program, indent)?;
+                let lists = write_case_pattern(case.pattern_id, output_file, program, indent)?;
                 write!(output_file, " => {{")?;
+                write_list_matches(&lists, "unreachable!()", output_file, program, indent)?;
                 write_expr(case.body, output_file, program, indent)?;
                 write!(output_file, "}}\n")?;
+                if let Pattern::Guarded(..) = program.patterns.get(&case.pattern_id).item {
+                    guarded = true;
+                }
+                if !lists.is_empty() {
+                    guarded = true;
+                }
+            }
+            // the guards and the list patterns are not visible to rustc, the pattern checker
+            // already made sure that the cases are exhaustive
+            if guarded {
+                write!(output_file, "{}_ => unreachable!(),\n", indent)?;
             }
             indent.dec();
             write!(output_file, "{}}}", indent)?;
@@ -266,6 +281,50 @@ pub fn write_expr(
             write!(output_file, "return ")?;
             write_expr(*rhs, output_file, program, indent)?;
         }
+        Expr::Loop(pattern, initializer, items) => {
+            write!(output_file, "{{\n")?;
+            indent.inc();
+            write!(output_file, "{}let mut loop_var = ", indent)?;
+            write_expr(*initializer, output_file, program, indent)?;
+            write!(output_file, ";\n{}loop {{\n", indent)?;
+            indent.inc();
+            write!(output_file, "{}loop_var = {{\n", indent)?;
+            indent.inc();
+            write!(output_file, "{}let ", indent)?;
+            write_pattern(*pattern, output_file, program, indent, &mut Vec::new())?;
+            write!(output_file, " = loop_var;\n")?;
+            for (index, item) in items.iter().enumerate() {
+                write!(output_file, "{}", indent)?;
+                let is_statement = write_expr(*item, output_file, program, indent)?;
+                if is_statement {
+                    if index == items.len() - 1 {
+                        let ty = program.get_expr_type(&expr_id);
+                        write!(output_file, "{} {{ }} ", ir_type_to_rust_type(ty, program))?;
+                    } else {
+                        write!(output_file, "\n")?;
+                    }
+                } else {
+                    if index != items.len() - 1 {
+                        write!(output_file, ";\n")?;
+                    }
+                }
+            }
+            indent.dec();
+            write!(output_file, "\n{}}};\n", indent)?;
+            indent.dec();
+            write!(output_file, "{}}}\n", indent)?;
+            indent.dec();
+            write!(output_file, "{}}}", indent)?;
+        }
+        Expr::Continue(rhs) => {
+            write!(output_file, "{{ loop_var = ")?;
+            write_expr(*rhs, output_file, program, indent)?;
+            write!(output_file, "; continue; }}")?;
+        }
+        Expr::Break(rhs) => {
+            write!(output_file, "break ")?;
+            write_expr(*rhs, output_file, program, indent)?;
+        }
     }
     Ok(is_statement)
 }
@@ -2,9 +2,12 @@ use crate::types::ir_type_to_rust_type;
 use crate::util::arg_name;
 use crate::util::Indent;
 use siko_constants::MIR_FUNCTION_TRAIT_NAME;
+use siko_constants::MIR_ITERATOR_TRAIT_NAME;
+use siko_mir::data::TypeDefId;
 use siko_mir::program::Program;
 use siko_mir::types::Closure;
 use siko_mir::types::DynamicCallTrait;
+use siko_mir::types::Modifier;
 use siko_mir::types::PartialFunctionCall;
 use siko_mir::types::Type;
 use std::io::Result;
@@ -126,7 +129,7 @@ fn write_dyn_trait_impl_save_arg(
         } else {
             write!(
                 output_file,
-                "{}{}: self.{}.take(),\n",
+                "{}{}: self.{}.clone(),\n",
                 indent,
                 arg_name(index),
                 arg_name(index)
@@ -197,7 +200,7 @@ fn write_dyn_trait_impl_real_call(
     for index in 0..partial_function_call.fields.len() {
         write!(
             output_file,
-            "self.{}.take().expect(\"Missing arg\"), ",
+            "self.{}.clone().expect(\"Missing arg\"), ",
             arg_name(index)
         )?;
     }
@@ -241,6 +244,41 @@ fn write_function_trait(output_file: &mut dyn Write, indent: &mut Indent) -> Res
     Ok(())
 }
 
+// Every std adapter chain that is cloneable can be stored behind an Iterator value.
+fn write_iterator_trait(output_file: &mut dyn Write, indent: &mut Indent) -> Result<()> {
+    write!(
+        output_file,
+        "{}pub trait {}<T>: std::iter::Iterator<Item = T> {{\n",
+        indent, MIR_ITERATOR_TRAIT_NAME
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}fn box_clone(&self) -> Box<dyn {}<T>>;\n",
+        indent, MIR_ITERATOR_TRAIT_NAME
+    )?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    write!(
+        output_file,
+        "{}impl<T: 'static, I: std::iter::Iterator<Item = T> + Clone + 'static> {}<T> for I {{\n",
+        indent, MIR_ITERATOR_TRAIT_NAME
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}fn box_clone(&self) -> Box<dyn {}<T>> {{\n",
+        indent, MIR_ITERATOR_TRAIT_NAME
+    )?;
+    indent.inc();
+    write!(output_file, "{}Box::new(self.clone())\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    Ok(())
+}
+
 fn write_closure_def(
     output_file: &mut dyn Write,
     indent: &mut Indent,
@@ -309,6 +347,132 @@ fn write_closure_impl(
     Ok(())
 }
 
+pub fn get_message_variant_name(protocol: TypeDefId) -> String {
+    format!("Protocol{}", protocol.id)
+}
+
+fn write_actor_runtime(
+    output_file: &mut dyn Write,
+    indent: &mut Indent,
+    program: &Program,
+) -> Result<()> {
+    write!(
+        output_file,
+        "{}pub enum Message {{
+",
+        indent
+    )?;
+    indent.inc();
+    for protocol in &program.protocols {
+        let ty = Type::Named(Modifier::Owned, *protocol);
+        write!(
+            output_file,
+            "{}{}({}),\n",
+            indent,
+            get_message_variant_name(*protocol),
+            ir_type_to_rust_type(&ty, program)
+        )?;
+    }
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    write!(
+        output_file,
+        "{}static SENDERS: std::sync::Mutex<Vec<std::sync::mpsc::Sender<Message>>> = std::sync::Mutex::new(Vec::new());\n",
+        indent
+    )?;
+    write!(
+        output_file,
+        "{}static PENDING_MESSAGES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);\n",
+        indent
+    )?;
+    write!(
+        output_file,
+        "{}pub fn register_actor(sender: std::sync::mpsc::Sender<Message>) -> usize {{\n",
+        indent
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}let mut senders = SENDERS.lock().expect(\"Lock failed\");\n",
+        indent
+    )?;
+    write!(output_file, "{}senders.push(sender);\n", indent)?;
+    write!(output_file, "{}senders.len() - 1\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    write!(
+        output_file,
+        "{}pub fn start_actor<F: FnOnce() + Send + 'static>(f: F) {{\n",
+        indent
+    )?;
+    indent.inc();
+    write!(output_file, "{}std::thread::spawn(move || {{\n", indent)?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}if std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).is_err() {{\n",
+        indent
+    )?;
+    indent.inc();
+    write!(output_file, "{}std::process::exit(1);\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}});\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    write!(
+        output_file,
+        "{}pub fn send_message(pid: usize, message: Message) {{\n",
+        indent
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}PENDING_MESSAGES.fetch_add(1, std::sync::atomic::Ordering::SeqCst);\n",
+        indent
+    )?;
+    write!(
+        output_file,
+        "{}let sender = SENDERS.lock().expect(\"Lock failed\")[pid].clone();\n",
+        indent
+    )?;
+    write!(
+        output_file,
+        "{}sender.send(message).expect(\"Send failed\");\n",
+        indent
+    )?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    write!(output_file, "{}pub fn message_done() {{\n", indent)?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}PENDING_MESSAGES.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);\n",
+        indent
+    )?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    write!(output_file, "{}pub fn wait_for_actors() {{\n", indent)?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}while PENDING_MESSAGES.load(std::sync::atomic::Ordering::SeqCst) != 0 {{\n",
+        indent
+    )?;
+    indent.inc();
+    write!(
+        output_file,
+        "{}std::thread::sleep(std::time::Duration::from_millis(1));\n",
+        indent
+    )?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    indent.dec();
+    write!(output_file, "{}}}\n", indent)?;
+    Ok(())
+}
+
 pub fn write_internal_defs(
     output_file: &mut dyn Write,
     program: &Program,
@@ -350,11 +514,16 @@ pub fn write_internal_defs(
     }
 
     write_function_trait(output_file, indent)?;
+    write_iterator_trait(output_file, indent)?;
 
     for (_, closure) in &program.closures {
         write_closure_def(output_file, indent, closure, program)?;
         write_closure_impl(output_file, indent, closure, program)?;
         write_closure_clone_impl(output_file, indent, closure)?;
     }
+
+    if !program.protocols.is_empty() {
+        write_actor_runtime(output_file, indent, program)?;
+    }
     Ok(())
 }
@@ -8,11 +8,184 @@ use siko_mir::program::Program;
 use std::io::Result;
 use std::io::Write;
 
+fn list_name(pattern_id: PatternId) -> String {
+    format!("list_{}", pattern_id.id)
+}
+
+fn collect_bindings(pattern_id: PatternId, program: &Program, bindings: &mut Vec<String>) {
+    let pattern = &program.patterns.get(&pattern_id).item;
+    match pattern {
+        Pattern::Binding(name) => {
+            bindings.push(name.clone());
+        }
+        Pattern::Record(_, items) | Pattern::Variant(_, _, items) => {
+            for item in items {
+                collect_bindings(*item, program, bindings);
+            }
+        }
+        Pattern::Guarded(pattern, _) => {
+            collect_bindings(*pattern, program, bindings);
+        }
+        Pattern::List(_) | Pattern::Cons(..) => {
+            bindings.push(list_name(pattern_id));
+        }
+        Pattern::Wildcard
+        | Pattern::IntegerLiteral(_)
+        | Pattern::CharLiteral(_)
+        | Pattern::CharRange(..)
+        | Pattern::IntegerRange(..)
+        | Pattern::StringLiteral(_) => {}
+    }
+}
+
+// matches the value against the pattern and binds the variables of the pattern
+fn write_sub_match(
+    pattern_id: PatternId,
+    value: &str,
+    fail: &str,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+    lists: &mut Vec<PatternId>,
+) -> Result<()> {
+    let mut bindings = Vec::new();
+    collect_bindings(pattern_id, program, &mut bindings);
+    let bindings: Vec<_> = bindings.iter().map(|b| format!("{},", b)).collect();
+    let bindings = bindings.join(" ");
+    write!(output_file, "let ({}) = match ({}) {{ ", bindings, value)?;
+    write_pattern(pattern_id, output_file, program, indent, lists)?;
+    write!(output_file, " => ({}), _ => {} }}; ", bindings, fail)?;
+    Ok(())
+}
+
+// Rust cannot match the items of a Vec in a pattern so list patterns are written as a binding
+// and their items are matched separately, in the guard and again in the body of the case
+pub fn write_list_matches(
+    lists: &[PatternId],
+    fail: &str,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+) -> Result<()> {
+    let mut lists = lists.to_vec();
+    while !lists.is_empty() {
+        let pattern_id = lists.remove(0);
+        let name = list_name(pattern_id);
+        let items_name = format!("items_{}", pattern_id.id);
+        write!(
+            output_file,
+            "let mut {} = {}.value.clone(); ",
+            items_name, name
+        )?;
+        match &program.patterns.get(&pattern_id).item {
+            Pattern::List(items) => {
+                write!(
+                    output_file,
+                    "if {}.len() != {} {{ {} }} ",
+                    items_name,
+                    items.len(),
+                    fail
+                )?;
+                write!(
+                    output_file,
+                    "let mut {} = {}.into_iter(); ",
+                    items_name, items_name
+                )?;
+                for item in items {
+                    let value = format!("{}.next().unwrap()", items_name);
+                    write_sub_match(
+                        *item,
+                        &value,
+                        fail,
+                        output_file,
+                        program,
+                        indent,
+                        &mut lists,
+                    )?;
+                }
+            }
+            Pattern::Cons(head, tail) => {
+                let ty = program.get_pattern_type(&pattern_id);
+                let ty = ir_type_to_rust_type(ty, program);
+                write!(output_file, "if {}.is_empty() {{ {} }} ", items_name, fail)?;
+                let value = format!("{}.remove(0)", items_name);
+                write_sub_match(
+                    *head,
+                    &value,
+                    fail,
+                    output_file,
+                    program,
+                    indent,
+                    &mut lists,
+                )?;
+                let value = format!("{} {{ value: {} }}", ty, items_name);
+                write_sub_match(
+                    *tail,
+                    &value,
+                    fail,
+                    output_file,
+                    program,
+                    indent,
+                    &mut lists,
+                )?;
+            }
+            _ => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+// writes the pattern of a case with its guard, returns the list patterns that have to be
+// matched in the body as well
+pub fn write_case_pattern(
+    pattern_id: PatternId,
+    output_file: &mut dyn Write,
+    program: &Program,
+    indent: &mut Indent,
+) -> Result<Vec<PatternId>> {
+    let (pattern_id, guard) = match &program.patterns.get(&pattern_id).item {
+        Pattern::Guarded(pattern, expr) => (*pattern, Some(*expr)),
+        _ => (pattern_id, None),
+    };
+    let mut lists = Vec::new();
+    write_pattern(pattern_id, output_file, program, indent, &mut lists)?;
+    if lists.is_empty() && guard.is_none() {
+        return Ok(lists);
+    }
+    let label = format!("'guard_{}", pattern_id.id);
+    write!(output_file, " if {}: {{ ", label)?;
+    write_list_matches(
+        &lists,
+        &format!("break {} false", label),
+        output_file,
+        program,
+        indent,
+    )?;
+    match guard {
+        Some(expr) => {
+            write!(output_file, "match ")?;
+            write_expr(expr, output_file, program, indent)?;
+            let ty = program.get_expr_type(&expr);
+            let ty = ir_type_to_rust_type(ty, program);
+            write!(
+                output_file,
+                "  {{ {}::True => true, {}::False => false }} }}",
+                ty, ty
+            )?;
+        }
+        None => {
+            write!(output_file, "true }}")?;
+        }
+    }
+    Ok(lists)
+}
+
 pub fn write_pattern(
     pattern_id: PatternId,
     output_file: &mut dyn Write,
     program: &Program,
     indent: &mut Indent,
+    lists: &mut Vec<PatternId>,
 ) -> Result<()> {
     let pattern = &program.patterns.get(&pattern_id).item;
     match pattern {
@@ -26,7 +199,7 @@ pub fn write_pattern(
             for (index, item) in items.iter().enumerate() {
                 let field = &record.fields[index];
                 write!(output_file, "{}: ", field.name)?;
-                write_pattern(*item, output_file, program, indent)?;
+                write_pattern(*item, output_file, program, indent, lists)?;
                 write!(output_file, ", ")?;
             }
             write!(output_file, "}}")?;
@@ -44,7 +217,7 @@ pub fn write_pattern(
             if !items.is_empty() {
                 write!(output_file, "(")?;
                 for (index, item) in items.iter().enumerate() {
-                    write_pattern(*item, output_file, program, indent)?;
+                    write_pattern(*item, output_file, program, indent, lists)?;
                     if index != items.len() - 1 {
                         write!(output_file, ", ")?;
                     }
@@ -52,17 +225,9 @@ pub fn write_pattern(
                 write!(output_file, ")")?;
             }
         }
-        Pattern::Guarded(pattern, expr) => {
-            write_pattern(*pattern, output_file, program, indent)?;
-            write!(output_file, " if {{ match ")?;
-            write_expr(*expr, output_file, program, indent)?;
-            let ty = program.get_expr_type(expr);
-            let ty = ir_type_to_rust_type(ty, program);
-            write!(
-                output_file,
-                "  {{ {}::True => true, {}::False => false }} }}",
-                ty, ty
-            )?;
+        Pattern::Guarded(..) => {
+            // guards are written by write_case_pattern
+            unreachable!()
         }
         Pattern::Wildcard => {
             write!(output_file, "_")?;
@@ -96,11 +261,35 @@ pub fn write_pattern(
             }
             write!(output_file, ".contains(&p.value)")?;
         }
+        Pattern::IntegerRange(start, end, kind) => {
+            let ty = program.get_pattern_type(&pattern_id);
+            let ty = ir_type_to_rust_type(ty, program);
+            match kind {
+                RangeKind::Exclusive => {
+                    if start < end {
+                        write!(output_file, "{} {{ value: {}..={} }}", ty, start, end - 1)?;
+                    } else {
+                        write!(output_file, "p if false")?;
+                    }
+                }
+                RangeKind::Inclusive => {
+                    if start <= end {
+                        write!(output_file, "{} {{ value: {}..={} }}", ty, start, end)?;
+                    } else {
+                        write!(output_file, "p if false")?;
+                    }
+                }
+            }
+        }
         Pattern::StringLiteral(s) => {
             let ty = program.get_pattern_type(&pattern_id);
             let ty = ir_type_to_rust_type(ty, program);
             write!(output_file, "{} {{ value: {} }}", ty, s)?;
         }
+        Pattern::List(_) | Pattern::Cons(..) => {
+            write!(output_file, "{}", list_name(pattern_id))?;
+            lists.push(pattern_id);
+        }
     }
     Ok(())
 }
@@ -1,4 +1,5 @@
 use crate::module::Module;
+use crate::util::get_module_name;
 use crate::util::Indent;
 use siko_constants::MIR_INTERNAL_MODULE_NAME;
 use siko_mir::data::TypeDef;
@@ -42,6 +43,14 @@ impl RustProgram {
         write!(output_file, "fn main() {{\n")?;
         indent.inc();
         write!(output_file, "{}crate::Main::main_0();\n", indent)?;
+        if !program.actors.is_empty() {
+            write!(
+                output_file,
+                "{}crate::{}::wait_for_actors();\n",
+                indent,
+                get_module_name(MIR_INTERNAL_MODULE_NAME)
+            )?;
+        }
         write!(output_file, "}}\n")?;
         Ok(())
     }
@@ -57,7 +66,8 @@ impl Transpiler {
         write!(output_file, "#![allow(non_camel_case_types)]\n")?;
         write!(output_file, "#![allow(unused_variables)]\n")?;
         write!(output_file, "#![allow(dead_code)]\n")?;
-        write!(output_file, "#![allow(unused_parens)]\n\n")?;
+        write!(output_file, "#![allow(unused_parens)]\n")?;
+        write!(output_file, "#![allow(unreachable_patterns)]\n\n")?;
         let mut rust_program = RustProgram::new();
         rust_program.get_module(MIR_INTERNAL_MODULE_NAME.to_string());
         for (id, function) in program.functions.items.iter() {
@@ -1,5 +1,7 @@
 use crate::types::ir_type_to_rust_type;
 use crate::util::Indent;
+use siko_constants::MIR_INTERNAL_MODULE_NAME;
+use siko_constants::MIR_ITERATOR_TRAIT_NAME;
 use siko_mir::data::ExternalDataKind;
 use siko_mir::data::RecordKind;
 use siko_mir::data::TypeDef;
@@ -89,32 +91,31 @@ pub fn write_typedef(
                         indent.dec();
                         write!(output_file, "{}}}\n", indent)?;
                     }
-                    ExternalDataKind::Iterator => {
-                        let elem_ty = ir_type_to_rust_type(&args[0], program);
+                    ExternalDataKind::Set => {
                         write!(
                             output_file,
-                            "{}pub trait Trait_{} {{\n",
-                            indent, record.name
+                            "{}#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]\n",
+                            indent
                         )?;
+                        write!(output_file, "{}pub struct {} {{\n", indent, record.name)?;
                         indent.inc();
+                        let elem_ty = ir_type_to_rust_type(&args[0], program);
                         write!(
                             output_file,
-                            "{}fn next(&mut self) -> Option<{}>;\n",
+                            "{}pub value: std::collections::BTreeSet<{}>,\n",
                             indent, elem_ty
                         )?;
-                        write!(
-                            output_file,
-                            "{}fn box_clone(&self) -> Box<dyn Trait_{}>;\n",
-                            indent, record.name
-                        )?;
                         indent.dec();
                         write!(output_file, "{}}}\n", indent)?;
+                    }
+                    ExternalDataKind::Iterator => {
+                        let elem_ty = ir_type_to_rust_type(&args[0], program);
                         write!(output_file, "{}pub struct {} {{\n", indent, record.name)?;
                         indent.inc();
                         write!(
                             output_file,
-                            "{}pub value: Box<dyn Trait_{}>,\n",
-                            indent, record.name
+                            "{}pub value: Box<dyn crate::{}::{}<{}>>,\n",
+                            indent, MIR_INTERNAL_MODULE_NAME, MIR_ITERATOR_TRAIT_NAME, elem_ty
                         )?;
                         indent.dec();
                         write!(output_file, "{}}}\n", indent)?;
@@ -136,6 +137,25 @@ pub fn write_typedef(
                         write!(output_file, "{}}}\n", indent)?;
                         indent.dec();
                         write!(output_file, "{}}}\n", indent)?;
+
+                        write!(
+                            output_file,
+                            "{}impl std::iter::Iterator for {} {{\n",
+                            indent, record.name
+                        )?;
+                        indent.inc();
+                        write!(output_file, "{}type Item = {};\n", indent, elem_ty)?;
+                        write!(
+                            output_file,
+                            "{}fn next(&mut self) -> Option<{}> {{\n",
+                            indent, elem_ty
+                        )?;
+                        indent.inc();
+                        write!(output_file, "{}self.value.next()\n", indent)?;
+                        indent.dec();
+                        write!(output_file, "{}}}\n", indent)?;
+                        indent.dec();
+                        write!(output_file, "{}}}\n", indent)?;
                     }
                     ExternalDataKind::List => {
                         let elem_ty = ir_type_to_rust_type(&args[0], program);
@@ -5,6 +5,7 @@ use crate::type_store::TypeStore;
 use crate::util::create_general_function_type_info;
 use siko_ir::expr::Expr;
 use siko_ir::expr::ExprId;
+use siko_ir::function::FunctionId;
 use siko_ir::pattern::Pattern;
 use siko_ir::pattern::PatternId;
 use siko_ir::program::Program;
@@ -12,9 +13,11 @@ use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
 use siko_ir::walker::Visitor;
 use siko_location_info::location_id::LocationId;
+use siko_util::dependency_processor::DependencyGroup;
 
 pub struct ClassConstraintChecker<'a> {
     program: &'a mut Program,
+    group: &'a DependencyGroup<FunctionId>,
     type_store: &'a mut TypeStore,
     errors: &'a mut Vec<TypecheckError>,
     type_info_provider: &'a mut TypeInfoProvider,
@@ -23,12 +26,14 @@ pub struct ClassConstraintChecker<'a> {
 impl<'a> ClassConstraintChecker<'a> {
     pub fn new(
         program: &'a mut Program,
+        group: &'a DependencyGroup<FunctionId>,
         type_store: &'a mut TypeStore,
         errors: &'a mut Vec<TypecheckError>,
         type_info_provider: &'a mut TypeInfoProvider,
     ) -> ClassConstraintChecker<'a> {
         ClassConstraintChecker {
             program: program,
+            group: group,
             type_store: type_store,
             errors: errors,
             type_info_provider: type_info_provider,
@@ -47,11 +52,15 @@ impl<'a> ClassConstraintChecker<'a> {
         let r = unifier.unify(ty1, ty2);
         assert!(r.is_ok());
         let constraints = unifier.get_constraints();
+        let mut unifiers = Vec::new();
         for constraint in &constraints {
-            let mut unifiers = Vec::new();
+            let mut constraint_ty = constraint.ty.clone();
+            for (unifier, _) in &unifiers {
+                constraint_ty.apply(unifier);
+            }
             if !self.program.instance_resolver.check_instance(
                 constraint.class_id,
-                &constraint.ty,
+                &constraint_ty,
                 location,
                 &mut unifiers,
             ) {
@@ -67,6 +76,17 @@ impl<'a> ClassConstraintChecker<'a> {
         } else {
             if let Some(func_type_info) = func_type_info {
                 func_type_info.apply(&unifier);
+                for (unifier, _) in &unifiers {
+                    func_type_info.apply(unifier);
+                }
+            }
+            // constraints required by the instances are propagated to the type vars of the group
+            for (unifier, _) in &unifiers {
+                self.type_store.apply(unifier);
+                for id in &self.group.items {
+                    let info = self.type_info_provider.function_type_info_store.get_mut(id);
+                    info.apply(unifier);
+                }
             }
         }
     }
@@ -1,6 +1,7 @@
 use siko_ir::expr::ExprId;
 use siko_ir::function::FunctionId;
 use siko_ir::program::Program;
+use siko_ir::substitution::Constraint;
 use siko_ir::type_var_generator::TypeVarGenerator;
 use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
@@ -21,6 +22,10 @@ impl FunctionTypeInfoStore {
         self.function_type_info_map.insert(id, function_type_info);
     }
 
+    pub fn remove(&mut self, id: &FunctionId) {
+        self.function_type_info_map.remove(id);
+    }
+
     pub fn get(&self, id: &FunctionId) -> &FunctionTypeInfo {
         self.function_type_info_map
             .get(id)
@@ -59,6 +64,7 @@ pub struct FunctionTypeInfo {
     pub typed: bool,
     pub result: Type,
     pub function_type: Type,
+    pub multi_param_constraints: Vec<Constraint>,
     pub body: Option<ExprId>,
 }
 
@@ -76,6 +82,11 @@ impl FunctionTypeInfo {
         if self.function_type.apply(unifier) {
             changed = true;
         }
+        for constraint in &mut self.multi_param_constraints {
+            if constraint.ty.apply(unifier) {
+                changed = true;
+            }
+        }
         changed
     }
 
@@ -93,6 +104,14 @@ impl FunctionTypeInfo {
             function_type: self
                 .function_type
                 .duplicate(&mut arg_map, type_var_generator),
+            multi_param_constraints: self
+                .multi_param_constraints
+                .iter()
+                .map(|constraint| Constraint {
+                    class_id: constraint.class_id,
+                    ty: constraint.ty.duplicate(&mut arg_map, type_var_generator),
+                })
+                .collect(),
             body: self.body,
         }
     }
@@ -104,6 +123,14 @@ impl FunctionTypeInfo {
             typed: self.typed,
             result: self.result.remove_fixed_types(),
             function_type: self.function_type.remove_fixed_types(),
+            multi_param_constraints: self
+                .multi_param_constraints
+                .iter()
+                .map(|constraint| Constraint {
+                    class_id: constraint.class_id,
+                    ty: constraint.ty.remove_fixed_types(),
+                })
+                .collect(),
             body: self.body,
         }
     }
@@ -111,4 +138,5 @@ impl FunctionTypeInfo {
 
 pub struct ClassMemberTypeInfo {
     pub ty: Type,
+    pub multi_param_constraint: Option<Constraint>,
 }
@@ -18,8 +18,17 @@ pub enum TypecheckError {
     CyclicClassDependencies(LocationId, String),
     MissingInstance(String, LocationId),
     ClassNotAutoDerivable(String, LocationId),
-    UnreachablePattern(LocationId),
-    NonExhaustivePattern(LocationId),
+    UnreachablePattern(LocationId, Option<LocationId>),
+    NonExhaustivePattern(Vec<String>, LocationId),
+    EmptyRangePattern(LocationId),
+    InvalidProtocolHandler(String, String, String, LocationId),
+    TypedHole(
+        String,
+        String,
+        Vec<(String, String)>,
+        Vec<(String, String)>,
+        LocationId,
+    ),
 }
 
 #[derive(Debug)]
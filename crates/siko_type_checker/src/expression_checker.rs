@@ -352,6 +352,7 @@ impl<'a> Visitor for ExpressionChecker<'a> {
             }
             Expr::Break(..) => {}
             Expr::Continue(..) => {}
+            Expr::Hole(..) => {}
         }
     }
 
@@ -377,6 +378,7 @@ impl<'a> Visitor for ExpressionChecker<'a> {
             Pattern::StringLiteral(_) => {}
             Pattern::CharLiteral(_) => {}
             Pattern::CharRange(_, _, _) => {}
+            Pattern::IntegerRange(_, _, _) => {}
             Pattern::Tuple(items) => {
                 let ty = self.type_store.get_pattern_type(&pattern_id).clone();
                 if let Type::Tuple(item_types) = ty {
@@ -399,6 +401,22 @@ impl<'a> Visitor for ExpressionChecker<'a> {
                 }
             }
             Pattern::Wildcard => {}
+            Pattern::List(items) => {
+                if let Some(first) = items.first() {
+                    let ty = self.type_store.get_pattern_type(first).clone();
+                    let ty = self.program.get_list_type(ty);
+                    self.match_pattern_with(pattern_id, &ty);
+                    for item in items {
+                        self.match_patterns(*first, *item);
+                    }
+                }
+            }
+            Pattern::Cons(head, tail) => {
+                let ty = self.type_store.get_pattern_type(head).clone();
+                let ty = self.program.get_list_type(ty);
+                self.match_pattern_with(pattern_id, &ty);
+                self.match_patterns(pattern_id, *tail);
+            }
         }
     }
 }
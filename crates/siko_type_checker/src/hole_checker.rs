@@ -0,0 +1,188 @@
+use crate::error::TypecheckError;
+use crate::type_info_provider::TypeInfoProvider;
+use crate::type_store::TypeStore;
+use siko_ir::expr::Expr;
+use siko_ir::expr::ExprId;
+use siko_ir::expr::HoleInfo;
+use siko_ir::function::FunctionId;
+use siko_ir::pattern::Pattern;
+use siko_ir::pattern::PatternId;
+use siko_ir::program::Program;
+use siko_ir::types::ResolverContext;
+use siko_ir::types::Type;
+use siko_ir::walker::walk_expr;
+use siko_ir::walker::Visitor;
+use siko_location_info::location_id::LocationId;
+use siko_util::dependency_processor::DependencyGroup;
+
+const MAX_CANDIDATES: usize = 10;
+
+struct HoleCollector<'a> {
+    program: &'a Program,
+    holes: Vec<(ExprId, HoleInfo)>,
+}
+
+impl<'a> Visitor for HoleCollector<'a> {
+    fn get_program(&self) -> &Program {
+        &self.program
+    }
+
+    fn visit_expr(&mut self, expr_id: ExprId, expr: &Expr) {
+        if let Expr::Hole(info) = expr {
+            self.holes.push((expr_id, info.clone()));
+        }
+    }
+
+    fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
+}
+
+// Reports every hole of a dependency group with its inferred type, the types of the
+// bindings in scope and the imported functions that can produce a value of that type.
+pub struct HoleChecker<'a> {
+    program: &'a mut Program,
+    group: &'a DependencyGroup<FunctionId>,
+    type_store: &'a TypeStore,
+    type_info_provider: &'a mut TypeInfoProvider,
+    errors: &'a mut Vec<TypecheckError>,
+}
+
+impl<'a> HoleChecker<'a> {
+    pub fn new(
+        program: &'a mut Program,
+        group: &'a DependencyGroup<FunctionId>,
+        type_store: &'a TypeStore,
+        type_info_provider: &'a mut TypeInfoProvider,
+        errors: &'a mut Vec<TypecheckError>,
+    ) -> HoleChecker<'a> {
+        HoleChecker {
+            program: program,
+            group: group,
+            type_store: type_store,
+            type_info_provider: type_info_provider,
+            errors: errors,
+        }
+    }
+
+    // Returns how many args have to be applied to the candidate to get the expected type.
+    fn get_applied_arg_count(
+        &mut self,
+        expected: &Type,
+        function_id: &FunctionId,
+        location: LocationId,
+    ) -> Option<usize> {
+        let function_type_info = self
+            .type_info_provider
+            .function_type_info_store
+            .get(function_id);
+        if !function_type_info.typed {
+            return None;
+        }
+        let mut ty = self
+            .type_info_provider
+            .get_function_type(function_id, true)
+            .function_type;
+        let mut arg_count = 0;
+        loop {
+            // a result that is a type variable fits anywhere, it would not help
+            match ty {
+                Type::Var(..) | Type::Never(..) => return None,
+                _ => {}
+            }
+            let mut unifier = self.program.get_unifier();
+            if unifier.unify(&ty, expected).is_ok() {
+                let mut unifiers = Vec::new();
+                if unifier.get_constraints().iter().all(|constraint| {
+                    self.program.instance_resolver.check_instance(
+                        constraint.class_id,
+                        &constraint.ty,
+                        location,
+                        &mut unifiers,
+                    )
+                }) {
+                    return Some(arg_count);
+                }
+            }
+            match ty {
+                Type::Function(_, to) => {
+                    ty = *to;
+                    arg_count += 1;
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn get_candidates(
+        &mut self,
+        expected: &Type,
+        info: &HoleInfo,
+        location: LocationId,
+    ) -> Vec<(String, String)> {
+        if let Type::Var(..) = expected {
+            return Vec::new();
+        }
+        let mut candidates = Vec::new();
+        for (name, function_id) in &info.candidates {
+            if let Some(arg_count) = self.get_applied_arg_count(expected, function_id, location) {
+                candidates.push((arg_count, name.clone(), *function_id));
+            }
+        }
+        candidates.sort();
+        candidates
+            .into_iter()
+            .take(MAX_CANDIDATES)
+            .map(|(_, name, function_id)| {
+                let ty = &self
+                    .type_info_provider
+                    .function_type_info_store
+                    .get(&function_id)
+                    .function_type;
+                (name, ty.get_resolved_type_string(self.program))
+            })
+            .collect()
+    }
+
+    fn report_hole(&mut self, expr_id: ExprId, info: HoleInfo, location: LocationId) {
+        let expected = self.type_store.get_expr_type(&expr_id).clone();
+        let mut context = ResolverContext::new(self.program);
+        let expected_str = expected.get_resolved_type_string_with_context(&mut context);
+        let bindings = info
+            .bindings
+            .iter()
+            .map(|(name, binding)| {
+                let ty = self.type_store.get_expr_type(binding);
+                (
+                    name.clone(),
+                    ty.get_resolved_type_string_with_context(&mut context),
+                )
+            })
+            .collect();
+        let candidates = self.get_candidates(&expected, &info, location);
+        let err = TypecheckError::TypedHole(
+            info.name.clone(),
+            expected_str,
+            bindings,
+            candidates,
+            location,
+        );
+        self.errors.push(err);
+    }
+
+    pub fn check(&mut self) {
+        let mut holes = Vec::new();
+        for function_id in &self.group.items {
+            let body = self.program.functions.get(function_id).get_body();
+            let body = body.expect("body not found");
+            let mut collector = HoleCollector {
+                program: self.program,
+                holes: Vec::new(),
+            };
+            walk_expr(&body, &mut collector);
+            holes.extend(collector.holes);
+        }
+        for (expr_id, info) in holes {
+            let location = self.program.exprs.get(&expr_id).location_id;
+            self.report_hole(expr_id, info, location);
+        }
+    }
+}
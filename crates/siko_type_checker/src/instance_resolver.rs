@@ -1,4 +1,5 @@
 use crate::error::TypecheckError;
+use crate::util::select_class_args;
 use siko_ir::class::ClassId;
 use siko_ir::instance_resolver::InstanceInfo;
 use siko_ir::program::Program;
@@ -86,7 +87,19 @@ pub fn check_conflicts(errors: &mut Vec<TypecheckError>, program: &Program) {
                             let second = second_instance.get_type(instance_resolver);
                             let mut unifier =
                                 Unifier::new(instance_resolver.type_var_generator.clone());
-                            if unifier.unify(first, second).is_ok() {
+                            let conflicting = unifier.unify(first, second).is_ok()
+                                || class.functional_dependencies.iter().any(|dependency| {
+                                    // instances must not overlap in the determining args
+                                    let mut unifier =
+                                        Unifier::new(instance_resolver.type_var_generator.clone());
+                                    unifier
+                                        .unify(
+                                            &select_class_args(first, &dependency.from),
+                                            &select_class_args(second, &dependency.from),
+                                        )
+                                        .is_ok()
+                                });
+                            if conflicting {
                                 let err = TypecheckError::ConflictingInstances(
                                     class.name.clone(),
                                     first_instance.get_location(instance_resolver),
@@ -2,7 +2,9 @@ pub mod class_constraint_checker;
 pub mod common;
 pub mod error;
 pub mod expression_checker;
+pub mod hole_checker;
 pub mod instance_resolver;
+pub mod multi_param_constraint_checker;
 pub mod pattern_checker;
 pub mod type_info_provider;
 pub mod type_store;
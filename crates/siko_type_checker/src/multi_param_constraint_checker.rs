@@ -0,0 +1,340 @@
+use crate::error::TypecheckError;
+use crate::type_info_provider::TypeInfoProvider;
+use crate::type_store::TypeStore;
+use crate::util::select_class_args;
+use siko_ir::class::FunctionalDependency;
+use siko_ir::expr::Expr;
+use siko_ir::expr::ExprId;
+use siko_ir::function::FunctionId;
+use siko_ir::instance_resolver::InstanceInfo;
+use siko_ir::pattern::Pattern;
+use siko_ir::pattern::PatternId;
+use siko_ir::program::Program;
+use siko_ir::substitution::Constraint;
+use siko_ir::types::Type;
+use siko_ir::unifier::Unifier;
+use siko_ir::walker::walk_expr;
+use siko_ir::walker::Visitor;
+use siko_location_info::location_id::LocationId;
+use siko_util::dependency_processor::DependencyGroup;
+use std::collections::BTreeMap;
+
+struct CallCollector<'a> {
+    program: &'a Program,
+    calls: Vec<ExprId>,
+}
+
+impl<'a> Visitor for CallCollector<'a> {
+    fn get_program(&self) -> &Program {
+        &self.program
+    }
+
+    fn visit_expr(&mut self, expr_id: ExprId, expr: &Expr) {
+        match expr {
+            Expr::ClassFunctionCall(..) | Expr::StaticFunctionCall(..) => {
+                self.calls.push(expr_id);
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
+}
+
+#[derive(Clone)]
+struct WantedConstraint {
+    owner: FunctionId,
+    constraint: Constraint,
+    location: LocationId,
+}
+
+enum Resolution {
+    Resolved(Vec<WantedConstraint>),
+    Failed,
+    Unresolved,
+}
+
+fn get_args(ty: &Type) -> &Vec<Type> {
+    match ty {
+        Type::Tuple(items) => items,
+        _ => unreachable!(),
+    }
+}
+
+// Multi parameter class constraints are not attached to type variables, they are collected
+// from the calls of a dependency group and resolved using the constraints of the function
+// and the instances. Functional dependencies are used to improve the types of the calls.
+pub struct MultiParamConstraintChecker<'a> {
+    program: &'a mut Program,
+    group: &'a DependencyGroup<FunctionId>,
+    type_store: &'a mut TypeStore,
+    type_info_provider: &'a mut TypeInfoProvider,
+    errors: &'a mut Vec<TypecheckError>,
+    wanted: Vec<WantedConstraint>,
+}
+
+impl<'a> MultiParamConstraintChecker<'a> {
+    pub fn new(
+        program: &'a mut Program,
+        group: &'a DependencyGroup<FunctionId>,
+        type_store: &'a mut TypeStore,
+        type_info_provider: &'a mut TypeInfoProvider,
+        errors: &'a mut Vec<TypecheckError>,
+    ) -> MultiParamConstraintChecker<'a> {
+        MultiParamConstraintChecker {
+            program: program,
+            group: group,
+            type_store: type_store,
+            type_info_provider: type_info_provider,
+            errors: errors,
+            wanted: Vec::new(),
+        }
+    }
+
+    fn collect_wanted_constraints(&mut self) {
+        for function_id in &self.group.items {
+            let function = self.program.functions.get(function_id);
+            // lambdas are typechecked together with their host
+            let owner = function.get_lambda_host().unwrap_or(*function_id);
+            let body = function.get_body().expect("body not found");
+            let mut collector = CallCollector {
+                program: self.program,
+                calls: Vec::new(),
+            };
+            walk_expr(&body, &mut collector);
+            for call in collector.calls {
+                let location = self.program.exprs.get(&call).location_id;
+                let func_type_info = self.type_store.get_func_type_for_expr(&call);
+                for constraint in &func_type_info.multi_param_constraints {
+                    self.wanted.push(WantedConstraint {
+                        owner: owner,
+                        constraint: constraint.clone(),
+                        location: location,
+                    });
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, unifier: &Unifier) {
+        self.type_store.apply(unifier);
+        for id in &self.group.items {
+            let info = self.type_info_provider.function_type_info_store.get_mut(id);
+            info.apply(unifier);
+        }
+        for wanted in &mut self.wanted {
+            wanted.constraint.ty.apply(unifier);
+        }
+    }
+
+    fn get_constraint_string(&self, constraint: &Constraint) -> String {
+        let class = self.program.classes.get(&constraint.class_id);
+        let args: Vec<_> = get_args(&constraint.ty)
+            .iter()
+            .map(|arg| arg.get_resolved_type_string(self.program))
+            .collect();
+        format!("{} {}", class.name, args.join(" "))
+    }
+
+    fn matches(
+        &self,
+        ty: &Type,
+        candidate: &Type,
+        dependencies: &Vec<FunctionalDependency>,
+    ) -> bool {
+        let mut unifier = self.program.get_unifier();
+        if !ty.has_type_var() {
+            return unifier.unify(ty, candidate).is_ok();
+        }
+        // the args determined by a functional dependency can be taken from the candidate
+        dependencies.iter().any(|dependency| {
+            let mut unifier = self.program.get_unifier();
+            unifier
+                .unify(
+                    &select_class_args(ty, &dependency.from),
+                    &select_class_args(candidate, &dependency.from),
+                )
+                .is_ok()
+        })
+    }
+
+    fn resolve_with(
+        &mut self,
+        wanted: &WantedConstraint,
+        candidate: &Type,
+        context: Vec<Constraint>,
+    ) -> Resolution {
+        let mut unifier = self.program.get_unifier();
+        if unifier.unify(&wanted.constraint.ty, candidate).is_err() {
+            let ty_str1 = wanted.constraint.ty.get_resolved_type_string(self.program);
+            let ty_str2 = candidate.get_resolved_type_string(self.program);
+            let err = TypecheckError::TypeMismatch(wanted.location, ty_str1, ty_str2);
+            self.errors.push(err);
+            return Resolution::Failed;
+        }
+        let mut unifiers = Vec::new();
+        for constraint in unifier.get_constraints() {
+            if !self.program.instance_resolver.check_instance(
+                constraint.class_id,
+                &constraint.ty,
+                wanted.location,
+                &mut unifiers,
+            ) {
+                let class = self.program.classes.get(&constraint.class_id);
+                let err = TypecheckError::MissingInstance(class.name.clone(), wanted.location);
+                self.errors.push(err);
+                return Resolution::Failed;
+            }
+        }
+        let mut all_unifiers = vec![unifier];
+        all_unifiers.extend(unifiers.into_iter().map(|(unifier, _)| unifier));
+        for unifier in &all_unifiers {
+            self.apply(unifier);
+        }
+        let context = context
+            .into_iter()
+            .map(|mut constraint| {
+                for unifier in &all_unifiers {
+                    constraint.ty.apply(unifier);
+                }
+                WantedConstraint {
+                    owner: wanted.owner,
+                    constraint: constraint,
+                    location: wanted.location,
+                }
+            })
+            .collect();
+        Resolution::Resolved(context)
+    }
+
+    fn get_applicable_dependencies(&self, constraint: &Constraint) -> Vec<FunctionalDependency> {
+        let class = self.program.classes.get(&constraint.class_id);
+        class
+            .functional_dependencies
+            .iter()
+            .filter(|dependency| {
+                dependency
+                    .from
+                    .iter()
+                    .all(|index| !get_args(&constraint.ty)[*index].has_type_var())
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn resolve(&mut self, wanted: &WantedConstraint) -> Resolution {
+        let class_id = wanted.constraint.class_id;
+        let ty = &wanted.constraint.ty;
+        let dependencies = self.get_applicable_dependencies(&wanted.constraint);
+        if ty.has_type_var() && dependencies.is_empty() {
+            return Resolution::Unresolved;
+        }
+        let givens = self
+            .type_info_provider
+            .function_type_info_store
+            .get(&wanted.owner)
+            .multi_param_constraints
+            .clone();
+        for given in givens {
+            if given.class_id == class_id && self.matches(ty, &given.ty, &dependencies) {
+                return self.resolve_with(wanted, &given.ty, Vec::new());
+            }
+        }
+        let instances = match self.program.instance_resolver.instance_map.get(&class_id) {
+            Some(class_instances) => class_instances.values().flatten().cloned().collect(),
+            None => Vec::new(),
+        };
+        for instance in instances {
+            if let InstanceInfo::UserDefined(instance_ty, instance_id, _) = instance {
+                let mut arg_map = BTreeMap::new();
+                let instance_ty = instance_ty.duplicate(
+                    &mut arg_map,
+                    &mut self.type_info_provider.type_var_generator,
+                );
+                if self.matches(ty, &instance_ty, &dependencies) {
+                    let context = self
+                        .program
+                        .instance_resolver
+                        .get_multi_param_constraints(&instance_id)
+                        .clone()
+                        .into_iter()
+                        .map(|constraint| Constraint {
+                            class_id: constraint.class_id,
+                            ty: constraint.ty.duplicate(
+                                &mut arg_map,
+                                &mut self.type_info_provider.type_var_generator,
+                            ),
+                        })
+                        .collect();
+                    return self.resolve_with(wanted, &instance_ty, context);
+                }
+            }
+        }
+        Resolution::Unresolved
+    }
+
+    fn report_unresolved(&mut self) {
+        let wanted = std::mem::replace(&mut self.wanted, Vec::new());
+        for wanted in wanted {
+            // no instance can be found if the determining args are already known
+            if !wanted.constraint.ty.has_type_var()
+                || !self
+                    .get_applicable_dependencies(&wanted.constraint)
+                    .is_empty()
+            {
+                let constraint = self.get_constraint_string(&wanted.constraint);
+                let err = TypecheckError::MissingInstance(constraint, wanted.location);
+                self.errors.push(err);
+                continue;
+            }
+            // untyped functions get the unresolved constraints, their callers have to satisfy them
+            let mut constraint_args = Vec::new();
+            wanted
+                .constraint
+                .ty
+                .collect_type_args(&mut constraint_args, self.program);
+            let owner_info = self
+                .type_info_provider
+                .function_type_info_store
+                .get_mut(&wanted.owner);
+            let mut owner_args = Vec::new();
+            owner_info
+                .function_type
+                .collect_type_args(&mut owner_args, self.program);
+            if !owner_info.typed && constraint_args.iter().all(|arg| owner_args.contains(arg)) {
+                owner_info.multi_param_constraints.push(wanted.constraint);
+            } else {
+                let err = TypecheckError::TypeAnnotationNeeded(wanted.location);
+                self.errors.push(err);
+            }
+        }
+    }
+
+    pub fn check(&mut self) {
+        self.collect_wanted_constraints();
+        loop {
+            let mut progress = false;
+            let mut index = 0;
+            while index < self.wanted.len() {
+                let wanted = self.wanted[index].clone();
+                match self.resolve(&wanted) {
+                    Resolution::Resolved(context) => {
+                        self.wanted.remove(index);
+                        self.wanted.extend(context);
+                        progress = true;
+                    }
+                    Resolution::Failed => {
+                        self.wanted.remove(index);
+                    }
+                    Resolution::Unresolved => {
+                        index += 1;
+                    }
+                }
+            }
+            if !progress {
+                break;
+            }
+        }
+        self.report_unresolved();
+    }
+}
@@ -1,234 +1,641 @@
 use crate::error::TypecheckError;
+use siko_constants::BOOL_MODULE_NAME;
+use siko_constants::BOOL_TYPE_NAME;
+use siko_constants::CHAR_TYPE_NAME;
+use siko_constants::INT_TYPE_NAME;
+use siko_constants::STRING_TYPE_NAME;
+use siko_constants::TRUE_NAME;
 use siko_ir::data::TypeDefId;
 use siko_ir::expr::Expr;
 use siko_ir::expr::ExprId;
+use siko_ir::function::FunctionInfo;
 use siko_ir::pattern::Pattern;
 use siko_ir::pattern::PatternId;
+use siko_ir::pattern::RangeKind;
 use siko_ir::program::Program;
 use siko_ir::walker::Visitor;
 use siko_location_info::location_id::LocationId;
-use siko_util::Counter;
-use std::collections::BTreeMap;
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Clone, Debug)]
-enum ChoiceKind {
-    Binding,
-    Variant(TypeDefId, usize),
-    String(String),
-    Int(i64),
-    Char(char),
+// Int and Char values are handled as inclusive intervals, a literal is an interval of a single value
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Interval {
+    start: i128,
+    end: i128,
 }
 
-#[derive(Clone)]
-struct Choice {
-    first_location: Option<LocationId>,
-    sub_choices: BTreeMap<ChoiceKind, usize>,
+impl Interval {
+    fn new(start: i128, end: i128) -> Interval {
+        Interval {
+            start: start,
+            end: end,
+        }
+    }
+
+    fn from_range(start: i128, end: i128, kind: &RangeKind) -> Interval {
+        match kind {
+            RangeKind::Exclusive => Interval::new(start, end - 1),
+            RangeKind::Inclusive => Interval::new(start, end),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    // cuts the interval at the boundaries of the given intervals, every piece is either
+    // fully inside or fully outside of each of them
+    fn split(&self, intervals: &[Interval]) -> Vec<Interval> {
+        let mut boundaries: Vec<i128> = Vec::new();
+        for interval in intervals {
+            if interval.is_empty() {
+                continue;
+            }
+            boundaries.push(interval.start);
+            boundaries.push(interval.end + 1);
+        }
+        boundaries.retain(|b| self.start < *b && *b <= self.end);
+        boundaries.sort();
+        boundaries.dedup();
+        let mut pieces = Vec::new();
+        let mut start = self.start;
+        for boundary in boundaries {
+            pieces.push(Interval::new(start, boundary - 1));
+            start = boundary;
+        }
+        if !self.is_empty() {
+            pieces.push(Interval::new(start, self.end));
+        }
+        pieces
+    }
+
+    fn format(&self, format_value: fn(i128) -> String) -> String {
+        if self.start == self.end {
+            format_value(self.start)
+        } else {
+            format!("{}..={}", format_value(self.start), format_value(self.end))
+        }
+    }
 }
 
-impl Choice {
-    fn new() -> Choice {
-        Choice {
-            first_location: None,
-            sub_choices: BTreeMap::new(),
+// sorts the intervals and joins the overlapping and adjacent ones
+fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort_by_key(|interval| interval.start);
+    let mut merged: Vec<Interval> = Vec::new();
+    for interval in intervals {
+        if let Some(last) = merged.last_mut() {
+            if interval.start <= last.end + 1 {
+                last.end = std::cmp::max(last.end, interval.end);
+                continue;
+            }
         }
+        merged.push(interval);
+    }
+    merged
+}
+
+fn format_int(value: i128) -> String {
+    format!("{}", value)
+}
+
+fn format_char(value: i128) -> String {
+    match std::char::from_u32(value as u32) {
+        Some(c) => format!("{:?}", c),
+        None => format!("{}", value),
     }
 }
 
-struct CaseInfo {
-    choices: BTreeMap<usize, Choice>,
-    next_id: Counter,
+fn get_int_domain() -> Vec<Interval> {
+    vec![Interval::new(i64::MIN as i128, i64::MAX as i128)]
+}
+
+fn get_char_domain() -> Vec<Interval> {
+    // surrogates are not valid chars
+    vec![
+        Interval::new(0, 0xD7FF),
+        Interval::new(0xE000, char::MAX as i128),
+    ]
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Constructor {
+    Variant(TypeDefId, usize),
+    Tuple(usize),
+    Record(TypeDefId),
+    Int(Interval),
+    Char(Interval),
+    String(String),
+    // lists are matched as if they were defined as data List a = Nil | Cons a (List a)
+    Nil,
+    Cons,
 }
 
-impl CaseInfo {
-    fn new() -> CaseInfo {
-        CaseInfo {
-            choices: BTreeMap::new(),
-            next_id: Counter::new(),
+impl Constructor {
+    fn get_arity(&self, program: &Program) -> usize {
+        match self {
+            Constructor::Variant(id, index) => program.typedefs.get(id).get_adt().variants[*index]
+                .items
+                .len(),
+            Constructor::Tuple(len) => *len,
+            Constructor::Record(id) => program.typedefs.get(id).get_record().fields.len(),
+            Constructor::Int(_) | Constructor::Char(_) | Constructor::String(_) => 0,
+            Constructor::Nil => 0,
+            Constructor::Cons => 2,
         }
     }
 
-    fn add_first(&mut self) -> usize {
-        let id = self.next_id.next();
-        let choice = Choice::new();
-        self.choices.insert(id, choice);
-        id
+    // every constructor is either fully covered by another one or not at all,
+    // the intervals are split before they are compared
+    fn covers(&self, other: &Constructor) -> bool {
+        match (self, other) {
+            (Constructor::Int(interval), Constructor::Int(other)) => interval.contains(other),
+            (Constructor::Char(interval), Constructor::Char(other)) => interval.contains(other),
+            _ => self == other,
+        }
     }
+}
+
+#[derive(Clone, Debug)]
+enum SimplePattern {
+    Wildcard,
+    Constructor(Constructor, Vec<SimplePattern>),
+    // any value of a literal type except the listed ones, only used for the missing patterns
+    Except(Vec<Constructor>),
+}
 
-    fn get_choice(&self, id: usize) -> &Choice {
-        self.choices.get(&id).expect("Choice not found")
+impl SimplePattern {
+    fn is_wildcard(&self) -> bool {
+        match self {
+            SimplePattern::Wildcard => true,
+            _ => false,
+        }
     }
 
-    fn check_choices(&self, first: usize, program: &Program, errors: &mut Vec<TypecheckError>) {
-        let choice = self.get_choice(first);
-        let mut bind_found = false;
-        let mut variants = Vec::new();
-        let mut constant_pattern_found = false;
-        for (kind, _) in &choice.sub_choices {
-            match kind {
-                ChoiceKind::Binding => {
-                    bind_found = true;
+    fn format(&self, program: &Program, need_parens: bool) -> String {
+        match self {
+            SimplePattern::Wildcard => format!("_"),
+            SimplePattern::Constructor(constructor, args) => match constructor {
+                Constructor::Variant(id, index) => {
+                    let adt = program.typedefs.get(id).get_adt();
+                    let name = &adt.variants[*index].name;
+                    if args.is_empty() {
+                        name.clone()
+                    } else {
+                        let args: Vec<_> =
+                            args.iter().map(|arg| arg.format(program, true)).collect();
+                        let s = format!("{} {}", name, args.join(" "));
+                        if need_parens {
+                            format!("({})", s)
+                        } else {
+                            s
+                        }
+                    }
                 }
-                ChoiceKind::Variant(id, index) => {
-                    variants.push((id, index));
+                Constructor::Tuple(_) => {
+                    let args: Vec<_> = args.iter().map(|arg| arg.format(program, false)).collect();
+                    format!("({})", args.join(", "))
                 }
-                ChoiceKind::String(_) => {
-                    constant_pattern_found = true;
+                Constructor::Record(id) => {
+                    let record = program.typedefs.get(id).get_record();
+                    let fields: Vec<_> = record
+                        .fields
+                        .iter()
+                        .zip(args.iter())
+                        .map(|(field, arg)| {
+                            format!("{} = {}", field.name, arg.format(program, false))
+                        })
+                        .collect();
+                    format!("{} {{ {} }}", record.name, fields.join(", "))
                 }
-                ChoiceKind::Int(_) => {
-                    constant_pattern_found = true;
+                Constructor::Int(interval) => interval.format(format_int),
+                Constructor::Char(interval) => interval.format(format_char),
+                Constructor::String(s) => format!("{:?}", s),
+                Constructor::Nil => format!("[]"),
+                Constructor::Cons => {
+                    // a fixed length list is shown as [a, b], otherwise as a :: b :: tail
+                    let mut heads = vec![&args[0]];
+                    let mut tail = &args[1];
+                    while let SimplePattern::Constructor(Constructor::Cons, args) = tail {
+                        heads.push(&args[0]);
+                        tail = &args[1];
+                    }
+                    match tail {
+                        SimplePattern::Constructor(Constructor::Nil, _) => {
+                            let heads: Vec<_> = heads
+                                .iter()
+                                .map(|head| head.format(program, false))
+                                .collect();
+                            format!("[{}]", heads.join(", "))
+                        }
+                        _ => {
+                            let mut items: Vec<_> = heads
+                                .iter()
+                                .map(|head| head.format(program, true))
+                                .collect();
+                            items.push(tail.format(program, true));
+                            let s = items.join(" :: ");
+                            if need_parens {
+                                format!("({})", s)
+                            } else {
+                                s
+                            }
+                        }
+                    }
                 }
-                ChoiceKind::Char(_) => {
-                    constant_pattern_found = true;
+            },
+            SimplePattern::Except(constructors) => {
+                let (type_name, values) = match &constructors[0] {
+                    Constructor::Int(_) => {
+                        let intervals = MatchChecker::get_intervals(constructors);
+                        let values: Vec<_> = merge_intervals(intervals)
+                            .iter()
+                            .map(|interval| interval.format(format_int))
+                            .collect();
+                        (INT_TYPE_NAME, values)
+                    }
+                    Constructor::Char(_) => {
+                        let intervals = MatchChecker::get_intervals(constructors);
+                        let values: Vec<_> = merge_intervals(intervals)
+                            .iter()
+                            .map(|interval| interval.format(format_char))
+                            .collect();
+                        (CHAR_TYPE_NAME, values)
+                    }
+                    Constructor::String(_) => {
+                        let values: Vec<_> = constructors
+                            .iter()
+                            .map(|constructor| match constructor {
+                                Constructor::String(s) => format!("{:?}", s),
+                                _ => unreachable!(),
+                            })
+                            .collect();
+                        (STRING_TYPE_NAME, values)
+                    }
+                    _ => unreachable!(),
+                };
+                let s = format!("_ :: {} other than {}", type_name, values.join(", "));
+                if need_parens {
+                    format!("({})", s)
+                } else {
+                    s
                 }
             }
         }
-        if !bind_found {
-            if !variants.is_empty() {
-                let typedef_id = variants[0].0;
-                let adt = program.typedefs.get(&typedef_id).get_adt();
-                if adt.variants.len() != variants.len() {
-                    let err = TypecheckError::NonExhaustivePattern(
-                        choice.first_location.expect("First location not found"),
-                    );
-                    errors.push(err);
+    }
+}
+
+type Row = Vec<SimplePattern>;
+
+fn is_trivially_true(expr_id: &ExprId, program: &Program, depth: usize) -> bool {
+    match &program.exprs.get(expr_id).item {
+        Expr::StaticFunctionCall(function_id, args) if args.is_empty() => {
+            let function = program.functions.get(function_id);
+            match &function.info {
+                FunctionInfo::VariantConstructor(info) => {
+                    let adt = program.typedefs.get(&info.type_id).get_adt();
+                    adt.module == BOOL_MODULE_NAME
+                        && adt.name == BOOL_TYPE_NAME
+                        && adt.variants[info.index].name == TRUE_NAME
                 }
+                // constants like otherwise
+                FunctionInfo::NamedFunction(info) if function.arg_count == 0 && depth > 0 => {
+                    match info.body {
+                        Some(body) => is_trivially_true(&body, program, depth - 1),
+                        None => false,
+                    }
+                }
+                _ => false,
             }
-            if constant_pattern_found {
-                let err = TypecheckError::NonExhaustivePattern(
-                    choice.first_location.expect("First location not found"),
-                );
-                errors.push(err);
-            }
+        }
+        Expr::Do(items) if items.len() == 1 => is_trivially_true(&items[0], program, depth),
+        _ => false,
+    }
+}
+
+fn simplify_pattern(
+    pattern_id: &PatternId,
+    program: &Program,
+    guards: &mut Vec<ExprId>,
+) -> SimplePattern {
+    let pattern = &program.patterns.get(pattern_id).item;
+    let simplify_items = |items: &Vec<PatternId>, guards: &mut Vec<ExprId>| -> Vec<_> {
+        items
+            .iter()
+            .map(|item| simplify_pattern(item, program, guards))
+            .collect()
+    };
+    match pattern {
+        Pattern::Binding(_) | Pattern::Wildcard => SimplePattern::Wildcard,
+        Pattern::Tuple(items) => {
+            let items = simplify_items(items, guards);
+            SimplePattern::Constructor(Constructor::Tuple(items.len()), items)
+        }
+        Pattern::Record(id, items) => {
+            let items = simplify_items(items, guards);
+            SimplePattern::Constructor(Constructor::Record(*id), items)
+        }
+        Pattern::Variant(id, index, items) => {
+            let items = simplify_items(items, guards);
+            SimplePattern::Constructor(Constructor::Variant(*id, *index), items)
+        }
+        Pattern::Guarded(item, guard) => {
+            guards.push(*guard);
+            simplify_pattern(item, program, guards)
+        }
+        Pattern::IntegerLiteral(value) => {
+            let value = *value as i128;
+            SimplePattern::Constructor(Constructor::Int(Interval::new(value, value)), Vec::new())
+        }
+        Pattern::IntegerRange(start, end, kind) => SimplePattern::Constructor(
+            Constructor::Int(Interval::from_range(*start as i128, *end as i128, kind)),
+            Vec::new(),
+        ),
+        Pattern::CharLiteral(c) => {
+            let value = *c as i128;
+            SimplePattern::Constructor(Constructor::Char(Interval::new(value, value)), Vec::new())
+        }
+        Pattern::CharRange(start, end, kind) => SimplePattern::Constructor(
+            Constructor::Char(Interval::from_range(*start as i128, *end as i128, kind)),
+            Vec::new(),
+        ),
+        Pattern::StringLiteral(s) => {
+            SimplePattern::Constructor(Constructor::String(s.clone()), Vec::new())
+        }
+        Pattern::Typed(item, _) => simplify_pattern(item, program, guards),
+        Pattern::List(items) => {
+            let items = simplify_items(items, guards);
+            items.into_iter().rev().fold(
+                SimplePattern::Constructor(Constructor::Nil, Vec::new()),
+                |tail, head| SimplePattern::Constructor(Constructor::Cons, vec![head, tail]),
+            )
+        }
+        Pattern::Cons(head, tail) => {
+            let items = simplify_items(&vec![*head, *tail], guards);
+            SimplePattern::Constructor(Constructor::Cons, items)
+        }
+    }
+}
+
+// The constructors that a wildcard in the first column has to be split into
+enum Signature {
+    // every constructor of the type is present in the column
+    Complete(Vec<Constructor>),
+    // some constructors are missing, the first ones are present in the column,
+    // the patterns describe the missing ones
+    Incomplete(Vec<Constructor>, Vec<SimplePattern>),
+}
+
+// Exhaustiveness and reachability of case arms, based on the usefulness of pattern rows
+struct MatchChecker<'a> {
+    program: &'a Program,
+}
 
-            for (_, choice) in &choice.sub_choices {
-                if errors.is_empty() {
-                    self.check_choices(*choice, program, errors);
+impl<'a> MatchChecker<'a> {
+    fn get_head_constructors(&self, rows: &[Row]) -> Vec<Constructor> {
+        let mut constructors = Vec::new();
+        for row in rows {
+            if let SimplePattern::Constructor(constructor, _) = &row[0] {
+                if !constructors.contains(constructor) {
+                    constructors.push(constructor.clone());
                 }
             }
         }
+        constructors
     }
 
-    fn add_choice(
-        &mut self,
-        parent: usize,
-        kind: ChoiceKind,
-        choice_added: &mut bool,
-        location_id: LocationId,
-    ) -> usize {
-        let parent = self
-            .choices
-            .get_mut(&parent)
-            .expect("Parent choice not found");
-        let mut newly_added = false;
-        let new_id = self.next_id.next();
-        if parent.first_location.is_none() {
-            parent.first_location = Some(location_id);
-        }
-        if let Some(bind_id) = parent.sub_choices.get(&ChoiceKind::Binding) {
-            return *bind_id;
-        }
-        let id = *parent.sub_choices.entry(kind).or_insert_with(|| {
-            newly_added = true;
-            new_id
-        });
-        if newly_added {
-            let choice = Choice::new();
-            self.choices.insert(id, choice);
-            *choice_added = true;
-        }
-        id
+    fn get_intervals(constructors: &[Constructor]) -> Vec<Interval> {
+        constructors
+            .iter()
+            .filter_map(|constructor| match constructor {
+                Constructor::Int(interval) | Constructor::Char(interval) => Some(*interval),
+                _ => None,
+            })
+            .collect()
     }
 
-    fn check_pattern(
-        &mut self,
-        pattern_id: &PatternId,
-        program: &Program,
-        parent: usize,
-        choice_added: &mut bool,
-    ) -> usize {
-        let item_info = program.patterns.get(pattern_id);
-        match &item_info.item {
-            Pattern::Binding(_) => {
-                return self.add_choice(
-                    parent,
-                    ChoiceKind::Binding,
-                    choice_added,
-                    item_info.location_id,
-                );
+    fn get_interval_signature(
+        domain: Vec<Interval>,
+        constructors: &[Constructor],
+        wrap: fn(Interval) -> Constructor,
+    ) -> Signature {
+        let intervals = Self::get_intervals(constructors);
+        let pieces: Vec<_> = domain.iter().flat_map(|d| d.split(&intervals)).collect();
+        let (covered, uncovered): (Vec<_>, Vec<_>) = pieces
+            .into_iter()
+            .partition(|piece| intervals.iter().any(|interval| interval.contains(piece)));
+        let covered = covered.into_iter().map(wrap).collect();
+        if uncovered.is_empty() {
+            Signature::Complete(covered)
+        } else {
+            Signature::Incomplete(covered, vec![SimplePattern::Except(constructors.to_vec())])
+        }
+    }
+
+    fn get_finite_signature(
+        &self,
+        all: Vec<Constructor>,
+        constructors: &[Constructor],
+    ) -> Signature {
+        let (present, missing): (Vec<_>, Vec<_>) =
+            all.into_iter().partition(|c| constructors.contains(c));
+        if missing.is_empty() {
+            Signature::Complete(present)
+        } else {
+            let missing = missing
+                .into_iter()
+                .map(|c| {
+                    let arity = c.get_arity(self.program);
+                    SimplePattern::Constructor(c, vec![SimplePattern::Wildcard; arity])
+                })
+                .collect();
+            Signature::Incomplete(present, missing)
+        }
+    }
+
+    fn get_signature(&self, constructors: &[Constructor]) -> Signature {
+        match &constructors[0] {
+            Constructor::Variant(id, _) => {
+                let adt = self.program.typedefs.get(id).get_adt();
+                let all = (0..adt.variants.len())
+                    .map(|index| Constructor::Variant(*id, index))
+                    .collect();
+                self.get_finite_signature(all, constructors)
             }
-            Pattern::Wildcard => {
-                return self.add_choice(
-                    parent,
-                    ChoiceKind::Binding,
-                    choice_added,
-                    item_info.location_id,
-                );
+            Constructor::Tuple(_) | Constructor::Record(_) => {
+                Signature::Complete(vec![constructors[0].clone()])
             }
-            Pattern::Variant(id, index, items) => {
-                let mut id = self.add_choice(
-                    parent,
-                    ChoiceKind::Variant(*id, *index),
-                    choice_added,
-                    item_info.location_id,
-                );
-                for item in items {
-                    id = self.check_pattern(item, program, id, choice_added);
-                }
-                id
+            Constructor::Int(_) => {
+                Self::get_interval_signature(get_int_domain(), constructors, Constructor::Int)
             }
-            Pattern::StringLiteral(s) => {
-                return self.add_choice(
-                    parent,
-                    ChoiceKind::String(s.clone()),
-                    choice_added,
-                    item_info.location_id,
-                );
+            Constructor::Char(_) => {
+                Self::get_interval_signature(get_char_domain(), constructors, Constructor::Char)
             }
-            Pattern::IntegerLiteral(i) => {
-                return self.add_choice(
-                    parent,
-                    ChoiceKind::Int(i.clone()),
-                    choice_added,
-                    item_info.location_id,
-                );
+            Constructor::String(_) => Signature::Incomplete(
+                constructors.to_vec(),
+                vec![SimplePattern::Except(constructors.to_vec())],
+            ),
+            Constructor::Nil | Constructor::Cons => {
+                let all = vec![Constructor::Nil, Constructor::Cons];
+                self.get_finite_signature(all, constructors)
             }
-            Pattern::CharLiteral(i) => {
-                return self.add_choice(
-                    parent,
-                    ChoiceKind::Char(i.clone()),
-                    choice_added,
-                    item_info.location_id,
-                );
+        }
+    }
+
+    fn specialize_row(&self, row: &Row, constructor: &Constructor) -> Option<Row> {
+        let mut new_row = match &row[0] {
+            SimplePattern::Wildcard => {
+                vec![SimplePattern::Wildcard; constructor.get_arity(self.program)]
             }
-            Pattern::Tuple(items) => {
-                let mut id = parent;
-                for item in items {
-                    id = self.check_pattern(item, program, id, choice_added);
+            SimplePattern::Constructor(head, args) => {
+                if head.covers(constructor) {
+                    args.clone()
+                } else {
+                    return None;
                 }
-                id
             }
-            Pattern::Record(_, items) => {
-                let mut id = parent;
-                for item in items {
-                    id = self.check_pattern(item, program, id, choice_added);
+            SimplePattern::Except(_) => unreachable!(),
+        };
+        new_row.extend(row[1..].iter().cloned());
+        Some(new_row)
+    }
+
+    fn specialize(&self, rows: &[Row], constructor: &Constructor) -> Vec<Row> {
+        rows.iter()
+            .filter_map(|row| self.specialize_row(row, constructor))
+            .collect()
+    }
+
+    fn default_rows(&self, rows: &[Row]) -> Vec<Row> {
+        rows.iter()
+            .filter(|row| row[0].is_wildcard())
+            .map(|row| row[1..].to_vec())
+            .collect()
+    }
+
+    fn is_useful(&self, rows: &[Row], row: &Row) -> bool {
+        if row.is_empty() {
+            return rows.is_empty();
+        }
+        let head_constructors = self.get_head_constructors(rows);
+        match &row[0] {
+            SimplePattern::Constructor(constructor, _) => {
+                let pieces = match constructor {
+                    Constructor::Int(interval) => interval
+                        .split(&Self::get_intervals(&head_constructors))
+                        .into_iter()
+                        .map(Constructor::Int)
+                        .collect(),
+                    Constructor::Char(interval) => interval
+                        .split(&Self::get_intervals(&head_constructors))
+                        .into_iter()
+                        .map(Constructor::Char)
+                        .collect(),
+                    _ => vec![constructor.clone()],
+                };
+                pieces.iter().any(|piece| {
+                    let row = self
+                        .specialize_row(row, piece)
+                        .expect("Specialization failed");
+                    self.is_useful(&self.specialize(rows, piece), &row)
+                })
+            }
+            SimplePattern::Wildcard => {
+                if !head_constructors.is_empty() {
+                    if let Signature::Complete(constructors) =
+                        self.get_signature(&head_constructors)
+                    {
+                        return constructors.iter().any(|constructor| {
+                            let row = self
+                                .specialize_row(row, constructor)
+                                .expect("Specialization failed");
+                            self.is_useful(&self.specialize(rows, constructor), &row)
+                        });
+                    }
                 }
-                id
+                self.is_useful(&self.default_rows(rows), &row[1..].to_vec())
+            }
+            SimplePattern::Except(_) => unreachable!(),
+        }
+    }
+
+    // returns the index of the earlier row that makes the given row unreachable, preferring
+    // a single row that covers it, otherwise the last one of the rows that cover it together,
+    // a row that matches no value at all is not covered by any of them
+    fn get_covering_row(&self, rows: &[Row], row: &Row) -> Option<usize> {
+        for (index, earlier) in rows.iter().enumerate() {
+            if !self.is_useful(&[earlier.clone()], row) {
+                return Some(index);
+            }
+        }
+        (0..rows.len()).find(|index| !self.is_useful(&rows[..index + 1], row))
+    }
+
+    fn get_missing_rows_for_constructors(
+        &self,
+        rows: &[Row],
+        len: usize,
+        constructors: Vec<Constructor>,
+        missing_rows: &mut Vec<Row>,
+    ) {
+        for constructor in constructors {
+            let arity = constructor.get_arity(self.program);
+            let specialized = self.specialize(rows, &constructor);
+            for mut row in self.get_missing_rows(&specialized, arity + len - 1) {
+                let rest = row.split_off(arity);
+                let mut new_row = vec![SimplePattern::Constructor(constructor.clone(), row)];
+                new_row.extend(rest);
+                missing_rows.push(new_row);
             }
-            Pattern::Typed(item, _) => {
-                return self.check_pattern(item, program, parent, choice_added);
+        }
+    }
+
+    // returns the value vectors of the given length that are not matched by any of the rows
+    fn get_missing_rows(&self, rows: &[Row], len: usize) -> Vec<Row> {
+        if len == 0 {
+            if rows.is_empty() {
+                return vec![Vec::new()];
+            } else {
+                return Vec::new();
             }
-            Pattern::CharRange(..) => {
-                // FIXME: add checks for char ranges
-                return 0;
+        }
+        let head_constructors = self.get_head_constructors(rows);
+        let signature = if head_constructors.is_empty() {
+            Signature::Incomplete(Vec::new(), Vec::new())
+        } else {
+            self.get_signature(&head_constructors)
+        };
+        let mut missing_rows = Vec::new();
+        match signature {
+            Signature::Complete(constructors) => {
+                self.get_missing_rows_for_constructors(rows, len, constructors, &mut missing_rows);
             }
-            Pattern::Guarded(..) => {
-                // FIXME:
-                // We cannot check at compile time whether the guard will return True or not,
-                // so guarded patterns are ignored for now, we assume that they fail
-                // This can be improved by checking the guard expression
-                return 0;
+            Signature::Incomplete(present_constructors, missing_patterns) => {
+                // the values starting with a present constructor can still be missing
+                self.get_missing_rows_for_constructors(
+                    rows,
+                    len,
+                    present_constructors,
+                    &mut missing_rows,
+                );
+                let default_rows = self.default_rows(rows);
+                let heads = if missing_patterns.is_empty() {
+                    vec![SimplePattern::Wildcard]
+                } else {
+                    missing_patterns
+                };
+                for row in self.get_missing_rows(&default_rows, len - 1) {
+                    for head in &heads {
+                        let mut new_row = vec![head.clone()];
+                        new_row.extend(row.iter().cloned());
+                        missing_rows.push(new_row);
+                    }
+                }
             }
         }
+        missing_rows
     }
 }
 
@@ -253,30 +660,67 @@ impl<'a> Visitor for PatternChecker<'a> {
 
     fn visit_expr(&mut self, _: ExprId, expr: &Expr) {
         if let Expr::CaseOf(_, cases, _) = expr {
-            let mut case_info = CaseInfo::new();
-            let first = case_info.add_first();
+            let checker = MatchChecker {
+                program: self.program,
+            };
+            let mut rows: Vec<Row> = Vec::new();
+            let mut row_locations = Vec::new();
+            let mut first_location: Option<LocationId> = None;
             for case in cases {
-                let mut choice_added = false;
-
-                let id = case_info.check_pattern(
-                    &case.pattern_id,
-                    &self.program,
-                    first,
-                    &mut choice_added,
-                );
-                if id == 0 {
-                    // guarded pattern, ignore it
-                } else {
-                    let location = self.program.patterns.get(&case.pattern_id).location_id;
-                    if !choice_added {
-                        let err = TypecheckError::UnreachablePattern(location);
-                        self.errors.push(err);
-                    }
+                let location = self.program.patterns.get(&case.pattern_id).location_id;
+                if first_location.is_none() {
+                    first_location = Some(location);
+                }
+                let mut guards = Vec::new();
+                let pattern = simplify_pattern(&case.pattern_id, &self.program, &mut guards);
+                let row = vec![pattern];
+                // a row that matches nothing on its own has an empty range, which is reported
+                // by itself
+                if checker.is_useful(&[], &row) && !checker.is_useful(&rows, &row) {
+                    let covering = checker
+                        .get_covering_row(&rows, &row)
+                        .map(|covering| row_locations[covering]);
+                    let err = TypecheckError::UnreachablePattern(location, covering);
+                    self.errors.push(err);
                 }
+                // we cannot tell whether a guard holds, so only trivially true guards count
+                if guards
+                    .iter()
+                    .all(|guard| is_trivially_true(guard, &self.program, 1))
+                {
+                    rows.push(row);
+                    row_locations.push(location);
+                }
+            }
+            let missing_patterns: Vec<_> = checker
+                .get_missing_rows(&rows, 1)
+                .iter()
+                .map(|row| row[0].format(&self.program, false))
+                .collect();
+            if !missing_patterns.is_empty() {
+                let err = TypecheckError::NonExhaustivePattern(
+                    missing_patterns,
+                    first_location.expect("First location not found"),
+                );
+                self.errors.push(err);
             }
-            case_info.check_choices(first, &self.program, self.errors);
         }
     }
 
-    fn visit_pattern(&mut self, _: PatternId, _: &Pattern) {}
+    fn visit_pattern(&mut self, pattern_id: PatternId, pattern: &Pattern) {
+        let interval = match pattern {
+            Pattern::IntegerRange(start, end, kind) => {
+                Interval::from_range(*start as i128, *end as i128, kind)
+            }
+            Pattern::CharRange(start, end, kind) => {
+                Interval::from_range(*start as i128, *end as i128, kind)
+            }
+            _ => return,
+        };
+        if interval.is_empty() {
+            let location = self.program.patterns.get(&pattern_id).location_id;
+            let err = TypecheckError::EmptyRangePattern(location);
+            self.errors.push(err);
+        }
+    }
 }
@@ -6,6 +6,7 @@ use siko_ir::data::TypeDefId;
 use siko_ir::data_type_info::AdtTypeInfo;
 use siko_ir::data_type_info::RecordTypeInfo;
 use siko_ir::function::FunctionId;
+use siko_ir::substitution::Constraint;
 use siko_ir::type_var_generator::TypeVarGenerator;
 use siko_ir::types::Type;
 use std::collections::BTreeMap;
@@ -56,6 +57,14 @@ impl TypeInfoProvider {
     }
 
     pub fn get_class_member_type(&mut self, class_member_id: &ClassMemberId) -> Type {
+        self.get_class_member_type_with_constraints(class_member_id)
+            .0
+    }
+
+    pub fn get_class_member_type_with_constraints(
+        &mut self,
+        class_member_id: &ClassMemberId,
+    ) -> (Type, Vec<Constraint>) {
         let class_member_type_info = self
             .class_member_type_info_map
             .get(class_member_id)
@@ -65,6 +74,18 @@ impl TypeInfoProvider {
             .ty
             .duplicate(&mut arg_map, &mut self.type_var_generator)
             .remove_fixed_types();
-        function_type
+        let constraints = class_member_type_info
+            .multi_param_constraint
+            .clone()
+            .into_iter()
+            .map(|constraint| Constraint {
+                class_id: constraint.class_id,
+                ty: constraint
+                    .ty
+                    .duplicate(&mut arg_map, &mut self.type_var_generator)
+                    .remove_fixed_types(),
+            })
+            .collect();
+        (function_type, constraints)
     }
 }
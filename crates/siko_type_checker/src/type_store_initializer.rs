@@ -74,9 +74,9 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                     .initialize_expr(expr_id, self.program.get_char_type());
             }
             Expr::ClassFunctionCall(class_member_id, args) => {
-                let class_member_type = self
+                let (class_member_type, multi_param_constraints) = self
                     .type_info_provider
-                    .get_class_member_type(class_member_id);
+                    .get_class_member_type_with_constraints(class_member_id);
                 let mut func_type_info = create_general_function_type_info(
                     args.len(),
                     &mut self.type_info_provider.type_var_generator,
@@ -97,6 +97,7 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                         self.errors,
                     )
                 }
+                func_type_info.multi_param_constraints = multi_param_constraints;
                 func_type_info.apply(&unifier);
                 self.type_store.initialize_expr_with_func(
                     expr_id,
@@ -190,6 +191,8 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                         self.errors,
                     )
                 }
+                func_type_info.multi_param_constraints =
+                    static_func_type_info.multi_param_constraints.clone();
                 func_type_info.apply(&unifier);
                 self.type_store.initialize_expr_with_func(
                     expr_id,
@@ -258,6 +261,13 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                     Type::Never(self.type_info_provider.type_var_generator.get_new_index()),
                 );
             }
+            Expr::Hole(..) => {
+                let ty = self
+                    .type_info_provider
+                    .type_var_generator
+                    .get_new_type_var();
+                self.type_store.initialize_expr(expr_id, ty);
+            }
         }
     }
 
@@ -290,6 +300,18 @@ impl<'a> Visitor for TypeStoreInitializer<'a> {
                 self.type_store
                     .initialize_pattern(pattern_id, self.program.get_char_type());
             }
+            Pattern::IntegerRange(_, _, _) => {
+                self.type_store
+                    .initialize_pattern(pattern_id, self.program.get_int_type());
+            }
+            Pattern::List(_) | Pattern::Cons(..) => {
+                let ty = self.program.get_list_type(
+                    self.type_info_provider
+                        .type_var_generator
+                        .get_new_type_var(),
+                );
+                self.type_store.initialize_pattern(pattern_id, ty);
+            }
             Pattern::Record(typedef_id, fields) => {
                 let record_type_info = self.type_info_provider.get_record_type_info(typedef_id);
                 if record_type_info.field_types.len() != fields.len() {
@@ -4,14 +4,17 @@ use crate::common::FunctionTypeInfo;
 use crate::error::Error;
 use crate::error::TypecheckError;
 use crate::expression_checker::ExpressionChecker;
+use crate::hole_checker::HoleChecker;
 use crate::instance_resolver::check_conflicts;
 use crate::instance_resolver::check_instance_dependencies;
+use crate::multi_param_constraint_checker::MultiParamConstraintChecker;
 use crate::pattern_checker::PatternChecker;
 use crate::type_info_provider::TypeInfoProvider;
 use crate::type_store::TypeStore;
 use crate::type_store_initializer::TypeStoreInitializer;
 use crate::undefined_var_checker::UndefinedVarChecker;
 use crate::util::create_general_function_type;
+use crate::util::process_multi_param_constraints;
 use crate::util::process_type_signature;
 use siko_ir::class::ClassId;
 use siko_ir::data::TypeDef;
@@ -24,20 +27,43 @@ use siko_ir::function::Function;
 use siko_ir::function::FunctionId;
 use siko_ir::function::FunctionInfo;
 use siko_ir::function::NamedFunctionKind;
+use siko_ir::function_dep_processor::FunctionDependencyProcessor;
 use siko_ir::program::Program;
+use siko_ir::substitution::Constraint;
 use siko_ir::type_var_generator::TypeVarGenerator;
 use siko_ir::types::BaseType;
 use siko_ir::types::Type;
 use siko_ir::unifier::Unifier;
 use siko_ir::walker::walk_expr;
+use siko_util::dependency_processor::DependencyCollector;
 use siko_util::dependency_processor::DependencyGroup;
+use siko_util::durations::Durations;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+fn get_dep_group_name(group: &DependencyGroup<FunctionId>, program: &Program) -> String {
+    let names: Vec<_> = group
+        .items
+        .iter()
+        .take(3)
+        .map(|id| format!("{}", program.functions.get(id).info))
+        .collect();
+    if group.items.len() > names.len() {
+        format!("{}, ...", names.join(", "))
+    } else {
+        names.join(", ")
+    }
+}
 
-pub struct Typechecker {}
+pub struct Typechecker {
+    main_required: bool,
+}
 
 impl Typechecker {
-    pub fn new() -> Typechecker {
-        Typechecker {}
+    pub fn new(main_required: bool) -> Typechecker {
+        Typechecker {
+            main_required: main_required,
+        }
     }
 
     fn process_derived_instances(
@@ -330,7 +356,12 @@ impl Typechecker {
                 let err = TypecheckError::CyclicClassDependencies(class.location_id, path);
                 errors.push(err);
             }
-            let ty = ty.add_constraints(&class.constraints);
+            // the type of a multi parameter class is the tuple of its args
+            let ty = if class.is_multi_param() {
+                ty
+            } else {
+                ty.add_constraints(&class.constraints)
+            };
             // println!("class type {}", ty);
             class_types.insert(*class_id, ty);
         }
@@ -338,11 +369,23 @@ impl Typechecker {
             let instance_ty =
                 process_type_signature(instance.type_signature, program, type_var_generator);
             let instance_ty = instance_ty.remove_fixed_types();
+            let multi_param_constraints = process_multi_param_constraints(
+                &instance.multi_param_constraints,
+                program,
+                type_var_generator,
+            )
+            .into_iter()
+            .map(|constraint| Constraint {
+                class_id: constraint.class_id,
+                ty: constraint.ty.remove_fixed_types(),
+            })
+            .collect();
 
             program.instance_resolver.add_user_defined(
                 instance.class_id,
                 instance_ty,
                 *instance_id,
+                multi_param_constraints,
                 instance.location_id,
             );
         }
@@ -364,6 +407,7 @@ impl Typechecker {
             typed: false,
             result: result_type,
             function_type: func_type,
+            multi_param_constraints: Vec::new(),
             body: Some(body),
         };
 
@@ -378,172 +422,210 @@ impl Typechecker {
         type_info_provider: &mut TypeInfoProvider,
     ) {
         for (id, function) in &program.functions.items {
-            match &function.info {
-                FunctionInfo::RecordConstructor(i) => {
-                    let record = program.typedefs.get(&i.type_id).get_record();
-                    let mut record_type_info = type_info_provider.get_record_type_info(&i.type_id);
-                    let mut func_args = Vec::new();
-
-                    let (func_type, result_type) = create_general_function_type(
-                        &mut func_args,
-                        record.fields.len(),
-                        type_var_generator,
-                    );
-
-                    let mut func_type_info = FunctionTypeInfo {
-                        displayed_name: format!("{}_ctor", record.name),
-                        args: func_args.clone(),
-                        typed: true,
-                        result: result_type.clone(),
-                        function_type: func_type,
-                        body: None,
-                    };
-
-                    let count = record_type_info.field_types.len();
-                    for index in 0..count {
-                        let field_type = &record_type_info.field_types[index];
-                        let mut unifier = Unifier::new(type_var_generator.clone());
-                        let arg_type = &func_args[index];
-                        unifier
-                            .unify(&field_type.0, arg_type)
-                            .expect("Unify failed");
-                        func_type_info.apply(&unifier);
-                        record_type_info.apply(&unifier);
-                    }
+            self.register_function(
+                id,
+                function,
+                program,
+                type_var_generator,
+                errors,
+                type_info_provider,
+            );
+        }
+    }
 
+    fn register_function(
+        &self,
+        id: &FunctionId,
+        function: &Function,
+        program: &Program,
+        type_var_generator: &mut TypeVarGenerator,
+        errors: &mut Vec<TypecheckError>,
+        type_info_provider: &mut TypeInfoProvider,
+    ) {
+        match &function.info {
+            FunctionInfo::RecordConstructor(i) => {
+                let record = program.typedefs.get(&i.type_id).get_record();
+                let mut record_type_info = type_info_provider.get_record_type_info(&i.type_id);
+                let mut func_args = Vec::new();
+
+                let (func_type, result_type) = create_general_function_type(
+                    &mut func_args,
+                    record.fields.len(),
+                    type_var_generator,
+                );
+
+                let mut func_type_info = FunctionTypeInfo {
+                    displayed_name: format!("{}_ctor", record.name),
+                    args: func_args.clone(),
+                    typed: true,
+                    result: result_type.clone(),
+                    function_type: func_type,
+                    multi_param_constraints: Vec::new(),
+                    body: None,
+                };
+
+                let count = record_type_info.field_types.len();
+                for index in 0..count {
+                    let field_type = &record_type_info.field_types[index];
                     let mut unifier = Unifier::new(type_var_generator.clone());
+                    let arg_type = &func_args[index];
                     unifier
-                        .unify(&record_type_info.record_type, &result_type)
+                        .unify(&field_type.0, arg_type)
                         .expect("Unify failed");
-
                     func_type_info.apply(&unifier);
+                    record_type_info.apply(&unifier);
+                }
 
-                    type_info_provider
-                        .function_type_info_store
-                        .add(*id, func_type_info);
+                let mut unifier = Unifier::new(type_var_generator.clone());
+                unifier
+                    .unify(&record_type_info.record_type, &result_type)
+                    .expect("Unify failed");
+
+                func_type_info.apply(&unifier);
+
+                type_info_provider
+                    .function_type_info_store
+                    .add(*id, func_type_info);
+            }
+            FunctionInfo::VariantConstructor(i) => {
+                let adt = program.typedefs.get(&i.type_id).get_adt();
+                let mut adt_type_info = type_info_provider.get_adt_type_info(&i.type_id);
+                let mut variant_type_info = adt_type_info.variant_types[i.index].clone();
+
+                let mut func_args = Vec::new();
+
+                let (func_type, result_type) = create_general_function_type(
+                    &mut func_args,
+                    variant_type_info.item_types.len(),
+                    type_var_generator,
+                );
+
+                let mut func_type_info = FunctionTypeInfo {
+                    displayed_name: format!("{}/{}_ctor", adt.name, adt.variants[i.index].name),
+                    args: func_args.clone(),
+                    typed: true,
+                    result: result_type.clone(),
+                    function_type: func_type,
+                    multi_param_constraints: Vec::new(),
+                    body: None,
+                };
+
+                let count = variant_type_info.item_types.len();
+                for index in 0..count {
+                    let item_type = &variant_type_info.item_types[index];
+                    let mut unifier = Unifier::new(type_var_generator.clone());
+                    let arg_type = &func_type_info.args[index];
+                    unifier.unify(&item_type.0, arg_type).expect("Unify failed");
+                    func_type_info.apply(&unifier);
+                    variant_type_info.apply(&unifier);
+                    adt_type_info.apply(&unifier);
                 }
-                FunctionInfo::VariantConstructor(i) => {
-                    let adt = program.typedefs.get(&i.type_id).get_adt();
-                    let mut adt_type_info = type_info_provider.get_adt_type_info(&i.type_id);
-                    let mut variant_type_info = adt_type_info.variant_types[i.index].clone();
+
+                let mut unifier = Unifier::new(type_var_generator.clone());
+                unifier
+                    .unify(&adt_type_info.adt_type, &func_type_info.result)
+                    .expect("Unify failed");
+
+                func_type_info.apply(&unifier);
+                type_info_provider
+                    .function_type_info_store
+                    .add(*id, func_type_info);
+            }
+            FunctionInfo::LocalFunction(i) => {
+                let displayed_name = format!("{}", function.info);
+                let func_type_info = self.register_untyped_function(
+                    displayed_name,
+                    function,
+                    i.body,
+                    type_var_generator,
+                );
+                type_info_provider
+                    .function_type_info_store
+                    .add(*id, func_type_info);
+            }
+            FunctionInfo::Lambda(i) => {
+                let displayed_name = format!("{}", function.info);
+                let func_type_info = self.register_untyped_function(
+                    displayed_name,
+                    function,
+                    i.body,
+                    type_var_generator,
+                );
+                type_info_provider
+                    .function_type_info_store
+                    .add(*id, func_type_info);
+            }
+            FunctionInfo::NamedFunction(i) => match i.type_signature {
+                Some(type_signature) => {
+                    let signature_ty =
+                        process_type_signature(type_signature, program, type_var_generator);
 
                     let mut func_args = Vec::new();
 
                     let (func_type, result_type) = create_general_function_type(
                         &mut func_args,
-                        variant_type_info.item_types.len(),
+                        function.arg_locations.len(),
+                        type_var_generator,
+                    );
+
+                    let is_member = i.kind != NamedFunctionKind::Free;
+
+                    let multi_param_constraints = process_multi_param_constraints(
+                        &i.multi_param_constraints,
+                        program,
                         type_var_generator,
                     );
 
                     let mut func_type_info = FunctionTypeInfo {
-                        displayed_name: format!("{}/{}_ctor", adt.name, adt.variants[i.index].name),
+                        displayed_name: i.name.clone(),
                         args: func_args.clone(),
                         typed: true,
                         result: result_type.clone(),
                         function_type: func_type,
-                        body: None,
+                        multi_param_constraints: multi_param_constraints,
+                        body: i.body,
                     };
 
-                    let count = variant_type_info.item_types.len();
-                    for index in 0..count {
-                        let item_type = &variant_type_info.item_types[index];
-                        let mut unifier = Unifier::new(type_var_generator.clone());
-                        let arg_type = &func_type_info.args[index];
-                        unifier.unify(&item_type.0, arg_type).expect("Unify failed");
+                    let mut unifier = Unifier::new(type_var_generator.clone());
+                    if unifier
+                        .unify(&signature_ty, &func_type_info.function_type)
+                        .is_err()
+                    {
+                        let err = TypecheckError::FunctionArgAndSignatureMismatch(
+                            i.name.clone(),
+                            func_args.len(),
+                            signature_ty.get_arg_count(),
+                            i.location_id,
+                            is_member,
+                        );
+                        errors.push(err);
+                    } else {
                         func_type_info.apply(&unifier);
-                        variant_type_info.apply(&unifier);
-                        adt_type_info.apply(&unifier);
                     }
-
-                    let mut unifier = Unifier::new(type_var_generator.clone());
-                    unifier
-                        .unify(&adt_type_info.adt_type, &func_type_info.result)
-                        .expect("Unify failed");
-
-                    func_type_info.apply(&unifier);
-                    type_info_provider
-                        .function_type_info_store
-                        .add(*id, func_type_info);
-                }
-                FunctionInfo::Lambda(i) => {
-                    let displayed_name = format!("{}", function.info);
-                    let func_type_info = self.register_untyped_function(
-                        displayed_name,
-                        function,
-                        i.body,
-                        type_var_generator,
-                    );
                     type_info_provider
                         .function_type_info_store
                         .add(*id, func_type_info);
                 }
-                FunctionInfo::NamedFunction(i) => match i.type_signature {
-                    Some(type_signature) => {
-                        let signature_ty =
-                            process_type_signature(type_signature, program, type_var_generator);
-
-                        let mut func_args = Vec::new();
-
-                        let (func_type, result_type) = create_general_function_type(
-                            &mut func_args,
-                            function.arg_locations.len(),
+                None => match i.body {
+                    Some(body) => {
+                        let displayed_name = format!("{}", function.info);
+                        let func_type_info = self.register_untyped_function(
+                            displayed_name,
+                            function,
+                            body,
                             type_var_generator,
                         );
-
-                        let is_member = i.kind != NamedFunctionKind::Free;
-
-                        let mut func_type_info = FunctionTypeInfo {
-                            displayed_name: i.name.clone(),
-                            args: func_args.clone(),
-                            typed: true,
-                            result: result_type.clone(),
-                            function_type: func_type,
-                            body: i.body,
-                        };
-
-                        let mut unifier = Unifier::new(type_var_generator.clone());
-                        if unifier
-                            .unify(&signature_ty, &func_type_info.function_type)
-                            .is_err()
-                        {
-                            let err = TypecheckError::FunctionArgAndSignatureMismatch(
-                                i.name.clone(),
-                                func_args.len(),
-                                signature_ty.get_arg_count(),
-                                i.location_id,
-                                is_member,
-                            );
-                            errors.push(err);
-                        } else {
-                            func_type_info.apply(&unifier);
-                        }
                         type_info_provider
                             .function_type_info_store
                             .add(*id, func_type_info);
                     }
-                    None => match i.body {
-                        Some(body) => {
-                            let displayed_name = format!("{}", function.info);
-                            let func_type_info = self.register_untyped_function(
-                                displayed_name,
-                                function,
-                                body,
-                                type_var_generator,
-                            );
-                            type_info_provider
-                                .function_type_info_store
-                                .add(*id, func_type_info);
-                        }
-                        None => {
-                            let err = TypecheckError::UntypedExternFunction(
-                                i.name.clone(),
-                                i.location_id,
-                            );
-                            errors.push(err)
-                        }
-                    },
+                    None => {
+                        let err =
+                            TypecheckError::UntypedExternFunction(i.name.clone(), i.location_id);
+                        errors.push(err)
+                    }
                 },
-            }
+            },
         }
     }
 
@@ -556,7 +638,9 @@ impl Typechecker {
         if let Some(main_id) = program.get_main() {
             let f = program.functions.get(&main_id);
             let main_type_info = type_info_provider.function_type_info_store.get(&main_id);
-            if main_type_info.function_type != Type::Tuple(vec![]) {
+            // a main that never returns, e.g. ends by calling exit, is accepted as well
+            let never = matches!(main_type_info.function_type, Type::Never(_));
+            if main_type_info.function_type != Type::Tuple(vec![]) && !never {
                 let main_type = main_type_info
                     .function_type
                     .get_resolved_type_string(program);
@@ -567,11 +651,53 @@ impl Typechecker {
                     unreachable!();
                 }
             }
-        } else {
+        } else if self.main_required {
             errors.push(TypecheckError::MainNotFound);
         }
     }
 
+    fn check_actors(
+        &self,
+        program: &Program,
+        errors: &mut Vec<TypecheckError>,
+        type_info_provider: &mut TypeInfoProvider,
+    ) {
+        for (_, actor) in &program.actors.items {
+            let actor_type_info = type_info_provider.get_record_type_info(&actor.typedef_id);
+            let state_ty = actor_type_info.field_types[0].0.clone();
+            for handler in &actor.handlers {
+                let protocol = program.protocols.get(&handler.protocol_id);
+                let protocol_type_info =
+                    type_info_provider.get_record_type_info(&protocol.typedef_id);
+                let payload_ty = protocol_type_info.field_types[0].0.clone();
+                let expected_ty = Type::Function(
+                    Box::new(state_ty.clone()),
+                    Box::new(Type::Function(
+                        Box::new(payload_ty),
+                        Box::new(state_ty.clone()),
+                    )),
+                );
+                let handler_type_info =
+                    type_info_provider.get_function_type(&handler.function_id, true);
+                let mut unifier = program.get_unifier();
+                if unifier
+                    .unify(&expected_ty, &handler_type_info.function_type)
+                    .is_err()
+                {
+                    let err = TypecheckError::InvalidProtocolHandler(
+                        protocol.name.clone(),
+                        expected_ty.get_resolved_type_string(program),
+                        handler_type_info
+                            .function_type
+                            .get_resolved_type_string(program),
+                        handler.location_id,
+                    );
+                    errors.push(err);
+                }
+            }
+        }
+    }
+
     fn init_expr_types<'a>(
         &self,
         function_id: &FunctionId,
@@ -651,6 +777,7 @@ impl Typechecker {
         &self,
         function_id: &FunctionId,
         errors: &'a mut Vec<TypecheckError>,
+        group: &'a DependencyGroup<FunctionId>,
         type_store: &'a mut TypeStore,
         type_info_provider: &'a mut TypeInfoProvider,
         program: &'a mut Program,
@@ -658,7 +785,7 @@ impl Typechecker {
         let function_type_info = type_info_provider.function_type_info_store.get(function_id);
         let body = function_type_info.body.expect("body not found");
         let mut class_constraint_checker =
-            ClassConstraintChecker::new(program, type_store, errors, type_info_provider);
+            ClassConstraintChecker::new(program, group, type_store, errors, type_info_provider);
         walk_expr(&body, &mut class_constraint_checker);
     }
 
@@ -670,6 +797,8 @@ impl Typechecker {
         type_info_provider: &'a mut TypeInfoProvider,
         program: &'a mut Program,
     ) {
+        let error_count = errors.len();
+
         for function in &group.items {
             self.init_expr_types(
                 function,
@@ -692,7 +821,28 @@ impl Typechecker {
             );
         }
 
-        if !errors.is_empty() {
+        if errors.len() > error_count {
+            return;
+        }
+
+        let mut multi_param_constraint_checker = MultiParamConstraintChecker::new(
+            program,
+            group,
+            type_store,
+            type_info_provider,
+            errors,
+        );
+        multi_param_constraint_checker.check();
+
+        if errors.len() > error_count {
+            return;
+        }
+
+        let mut hole_checker =
+            HoleChecker::new(program, group, type_store, type_info_provider, errors);
+        hole_checker.check();
+
+        if errors.len() > error_count {
             return;
         }
 
@@ -700,7 +850,7 @@ impl Typechecker {
             self.check_undefined_vars(function, errors, type_store, type_info_provider, program);
         }
 
-        if !errors.is_empty() {
+        if errors.len() > error_count {
             return;
         }
 
@@ -708,12 +858,19 @@ impl Typechecker {
             self.check_patterns(function, errors, type_info_provider, program);
         }
 
-        if !errors.is_empty() {
+        if errors.len() > error_count {
             return;
         }
 
         for function in &group.items {
-            self.check_class_constraints(function, errors, type_store, type_info_provider, program);
+            self.check_class_constraints(
+                function,
+                errors,
+                group,
+                type_store,
+                type_info_provider,
+                program,
+            );
         }
     }
 
@@ -729,24 +886,43 @@ impl Typechecker {
                 program,
                 &mut type_info_provider.type_var_generator,
             );
-            let class_member_type_info = ClassMemberTypeInfo { ty: ty.clone() };
-            program.class_member_types.insert(
-                *class_member_id,
-                (
-                    ty,
-                    class_types
-                        .get(&class_member.class_id)
-                        .expect("Class type not found")
-                        .clone(),
-                ),
-            );
+            let class_ty = class_types
+                .get(&class_member.class_id)
+                .expect("Class type not found")
+                .clone();
+            let multi_param_constraint =
+                if program.classes.get(&class_member.class_id).is_multi_param() {
+                    Some(Constraint {
+                        class_id: class_member.class_id,
+                        ty: class_ty.clone(),
+                    })
+                } else {
+                    None
+                };
+            let class_member_type_info = ClassMemberTypeInfo {
+                ty: ty.clone(),
+                multi_param_constraint: multi_param_constraint,
+            };
+            program
+                .class_member_types
+                .insert(*class_member_id, (ty, class_ty));
             type_info_provider
                 .class_member_type_info_map
                 .insert(*class_member_id, class_member_type_info);
         }
     }
 
-    pub fn check(&self, program: &mut Program) -> Result<(), Error> {
+    pub fn check(&self, program: &mut Program, durations: &mut Durations) -> Result<(), Error> {
+        self.check_program(program, durations).map(|_| ())
+    }
+
+    // Returns the collected type information, functions added to the program later
+    // can be checked against it with check_functions.
+    pub fn check_program(
+        &self,
+        program: &mut Program,
+        durations: &mut Durations,
+    ) -> Result<TypeInfoProvider, Error> {
         let mut errors = Vec::new();
         let mut type_var_generator = program.type_var_generator.clone();
         let mut type_info_provider = TypeInfoProvider::new(type_var_generator.clone());
@@ -800,17 +976,49 @@ impl Typechecker {
             return Err(Error::typecheck_err(errors));
         }
 
-        for group in &ordered_dep_groups {
-            let mut type_store = TypeStore::new();
-            self.process_dep_group(
-                group,
-                &mut errors,
-                &mut type_store,
-                &mut type_info_provider,
-                program,
-            );
-            //type_store.dump(program);
-            type_store.save_expr_and_pattern_types(program);
+        let group_dependencies: Vec<BTreeSet<FunctionId>> = {
+            let dep_processor = FunctionDependencyProcessor::new(program);
+            ordered_dep_groups
+                .iter()
+                .map(|group| {
+                    group
+                        .items
+                        .iter()
+                        .flat_map(|function| dep_processor.collect(*function))
+                        .collect()
+                })
+                .collect()
+        };
+
+        let mut failed_functions = BTreeSet::new();
+
+        for (group, dependencies) in ordered_dep_groups.iter().zip(group_dependencies) {
+            // the inferred types of untyped functions that failed to typecheck are
+            // unreliable, checking their users would only report follow-up errors
+            if dependencies
+                .iter()
+                .any(|dep| failed_functions.contains(dep))
+            {
+                failed_functions.extend(group.items.iter().cloned());
+                continue;
+            }
+            let error_count = errors.len();
+            let name = get_dep_group_name(group, program);
+            durations.measure(&name, |_| {
+                let mut type_store = TypeStore::new();
+                self.process_dep_group(
+                    group,
+                    &mut errors,
+                    &mut type_store,
+                    &mut type_info_provider,
+                    program,
+                );
+                //type_store.dump(program);
+                type_store.save_expr_and_pattern_types(program);
+            });
+            if errors.len() > error_count {
+                failed_functions.extend(group.items.iter().cloned());
+            }
         }
 
         if !errors.is_empty() {
@@ -819,6 +1027,8 @@ impl Typechecker {
 
         self.check_main(program, &mut errors, &type_info_provider);
 
+        self.check_actors(program, &mut errors, &mut type_info_provider);
+
         if !errors.is_empty() {
             return Err(Error::typecheck_err(errors));
         }
@@ -829,14 +1039,74 @@ impl Typechecker {
             .function_type_info_store
             .save_function_types(program);
 
-        std::mem::swap(
-            &mut program.adt_type_info_map,
-            &mut type_info_provider.adt_type_info_map,
-        );
-        std::mem::swap(
-            &mut program.record_type_info_map,
-            &mut type_info_provider.record_type_info_map,
-        );
+        program.adt_type_info_map = type_info_provider.adt_type_info_map.clone();
+        program.record_type_info_map = type_info_provider.record_type_info_map.clone();
+
+        Ok(type_info_provider)
+    }
+
+    // Checks functions added to an already checked program, the existing functions
+    // cannot depend on them. Nothing is recorded in the program if checking fails.
+    pub fn check_functions(
+        &self,
+        program: &mut Program,
+        type_info_provider: &mut TypeInfoProvider,
+        functions: &BTreeSet<FunctionId>,
+    ) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        let mut type_var_generator = type_info_provider.type_var_generator.clone();
+
+        for id in functions {
+            let function = program.functions.get(id);
+            self.register_function(
+                id,
+                function,
+                program,
+                &mut type_var_generator,
+                &mut errors,
+                type_info_provider,
+            );
+        }
+
+        if errors.is_empty() {
+            program.calculate_function_dependencies();
+
+            let groups: Vec<_> = program
+                .function_dependency_groups
+                .iter()
+                .filter(|group| group.items.iter().all(|id| functions.contains(id)))
+                .cloned()
+                .collect();
+
+            for group in &groups {
+                let mut type_store = TypeStore::new();
+                self.process_dep_group(
+                    group,
+                    &mut errors,
+                    &mut type_store,
+                    type_info_provider,
+                    program,
+                );
+                if !errors.is_empty() {
+                    break;
+                }
+                type_store.save_expr_and_pattern_types(program);
+            }
+        }
+
+        if !errors.is_empty() {
+            for id in functions {
+                type_info_provider.function_type_info_store.remove(id);
+            }
+            return Err(Error::typecheck_err(errors));
+        }
+
+        for id in functions {
+            let function_type_info = type_info_provider.function_type_info_store.get(id);
+            program
+                .function_types
+                .insert(*id, function_type_info.function_type.clone());
+        }
 
         Ok(())
     }
@@ -1,6 +1,8 @@
 use crate::common::FunctionTypeInfo;
 use crate::error::TypecheckError;
+use siko_ir::class::MultiParamConstraint;
 use siko_ir::program::Program;
+use siko_ir::substitution::Constraint;
 use siko_ir::type_signature::TypeSignature;
 use siko_ir::type_signature::TypeSignatureId;
 use siko_ir::type_var_generator::TypeVarGenerator;
@@ -40,6 +42,7 @@ pub fn create_general_function_type_info(
         typed: false,
         result: result_type,
         function_type: function_type,
+        multi_param_constraints: Vec::new(),
         body: None,
     }
 }
@@ -76,6 +79,14 @@ pub fn process_type_signature(
             constraints.sort();
             Type::FixedTypeArg(name.clone(), *index, constraints)
         }
+        TypeSignature::Applied(head, items) => {
+            let head_ty = process_type_signature(*head, program, type_var_generator);
+            let items: Vec<_> = items
+                .iter()
+                .map(|item| process_type_signature(*item, program, type_var_generator))
+                .collect();
+            Type::apply_args(head_ty, items)
+        }
         TypeSignature::Variant(..) => panic!("Variant should not appear here"),
         TypeSignature::Wildcard => type_var_generator.get_new_type_var(),
         TypeSignature::Never => Type::Never(type_var_generator.get_new_index()),
@@ -86,6 +97,30 @@ pub fn process_type_signature(
     }
 }
 
+pub fn process_multi_param_constraints(
+    constraints: &Vec<MultiParamConstraint>,
+    program: &Program,
+    type_var_generator: &mut TypeVarGenerator,
+) -> Vec<Constraint> {
+    constraints
+        .iter()
+        .map(|constraint| Constraint {
+            class_id: constraint.class_id,
+            ty: process_type_signature(constraint.type_signature, program, type_var_generator),
+        })
+        .collect()
+}
+
+// selects the given args from the tuple type of a multi parameter class
+pub fn select_class_args(ty: &Type, indices: &Vec<usize>) -> Type {
+    match ty {
+        Type::Tuple(args) => {
+            Type::Tuple(indices.iter().map(|index| args[*index].clone()).collect())
+        }
+        _ => unreachable!(),
+    }
+}
+
 pub fn function_argument_mismatch(
     program: &Program,
     func_type: &Type,
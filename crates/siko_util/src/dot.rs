@@ -2,6 +2,23 @@ use crate::Counter;
 use std::fs::File;
 use std::io::Result as IoResult;
 use std::io::Write;
+use std::path::Path;
+
+// characters with special meaning in record labels
+fn escape(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '{' | '}' | '|' | '<' | '>' | '"' | '\\' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '\n' => result.push_str("\\n"),
+            c => result.push(c),
+        }
+    }
+    result
+}
 
 pub struct Graph {
     pub name: String,
@@ -45,10 +62,9 @@ impl Graph {
         self.edges.push(edge);
     }
 
-    pub fn generate_dot(&self) -> IoResult<()> {
-        let dots_folder = "dots";
-        let _ = std::fs::create_dir_all(dots_folder);
-        let filename = format!("{}/{}.dot", dots_folder, self.name);
+    pub fn generate_dot(&self, dir: &Path) -> IoResult<()> {
+        std::fs::create_dir_all(dir)?;
+        let filename = dir.join(format!("{}.dot", self.name));
         let mut output = File::create(filename)?;
         write!(output, "digraph D {{\n")?;
         write!(
@@ -57,12 +73,23 @@ impl Graph {
         )?;
 
         for node in &self.nodes {
-            let elements = node.elements.join("|");
-            write!(
-                output,
-                "node{} [label=\"{{{}|{}}}\"]\n",
-                node.index, node.name, elements
-            )?;
+            let elements: Vec<_> = node.elements.iter().map(|e| escape(e)).collect();
+            if elements.is_empty() {
+                write!(
+                    output,
+                    "node{} [label=\"{{{}}}\"]\n",
+                    node.index,
+                    escape(&node.name)
+                )?;
+            } else {
+                write!(
+                    output,
+                    "node{} [label=\"{{{}|{}}}\"]\n",
+                    node.index,
+                    escape(&node.name),
+                    elements.join("|")
+                )?;
+            }
         }
 
         for edge in &self.edges {
@@ -70,7 +97,9 @@ impl Graph {
                 write!(
                     output,
                     "node{} -> node{} [label=\"{}\"]\n",
-                    edge.from, edge.to, name
+                    edge.from,
+                    edge.to,
+                    escape(name)
                 )?;
             } else {
                 write!(output, "node{} -> node{} \n", edge.from, edge.to)?;
@@ -0,0 +1,144 @@
+use std::time::Duration;
+use std::time::Instant;
+
+const MAX_TABLE_CHILDREN: usize = 10;
+
+pub struct Measurement {
+    pub name: String,
+    pub duration: Duration,
+    pub children: Vec<Measurement>,
+}
+
+pub struct Durations {
+    pub measurements: Vec<Measurement>,
+}
+
+fn escape_json(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+impl Durations {
+    pub fn new() -> Durations {
+        Durations {
+            measurements: Vec::new(),
+        }
+    }
+
+    pub fn measure<T, F>(&mut self, name: &str, f: F) -> T
+    where
+        F: FnOnce(&mut Durations) -> T,
+    {
+        let mut children = Durations::new();
+        let start = Instant::now();
+        let result = f(&mut children);
+        self.measurements.push(Measurement {
+            name: name.to_string(),
+            duration: start.elapsed(),
+            children: children.measurements,
+        });
+        result
+    }
+
+    pub fn total(&self) -> Duration {
+        self.measurements.iter().map(|m| m.duration).sum()
+    }
+
+    fn add_rows(
+        measurements: &[Measurement],
+        total: Duration,
+        depth: usize,
+        rows: &mut Vec<(String, String, String)>,
+    ) {
+        let mut sorted: Vec<_> = measurements.iter().collect();
+        // long lists of nested items are cut to the slowest ones
+        let shown = if depth > 0 && sorted.len() > MAX_TABLE_CHILDREN {
+            sorted.sort_by(|a, b| b.duration.cmp(&a.duration));
+            MAX_TABLE_CHILDREN
+        } else {
+            sorted.len()
+        };
+        for measurement in sorted.iter().take(shown) {
+            let percent = if total.as_nanos() > 0 {
+                measurement.duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            } else {
+                0.0
+            };
+            rows.push((
+                format!("{}{}", "  ".repeat(depth), measurement.name),
+                format_duration(measurement.duration),
+                format!("{:.1}%", percent),
+            ));
+            Durations::add_rows(&measurement.children, total, depth + 1, rows);
+        }
+        if sorted.len() > shown {
+            let rest: Duration = sorted[shown..].iter().map(|m| m.duration).sum();
+            rows.push((
+                format!("{}... {} more", "  ".repeat(depth), sorted.len() - shown),
+                format_duration(rest),
+                String::new(),
+            ));
+        }
+    }
+
+    pub fn format_table(&self) -> String {
+        let total = self.total();
+        let mut rows = vec![(format!("Phase"), format!("Duration"), format!("Share"))];
+        Durations::add_rows(&self.measurements, total, 0, &mut rows);
+        rows.push((format!("Total"), format_duration(total), format!("100.0%")));
+        let name_width = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+        let duration_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0);
+        let lines: Vec<_> = rows
+            .iter()
+            .map(|(name, duration, share)| {
+                format!(
+                    "{:<nw$}  {:>dw$}  {:>6}",
+                    name,
+                    duration,
+                    share,
+                    nw = name_width,
+                    dw = duration_width
+                )
+                .trim_end()
+                .to_string()
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    fn measurements_to_json(measurements: &[Measurement]) -> String {
+        let items: Vec<_> = measurements
+            .iter()
+            .map(|m| {
+                format!(
+                    "{{\"name\":\"{}\",\"duration_ms\":{},\"children\":{}}}",
+                    escape_json(&m.name),
+                    m.duration.as_secs_f64() * 1000.0,
+                    Durations::measurements_to_json(&m.children)
+                )
+            })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"total_ms\":{},\"phases\":{}}}",
+            self.total().as_secs_f64() * 1000.0,
+            Durations::measurements_to_json(&self.measurements)
+        )
+    }
+}
@@ -6,6 +6,7 @@ use std::rc::Rc;
 
 pub mod dependency_processor;
 pub mod dot;
+pub mod durations;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RcCounter {
@@ -37,13 +37,70 @@ fn process_dir(arg: String, inputs: &mut Vec<(PathBuf, String)>) -> bool {
     true
 }
 
+// A success test case can pass arguments to the program in an args file, one per
+// line, and can expect a different exit code than 0 in an exit_code file.
+fn read_args(tc_dir: &Path) -> Vec<String> {
+    match fs::read_to_string(tc_dir.join("args")) {
+        Ok(content) => content.lines().map(|line| line.to_string()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn read_exit_code(tc_dir: &Path) -> i32 {
+    match fs::read_to_string(tc_dir.join("exit_code")) {
+        Ok(content) => content.trim().parse().expect("invalid exit code"),
+        Err(_) => 0,
+    }
+}
+
+fn run_fmt(sikoc: &str, file: &str, check: bool) -> bool {
+    let mut command = Command::new(sikoc);
+    command.arg("fmt");
+    if check {
+        command.arg("--check");
+    }
+    let output = command
+        .arg(file)
+        .output()
+        .expect("failed to execute process");
+    output.status.success()
+}
+
+// A formatter test case formats a copy of its main.sk and expects the content of its
+// formatted.sk, formatting it again must not change it.
+fn check_fmt_tc(sikoc: &str, tc_dir: &Path, tc_name: &str, comp_dir: &str) -> bool {
+    let input = fs::read_to_string(tc_dir.join("main.sk")).expect("input read failed");
+    let expected =
+        fs::read_to_string(tc_dir.join("formatted.sk")).expect("formatted output read failed");
+    let file = format!("{}/{}.sk", comp_dir, tc_name);
+    fs::write(&file, &input).expect("input write failed");
+    // the check mode fails only for files that would change and leaves them untouched
+    if run_fmt(sikoc, &file, true) != (input == expected) {
+        return false;
+    }
+    if fs::read_to_string(&file).expect("read failed") != input {
+        return false;
+    }
+    for _ in 0..2 {
+        if !run_fmt(sikoc, &file, false) {
+            return false;
+        }
+        if fs::read_to_string(&file).expect("read failed") != expected {
+            return false;
+        }
+    }
+    run_fmt(sikoc, &file, true)
+}
+
 fn print_usage() {
     println!("Usage:");
-    println!("SikoTester SIKOC SIKO_STD COMP_DIR RUST_COMP_DIR SUCCESS_DIRFAIL_DIR PLATFORM");
+    println!(
+        "SikoTester SIKOC SIKO_STD COMP_DIR RUST_COMP_DIR SUCCESS_DIR FAIL_DIR FMT_DIR PLATFORM"
+    );
 }
 
 fn process_args(args: Vec<String>) -> bool {
-    if args.len() != 7 {
+    if args.len() != 8 {
         print_usage();
         return false;
     }
@@ -53,25 +110,35 @@ fn process_args(args: Vec<String>) -> bool {
     let rust_comp_dir = args[3].clone();
     let success_dir = args[4].clone();
     let fail_dir = args[5].clone();
-    let platform = args[6].clone();
+    let fmt_dir = args[6].clone();
+    let platform = args[7].clone();
     let mut success_files = Vec::new();
     process_dir(success_dir, &mut success_files);
     let mut fail_files = Vec::new();
     process_dir(fail_dir, &mut fail_files);
+    let mut fmt_files = Vec::new();
+    process_dir(fmt_dir, &mut fmt_files);
     success_files.sort_by(|a, b| a.1.cmp(&b.1));
     fail_files.sort_by(|a, b| a.1.cmp(&b.1));
+    fmt_files.sort_by(|a, b| a.1.cmp(&b.1));
     let mut success_count = 0;
     let mut fail_count = 0;
     let mut failed_tcs = BTreeSet::new();
     for (s, tc_name) in success_files {
         print!("TC-S: {} ", tc_name);
+        let program_args = read_args(&s);
+        let exit_code = read_exit_code(&s);
+        // test cases can use the comp dir as a scratch directory
         let status = Command::new(sikoc.clone())
             .arg("-s")
             .arg(siko_std.clone())
             .arg(s.clone())
+            .arg("--")
+            .args(&program_args)
+            .env("SIKO_TEST_DIR", &comp_dir)
             .status()
             .expect("failed to execute process");
-        if !status.success() {
+        if status.code() != Some(exit_code) {
             fail_count += 1;
             failed_tcs.insert(tc_name.clone());
             println!("Fail");
@@ -112,9 +179,11 @@ fn process_args(args: Vec<String>) -> bool {
             continue;
         }
         let status = Command::new(rustc_output_file)
+            .args(&program_args)
+            .env("SIKO_TEST_DIR", &comp_dir)
             .status()
             .expect("failed to execute process");
-        if status.success() {
+        if status.code() == Some(exit_code) {
             success_count += 1;
             println!("/OK");
         } else {
@@ -143,6 +212,17 @@ fn process_args(args: Vec<String>) -> bool {
             println!("Fail")
         }
     }
+    for (f, tc_name) in fmt_files {
+        print!("TC-FMT: {} ", tc_name);
+        if check_fmt_tc(&sikoc, &f, &tc_name, &comp_dir) {
+            success_count += 1;
+            println!("OK");
+        } else {
+            fail_count += 1;
+            failed_tcs.insert(tc_name.clone());
+            println!("Fail")
+        }
+    }
 
     println!("Total: {}/{}", success_count + fail_count, fail_count);
     if !failed_tcs.is_empty() {
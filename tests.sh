@@ -8,4 +8,4 @@ mkdir -p comp
 mkdir -p rust_comp
 
 cd siko_tester
-cargo run -- ../siko ../std ../comp ../rust_comp ../tests/success/ ../tests/fail/ linux
+cargo run -- ../siko ../std ../comp ../rust_comp ../tests/success/ ../tests/fail/ ../tests/fmt/ linux